@@ -0,0 +1,30 @@
+/// Checks the models against a Slides discovery document and prints every gap found.
+///
+/// Exits with status 1 if the models miss schemas, fields or enum values of the API.
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "conformance")]
+    {
+        use gslides_tools::conformance::ConformanceChecker;
+        use std::{env, fs};
+
+        let Some(discovery_path) = env::args().nth(1) else {
+            eprintln!("Usage: check_conformance <DISCOVERY_JSON>");
+            std::process::exit(1);
+        };
+
+        let report = ConformanceChecker::new()?.check(&fs::read_to_string(discovery_path)?)?;
+        print!("{}", report);
+        if !report.is_complete() {
+            std::process::exit(1);
+        }
+    }
+
+    #[cfg(not(feature = "conformance"))]
+    {
+        eprintln!(
+            r#"Run with: cargo run --example check_conformance --features "gslides-tools/conformance" -- <DISCOVERY_JSON>"#
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use std::env;
+
+use gslides_tools::cache::PresentationCache;
+use gslides_tools::diff::comparer::ComparerBuilder;
+
+/// Diffs the two most recent cached revisions of a presentation without network access.
+///
+/// The cache is filled by `SlidesClient::get_presentation_cached`.
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("--- Google Slides Cached Revision Diff Example ---");
+
+    let mut args = env::args().skip(1);
+    let (Some(cache_dir), Some(presentation_id)) = (args.next(), args.next()) else {
+        eprintln!("Usage: diff_cached_revisions <CACHE_DIR> <PRESENTATION_ID>");
+        std::process::exit(1);
+    };
+
+    let cache = PresentationCache::new(cache_dir)?;
+    let revisions = cache.list_revisions(&presentation_id)?;
+    println!("Cached revisions of {}:", presentation_id);
+    for revision in &revisions {
+        println!("  {} ({} bytes)", revision.revision_id, revision.size_bytes);
+    }
+
+    let [.., base_revision, changed_revision] = revisions.as_slice() else {
+        println!("At least two cached revisions are needed to diff.");
+        return Ok(());
+    };
+    let base = cache
+        .load(&presentation_id, &base_revision.revision_id)?
+        .ok_or("Base revision was evicted")?;
+    let changed = cache
+        .load(&presentation_id, &changed_revision.revision_id)?
+        .ok_or("Changed revision was evicted")?;
+
+    let comparer = ComparerBuilder::new().set_base(base).build()?;
+    let result = comparer.compare(&changed)?;
+
+    println!(
+        "\n-- Changes from {} to {} --\n",
+        base_revision.revision_id, changed_revision.revision_id
+    );
+    println!("{}", result.get_readable_diff()?);
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "emulator")]
+    {
+        use gslides_tools::emulator::SlidesEmulator;
+        use std::env;
+        use std::net::SocketAddr;
+
+        // Serves the given fixtures (default: the two bundled ones) until Ctrl-C.
+        let mut fixtures: Vec<String> = env::args().skip(1).collect();
+        if fixtures.is_empty() {
+            fixtures = vec![
+                "base_presentation.json".to_string(),
+                "changed_presentation.json".to_string(),
+            ];
+        }
+
+        let mut builder = SlidesEmulator::builder()
+            .set_address(SocketAddr::from(([127, 0, 0, 1], 8085)))
+            .set_require_auth(false);
+        for fixture in &fixtures {
+            println!("Loading fixture: {}", fixture);
+            builder = builder.add_fixture_file(fixture)?;
+        }
+        let emulator = builder.start().await?;
+
+        println!("Slides emulator listening on {}", emulator.base_url());
+        println!(
+            "Try: curl {}/v1/presentations/<PRESENTATION_ID>",
+            emulator.base_url()
+        );
+        tokio::signal::ctrl_c().await?;
+    }
+
+    #[cfg(not(feature = "emulator"))]
+    {
+        eprintln!(
+            r#"Run with: cargo run --example emulator --features "gslides-tools/emulator" -- [FIXTURE.json ...]"#
+        );
+    }
+
+    Ok(())
+}
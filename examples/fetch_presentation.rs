@@ -3,8 +3,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     #[cfg(feature = "yup-oauth2")]
     {
         use gslides_tools::{
-            client,
+            auth::ServiceAccountTokenProvider,
             errors::SlidesApiError,
+            sink::FileDumpSink,
+            SlidesClient,
             // Import specific element kinds if you want to match on them, otherwise Debug print works
             // models::elements::{PageElement, PageElementKind},
         };
@@ -31,9 +33,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let output_filename = "output.json";
 
         println!("Attempting to fetch presentation: {}", presentation_id);
-        let http_client = reqwest::Client::new();
+        // Capture the raw response (changed_presentation.json / deserialization_error.json)
+        let client = SlidesClient::builder()
+            .set_token_provider(ServiceAccountTokenProvider::from_env().await?)
+            .set_response_sink(FileDumpSink::new())
+            .build()?;
 
-        match client::get_presentation_sa(presentation_id, &http_client).await {
+        match client.get_presentation(presentation_id).await {
             Ok(presentation) => {
                 println!("\nSuccessfully fetched presentation!");
                 println!(
@@ -136,6 +142,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         eprintln!("  JSON Parsing Error: {}", err);
                         eprintln!("  (Check deserialization_error.json if it was created)");
                     }
+                    SlidesApiError::PresentationParse(problem) => {
+                        eprintln!("  Presentation Parsing Error: {}", problem);
+                        eprintln!("  (Check deserialization_error.json if it was created)");
+                    }
                     SlidesApiError::ApiError { status, message } => {
                         eprintln!("  API Error ({}): {}", status, message)
                     }
@@ -151,6 +161,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     err
                 ),
                     SlidesApiError::IoError(err) => eprintln!("  I/O Error: {}", err),
+                    SlidesApiError::RetryFailed {
+                        attempts,
+                        last_status,
+                        source,
+                    } => eprintln!(
+                        "  Gave up after {} attempts (last status: {:?}): {}",
+                        attempts, last_status, source
+                    ),
                     SlidesApiError::Unknown(msg) => eprintln!("  Unknown Error: {}", msg),
                 }
             }
@@ -0,0 +1,38 @@
+use std::{env, fs};
+
+use gslides_tools::models::requests::BatchUpdatePresentationRequest;
+use gslides_tools::template::TemplateFiller;
+use gslides_tools::Presentation;
+
+/// Fills a template presentation from a JSON data file without network access.
+///
+/// Writes the `batchUpdate` body that fills a copy of the template to stdout, and the
+/// filled presentation to `filled_presentation.json`.
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = env::args().skip(1);
+    let (Some(template_path), Some(data_path)) = (args.next(), args.next()) else {
+        eprintln!("Usage: fill_template <TEMPLATE_JSON> <DATA_JSON>");
+        std::process::exit(1);
+    };
+
+    let template: Presentation = serde_json::from_str(&fs::read_to_string(template_path)?)?;
+    let data: serde_json::Value = serde_json::from_str(&fs::read_to_string(data_path)?)?;
+
+    let filled = TemplateFiller::new().fill(&template, &data)?;
+    eprintln!(
+        "Filled {} slides with {} requests.",
+        filled.presentation.slides.as_ref().map_or(0, Vec::len),
+        filled.requests.len()
+    );
+
+    fs::write(
+        "filled_presentation.json",
+        serde_json::to_string_pretty(&filled.presentation)?,
+    )?;
+    let body = BatchUpdatePresentationRequest {
+        requests: filled.requests,
+        write_control: None,
+    };
+    println!("{}", serde_json::to_string_pretty(&body)?);
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use std::{env, fs::File};
+
+use gslides_tools::merge::{read_csv_rows, read_json_rows, MailMerge, MANIFEST_FILE_NAME};
+use gslides_tools::Presentation;
+
+/// Generates one deck per CSV/JSON row from a template JSON file, fully offline.
+///
+/// Each deck is written to the output directory as presentation JSON plus one SVG per
+/// slide, next to a manifest of generated decks and failed rows.
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = env::args().skip(1);
+    let (Some(template_path), Some(rows_path), Some(out_dir)) =
+        (args.next(), args.next(), args.next())
+    else {
+        eprintln!("Usage: mail_merge <TEMPLATE_JSON> <ROWS.csv|ROWS.json> <OUT_DIR> [NAME_COLUMN]");
+        std::process::exit(1);
+    };
+
+    let template: Presentation = serde_json::from_reader(File::open(template_path)?)?;
+    let rows = if rows_path.ends_with(".json") {
+        read_json_rows(File::open(&rows_path)?)?
+    } else {
+        read_csv_rows(File::open(&rows_path)?)?
+    };
+
+    let mut merge = MailMerge::new(template);
+    if let Some(name_column) = args.next() {
+        merge = merge.set_name_column(name_column);
+    }
+    let manifest = merge.write_offline(&rows, &out_dir)?;
+
+    println!(
+        "Generated {} decks into {} ({} failed rows, see {}).",
+        manifest.generated.len(),
+        out_dir,
+        manifest.failed.len(),
+        MANIFEST_FILE_NAME
+    );
+    for failed in &manifest.failed {
+        println!(
+            "  row {} ({}): {}",
+            failed.row_number, failed.name, failed.error
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use std::{env, fs};
+
+use gslides_tools::converters::markdown::{
+    extract_text_from_presentation_with_options, TextExtractionOptions,
+};
+use gslides_tools::converters::svg::{convert_presentation_to_svg_with_mode, SvgRenderMode};
+use gslides_tools::Presentation;
+
+/// Prints a presentation's slide text with speaker notes as Markdown, and renders the notes
+/// pages to `notes_page_<N>.svg` in the given output directory.
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = env::args().skip(1);
+    let (Some(presentation_path), Some(out_dir)) = (args.next(), args.next()) else {
+        eprintln!("Usage: speaker_notes <PRESENTATION_JSON> <OUT_DIR>");
+        std::process::exit(1);
+    };
+
+    let presentation: Presentation = serde_json::from_str(&fs::read_to_string(presentation_path)?)?;
+
+    let options = TextExtractionOptions {
+        include_speaker_notes: true,
+    };
+    println!(
+        "{}",
+        extract_text_from_presentation_with_options(&presentation, &options)
+    );
+
+    fs::create_dir_all(&out_dir)?;
+    let pages = convert_presentation_to_svg_with_mode(&presentation, SvgRenderMode::NotesPages)?;
+    for (index, svg) in pages.iter().enumerate() {
+        fs::write(format!("{}/notes_page_{}.svg", out_dir, index + 1), svg)?;
+    }
+    eprintln!("Rendered {} notes pages to {}.", pages.len(), out_dir);
+    Ok(())
+}
@@ -0,0 +1,413 @@
+//! Access token providers used by `SlidesClient`.
+//!
+//! A `TokenProvider` hands out OAuth bearer tokens for a set of scopes. Providers are
+//! long-lived and shared by the client, so implementations are expected to cache tokens
+//! and only refresh them when they expire.
+
+use crate::errors::{Result, SlidesApiError};
+use reqwest::header::HeaderValue;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use yup_oauth2::authenticator::DefaultAuthenticator;
+use yup_oauth2::{
+    read_application_secret, read_service_account_key, InstalledFlowAuthenticator,
+    InstalledFlowReturnMethod, ServiceAccountAuthenticator,
+};
+
+/// The boxed future returned by `TokenProvider::access_token`.
+pub type TokenFuture<'a> = Pin<Box<dyn Future<Output = Result<String>> + Send + 'a>>;
+
+/// A source of OAuth 2.0 access tokens.
+pub trait TokenProvider: Send + Sync {
+    /// Returns a valid access token for the given scopes.
+    ///
+    /// # Arguments
+    /// * `scopes` - The OAuth scopes the token must grant. Providers that cannot
+    ///   choose scopes (e.g. a static token) may ignore them.
+    ///
+    /// # Returns
+    /// A `Result` containing the bearer token string.
+    fn access_token<'a>(&'a self, scopes: &'a [&'a str]) -> TokenFuture<'a>;
+}
+
+/// Fetches a token from a yup-oauth2 authenticator, which caches and refreshes it internally.
+async fn authenticator_token(auth: &DefaultAuthenticator, scopes: &[&str]) -> Result<String> {
+    let token = auth.token(scopes).await?;
+    token.token().map(str::to_string).ok_or_else(|| {
+        SlidesApiError::AuthSetupError(
+            "OAuth token unexpectedly missing token field after successful retrieval".to_string(),
+        )
+    })
+}
+
+// --- Static Bearer Token ---
+
+/// Uses a fixed bearer token, e.g. one minted by `gcloud auth print-access-token`
+/// or a dummy token when talking to a local mock server.
+#[derive(Debug, Clone)]
+pub struct StaticTokenProvider {
+    token: String,
+}
+
+impl StaticTokenProvider {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+        }
+    }
+}
+
+impl TokenProvider for StaticTokenProvider {
+    fn access_token<'a>(&'a self, _scopes: &'a [&'a str]) -> TokenFuture<'a> {
+        Box::pin(async move { Ok(self.token.clone()) })
+    }
+}
+
+// --- Service Account ---
+
+/// Authenticates with a service account key file.
+pub struct ServiceAccountTokenProvider {
+    authenticator: DefaultAuthenticator,
+}
+
+impl ServiceAccountTokenProvider {
+    /// Creates a provider from the service account key file at `key_file_path`.
+    pub async fn from_key_file(key_file_path: impl AsRef<Path>) -> Result<Self> {
+        let key_file_path = key_file_path.as_ref();
+        let sa_key = read_service_account_key(key_file_path).await.map_err(|e| {
+            SlidesApiError::AuthSetupError(format!(
+                "Failed to read service account key from '{}': {}",
+                key_file_path.display(),
+                e
+            ))
+        })?;
+        let authenticator = ServiceAccountAuthenticator::builder(sa_key).build().await?;
+        Ok(Self { authenticator })
+    }
+
+    /// Creates a provider from the key file referenced by the
+    /// `GOOGLE_APPLICATION_CREDENTIALS` environment variable.
+    pub async fn from_env() -> Result<Self> {
+        let key_file_path = env::var("GOOGLE_APPLICATION_CREDENTIALS")?;
+        Self::from_key_file(key_file_path).await
+    }
+}
+
+impl TokenProvider for ServiceAccountTokenProvider {
+    fn access_token<'a>(&'a self, scopes: &'a [&'a str]) -> TokenFuture<'a> {
+        Box::pin(authenticator_token(&self.authenticator, scopes))
+    }
+}
+
+// --- Installed Application (user OAuth) ---
+
+/// Authenticates a user through the installed-application OAuth flow.
+/// On first use a browser consent URL is printed and a local redirect server captures the code.
+pub struct InstalledFlowTokenProvider {
+    authenticator: DefaultAuthenticator,
+}
+
+impl InstalledFlowTokenProvider {
+    /// Creates a provider from an OAuth client secret JSON file (as downloaded from the Cloud Console).
+    ///
+    /// # Arguments
+    /// * `client_secret_path` - Path to the client secret JSON.
+    /// * `token_cache_path` - Optional file where tokens are persisted between runs.
+    pub async fn new(
+        client_secret_path: impl AsRef<Path>,
+        token_cache_path: Option<PathBuf>,
+    ) -> Result<Self> {
+        let client_secret_path = client_secret_path.as_ref();
+        let secret = read_application_secret(client_secret_path)
+            .await
+            .map_err(|e| {
+                SlidesApiError::AuthSetupError(format!(
+                    "Failed to read OAuth client secret from '{}': {}",
+                    client_secret_path.display(),
+                    e
+                ))
+            })?;
+        let mut builder =
+            InstalledFlowAuthenticator::builder(secret, InstalledFlowReturnMethod::HTTPRedirect);
+        if let Some(path) = token_cache_path {
+            builder = builder.persist_tokens_to_disk(path);
+        }
+        let authenticator = builder.build().await?;
+        Ok(Self { authenticator })
+    }
+}
+
+impl TokenProvider for InstalledFlowTokenProvider {
+    fn access_token<'a>(&'a self, scopes: &'a [&'a str]) -> TokenFuture<'a> {
+        Box::pin(authenticator_token(&self.authenticator, scopes))
+    }
+}
+
+// --- GCE / Cloud Run Metadata Server ---
+
+/// Default metadata server host, overridable with the `GCE_METADATA_HOST` environment variable.
+const DEFAULT_METADATA_HOST: &str = "metadata.google.internal";
+
+/// Tokens are refreshed this long before they actually expire.
+const TOKEN_EXPIRY_MARGIN: Duration = Duration::from_secs(60);
+
+/// Token payload returned by the metadata server.
+#[derive(Deserialize, Debug)]
+struct MetadataTokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+/// Fetches tokens for the attached service account from the compute metadata server
+/// (GCE, Cloud Run, GKE workload identity). Tokens are cached per set of scopes.
+pub struct MetadataServerTokenProvider {
+    http_client: reqwest::Client,
+    endpoint: String,
+    /// Tokens and their expiry, keyed by the sorted, comma-joined scopes.
+    cached: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+impl MetadataServerTokenProvider {
+    /// Creates a provider for the default service account of the current instance.
+    pub fn new(http_client: reqwest::Client) -> Self {
+        let host =
+            env::var("GCE_METADATA_HOST").unwrap_or_else(|_| DEFAULT_METADATA_HOST.to_string());
+        Self::with_endpoint(
+            http_client,
+            format!(
+                "http://{}/computeMetadata/v1/instance/service-accounts/default/token",
+                host
+            ),
+        )
+    }
+
+    /// Creates a provider that requests tokens from a custom endpoint.
+    pub fn with_endpoint(http_client: reqwest::Client, endpoint: impl Into<String>) -> Self {
+        Self {
+            http_client,
+            endpoint: endpoint.into(),
+            cached: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn fetch_token(&self, scopes: &[&str]) -> Result<String> {
+        let mut sorted_scopes = scopes.to_vec();
+        sorted_scopes.sort_unstable();
+        sorted_scopes.dedup();
+        let cache_key = sorted_scopes.join(",");
+        if let Some((token, expires_at)) = self.cached.lock().unwrap().get(&cache_key) {
+            if Instant::now() + TOKEN_EXPIRY_MARGIN < *expires_at {
+                return Ok(token.clone());
+            }
+        }
+
+        let mut request = self
+            .http_client
+            .get(&self.endpoint)
+            .header("Metadata-Flavor", HeaderValue::from_static("Google"));
+        if !scopes.is_empty() {
+            request = request.query(&[("scopes", scopes.join(","))]);
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let message = response.text().await.unwrap_or_default();
+            return Err(SlidesApiError::AuthSetupError(format!(
+                "Metadata server returned {}: {}",
+                status, message
+            )));
+        }
+        let payload: MetadataTokenResponse = response.json().await?;
+        let expires_at = Instant::now() + Duration::from_secs(payload.expires_in);
+        self.cached
+            .lock()
+            .unwrap()
+            .insert(cache_key, (payload.access_token.clone(), expires_at));
+        Ok(payload.access_token)
+    }
+}
+
+impl TokenProvider for MetadataServerTokenProvider {
+    fn access_token<'a>(&'a self, scopes: &'a [&'a str]) -> TokenFuture<'a> {
+        Box::pin(self.fetch_token(scopes))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "emulator")]
+mod tests {
+    use super::*;
+    use crate::client::SlidesClient;
+    use crate::emulator::SlidesEmulator;
+    use serde_json::json;
+
+    /// Generates a throwaway RSA key to sign the assertions sent to the emulator.
+    fn test_private_key() -> String {
+        let rsa = openssl::rsa::Rsa::generate(2048).unwrap();
+        let key = openssl::pkey::PKey::from_rsa(rsa).unwrap();
+        String::from_utf8(key.private_key_to_pem_pkcs8().unwrap()).unwrap()
+    }
+
+    /// The scopes `SlidesClient` requests for reads, so a cached token covers its calls too.
+    const SCOPES: &[&str] = &[
+        "https://www.googleapis.com/auth/presentations.readonly",
+        "https://www.googleapis.com/auth/drive.readonly",
+    ];
+
+    async fn start_emulator() -> SlidesEmulator {
+        SlidesEmulator::builder()
+            .add_presentation_json(r#"{"presentationId": "deck", "slides": []}"#)
+            .unwrap()
+            .start()
+            .await
+            .unwrap()
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            env::temp_dir().join(format!("gslides-auth-test-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Checks that `provider` hands out `expected` and that a client using it is authorized.
+    async fn assert_provides(
+        emulator: &SlidesEmulator,
+        provider: impl TokenProvider + 'static,
+        expected: &str,
+    ) {
+        assert_eq!(provider.access_token(SCOPES).await.unwrap(), expected);
+        let client = SlidesClient::builder()
+            .set_token_provider(provider)
+            .set_base_url(emulator.base_url())
+            .build()
+            .unwrap();
+        assert!(client.get_presentation("deck").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_static_token_provider() {
+        let emulator = start_emulator().await;
+        assert_provides(
+            &emulator,
+            StaticTokenProvider::new("static-token"),
+            "static-token",
+        )
+        .await;
+
+        // Without a token provider the emulator rejects the call.
+        let error = reqwest::get(format!("{}/v1/presentations/deck", emulator.base_url()))
+            .await
+            .unwrap();
+        assert_eq!(error.status(), reqwest::StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_service_account_token_provider() {
+        let emulator = start_emulator().await;
+        let key_path = temp_dir("service-account").join("key.json");
+        let key = json!({
+            "type": "service_account",
+            "private_key": test_private_key(),
+            "client_email": "robot@example.iam.gserviceaccount.com",
+            "token_uri": emulator.token_uri(),
+        });
+        std::fs::write(&key_path, key.to_string()).unwrap();
+
+        let provider = ServiceAccountTokenProvider::from_key_file(&key_path)
+            .await
+            .unwrap();
+        assert_provides(&emulator, provider, "emulator_token_1").await;
+        assert!(
+            ServiceAccountTokenProvider::from_key_file(key_path.with_extension("missing"))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_installed_flow_token_provider_refreshes_cached_token() {
+        let emulator = start_emulator().await;
+        let dir = temp_dir("installed-flow");
+        let secret = json!({
+            "installed": {
+                "client_id": "client.apps.googleusercontent.com",
+                "client_secret": "secret",
+                "auth_uri": format!("{}/auth", emulator.base_url()),
+                "token_uri": emulator.token_uri(),
+                "redirect_uris": ["http://localhost"],
+            }
+        });
+        std::fs::write(dir.join("client_secret.json"), secret.to_string()).unwrap();
+        // An expired token from an earlier run; it is refreshed without user interaction.
+        let cache = json!([{
+            "scopes": SCOPES,
+            "token": {
+                "access_token": "expired-token",
+                "refresh_token": "refresh-token",
+                "expires_at": [2000, 1, 0, 0, 0, 0, 0, 0, 0],
+                "id_token": null,
+            }
+        }]);
+        std::fs::write(dir.join("tokens.json"), cache.to_string()).unwrap();
+
+        let provider = InstalledFlowTokenProvider::new(
+            dir.join("client_secret.json"),
+            Some(dir.join("tokens.json")),
+        )
+        .await
+        .unwrap();
+        assert_provides(&emulator, provider, "emulator_token_1").await;
+        let stored = std::fs::read_to_string(dir.join("tokens.json")).unwrap();
+        assert!(stored.contains("emulator_token_1"));
+    }
+
+    #[tokio::test]
+    async fn test_metadata_server_token_provider() {
+        let emulator = start_emulator().await;
+        let provider = MetadataServerTokenProvider::with_endpoint(
+            reqwest::Client::new(),
+            emulator.metadata_token_url(),
+        );
+        assert_provides(&emulator, provider, "emulator_token_1").await;
+
+        // Requests without the Metadata-Flavor header are refused.
+        let response = reqwest::get(emulator.metadata_token_url()).await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_metadata_server_tokens_are_cached_per_scope_set() {
+        let emulator = start_emulator().await;
+        let provider = MetadataServerTokenProvider::with_endpoint(
+            reqwest::Client::new(),
+            emulator.metadata_token_url(),
+        );
+        let write_scopes = &["https://www.googleapis.com/auth/presentations"];
+        let reversed: Vec<&str> = SCOPES.iter().rev().copied().collect();
+
+        let read_token = provider.access_token(SCOPES).await.unwrap();
+        let write_token = provider.access_token(write_scopes).await.unwrap();
+        assert_ne!(read_token, write_token);
+        // The same scopes in another order reuse the cached token.
+        assert_eq!(provider.access_token(&reversed).await.unwrap(), read_token);
+        assert_eq!(
+            provider.access_token(write_scopes).await.unwrap(),
+            write_token
+        );
+
+        let token_requests = emulator
+            .requests()
+            .into_iter()
+            .filter(|request| request.target.starts_with("/computeMetadata/"))
+            .count();
+        assert_eq!(token_requests, 2);
+    }
+}
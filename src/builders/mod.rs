@@ -0,0 +1,176 @@
+//! Fluent builders for presentations, pages and page elements.
+//!
+//! The builders produce plain `models` values, so fixtures for the diff, SVG and engine code
+//! can be written in Rust instead of as hand-edited JSON. Unset values get the defaults of
+//! a new Google Slides deck: a 16:9 page of 9144000 x 5143500 EMU, and elements of
+//! 3000000 x 3000000 EMU at the top-left corner with an identity transform.
+//!
+//! ```
+//! use gslides_tools::builders::{PageBuilder, PresentationBuilder, ShapeBuilder, TextBuilder};
+//!
+//! let presentation = PresentationBuilder::new("deck")
+//!     .set_title("Quarterly review")
+//!     .add_slide(
+//!         PageBuilder::slide("slide_1")
+//!             .add_element(
+//!                 ShapeBuilder::text_box("title_1")
+//!                     .set_position(311700.0, 744575.0)
+//!                     .set_size(8520600.0, 2052600.0)
+//!                     .set_text(TextBuilder::new().add_paragraph("Hello").build())
+//!                     .build(),
+//!             )
+//!             .build(),
+//!     )
+//!     .build();
+//! assert_eq!(presentation.slides.as_ref().map(Vec::len), Some(1));
+//! ```
+
+mod page;
+mod presentation;
+mod shape;
+mod table;
+mod text;
+
+pub use page::PageBuilder;
+pub use presentation::PresentationBuilder;
+pub use shape::ShapeBuilder;
+pub use table::TableBuilder;
+pub use text::TextBuilder;
+
+use crate::models::common::{AffineTransform, Dimension, Size, Unit};
+use crate::models::elements::{PageElement, PageElementKind};
+
+/// The width of a default 16:9 page, in EMU.
+pub const DEFAULT_PAGE_WIDTH_EMU: f64 = 9_144_000.0;
+/// The height of a default 16:9 page, in EMU.
+pub const DEFAULT_PAGE_HEIGHT_EMU: f64 = 5_143_500.0;
+/// The width and height of an element without an explicit size, in EMU.
+pub const DEFAULT_ELEMENT_SIZE_EMU: f64 = 3_000_000.0;
+/// The width of a table column without an explicit width, in EMU.
+pub const DEFAULT_COLUMN_WIDTH_EMU: f64 = 1_828_800.0;
+/// The height of a table row without an explicit height, in EMU.
+pub const DEFAULT_ROW_HEIGHT_EMU: f64 = 369_332.0;
+
+/// Returns a `Dimension` of `magnitude` EMU.
+pub fn emu(magnitude: f64) -> Dimension {
+    Dimension {
+        magnitude: Some(magnitude),
+        unit: Some(Unit::Emu),
+        extra: Default::default(),
+    }
+}
+
+/// Returns a `Size` of `width` x `height` EMU.
+pub fn emu_size(width: f64, height: f64) -> Size {
+    Size {
+        width: Some(emu(width)),
+        height: Some(emu(height)),
+        extra: Default::default(),
+    }
+}
+
+/// The size, position and alt text shared by the element builders.
+#[derive(Debug, Clone)]
+struct ElementFrame {
+    object_id: String,
+    size: Size,
+    transform: AffineTransform,
+    title: Option<String>,
+    description: Option<String>,
+}
+
+impl ElementFrame {
+    fn new(object_id: String) -> Self {
+        Self {
+            object_id,
+            size: emu_size(DEFAULT_ELEMENT_SIZE_EMU, DEFAULT_ELEMENT_SIZE_EMU),
+            transform: AffineTransform {
+                scale_x: Some(1.0),
+                scale_y: Some(1.0),
+                shear_x: None,
+                shear_y: None,
+                translate_x: None,
+                translate_y: None,
+                unit: Some(Unit::Emu),
+                extra: Default::default(),
+            },
+            title: None,
+            description: None,
+        }
+    }
+
+    /// Moves the top-left corner to `(x, y)` EMU. The API omits zero translations.
+    fn set_position(&mut self, x: f64, y: f64) {
+        self.transform.translate_x = (x != 0.0).then_some(x);
+        self.transform.translate_y = (y != 0.0).then_some(y);
+    }
+
+    fn into_element(self, element_kind: PageElementKind) -> PageElement {
+        PageElement {
+            object_id: self.object_id,
+            size: Some(self.size),
+            transform: Some(self.transform),
+            title: self.title,
+            description: self.description,
+            element_kind,
+            extra: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converters::svg::convert_presentation_to_svg;
+    use crate::diff::comparer::ComparerBuilder;
+    use crate::models::presentation::Presentation;
+
+    fn deck(greeting: &str) -> Presentation {
+        PresentationBuilder::new("deck_1")
+            .add_slide(
+                PageBuilder::slide("slide_1")
+                    .add_element(
+                        ShapeBuilder::text_box("box_1")
+                            .set_position(100.0, 200.0)
+                            .set_text(TextBuilder::new().add_paragraph(greeting).build())
+                            .build(),
+                    )
+                    .add_element(
+                        TableBuilder::new("table_1", 2, 2)
+                            .set_cell_text(1, 1, TextBuilder::new().add_paragraph("42").build())
+                            .build(),
+                    )
+                    .build(),
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_built_presentation_feeds_svg_and_diff() {
+        let base = deck("Hello");
+        let json = serde_json::to_value(&base).unwrap();
+        assert_eq!(json["pageSize"]["width"]["magnitude"], 9144000);
+        let element = &json["slides"][0]["pageElements"][0];
+        assert_eq!(element["size"]["height"]["unit"], "EMU");
+        assert_eq!(element["transform"]["translateY"], 200);
+        assert_eq!(
+            json["slides"][0]["pageElements"][1]["table"]["tableRows"][1]["tableCells"][1]
+                ["location"]["columnIndex"],
+            1
+        );
+        let reparsed: Presentation = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&reparsed).unwrap(), json);
+
+        let svgs = convert_presentation_to_svg(&base).unwrap();
+        assert_eq!(svgs.len(), 1);
+        assert!(svgs[0].contains("Hello") && svgs[0].contains("42"));
+
+        let comparison = ComparerBuilder::new()
+            .set_base(base)
+            .build()
+            .unwrap()
+            .compare(&deck("Goodbye"))
+            .unwrap();
+        assert!(!comparison.get_structured_diff().is_empty());
+    }
+}
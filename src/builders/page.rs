@@ -0,0 +1,128 @@
+use crate::models::elements::PageElement;
+use crate::models::page::{Page, PageType};
+use crate::models::page_properties::PageProperties;
+use crate::models::properties::{LayoutProperties, NotesProperties, SlideProperties};
+
+/// Builder for a `Page`: a slide, layout, master or notes page.
+#[derive(Debug, Clone)]
+pub struct PageBuilder {
+    page: Page,
+}
+
+impl PageBuilder {
+    /// Creates a builder for an empty page of the given type.
+    pub fn new(object_id: impl Into<String>, page_type: PageType) -> Self {
+        Self {
+            page: Page {
+                object_id: object_id.into(),
+                page_type: Some(page_type),
+                page_elements: Some(Vec::new()),
+                revision_id: None,
+                page_properties: None,
+                slide_properties: None,
+                layout_properties: None,
+                notes_properties: None,
+                master_properties: None,
+                extra: Default::default(),
+            },
+        }
+    }
+
+    /// Creates a builder for a slide.
+    pub fn slide(object_id: impl Into<String>) -> Self {
+        Self::new(object_id, PageType::Slide)
+    }
+
+    /// Creates a builder for a layout.
+    pub fn layout(object_id: impl Into<String>) -> Self {
+        Self::new(object_id, PageType::Layout)
+    }
+
+    /// Creates a builder for a master.
+    pub fn master(object_id: impl Into<String>) -> Self {
+        Self::new(object_id, PageType::Master)
+    }
+
+    /// Creates a builder for a notes page.
+    pub fn notes(object_id: impl Into<String>) -> Self {
+        Self::new(object_id, PageType::Notes)
+    }
+
+    /// Sets the layout a slide is based on.
+    pub fn set_layout(mut self, layout_object_id: impl Into<String>) -> Self {
+        self.slide_properties().layout_object_id = Some(layout_object_id.into());
+        self
+    }
+
+    /// Sets the master a slide or layout is based on.
+    pub fn set_master(mut self, master_object_id: impl Into<String>) -> Self {
+        let master_object_id = master_object_id.into();
+        if self.page.page_type == Some(PageType::Layout) {
+            self.page
+                .layout_properties
+                .get_or_insert_with(|| LayoutProperties {
+                    master_object_id: None,
+                    name: None,
+                    display_name: None,
+                    extra: Default::default(),
+                })
+                .master_object_id = Some(master_object_id);
+        } else {
+            self.slide_properties().master_object_id = Some(master_object_id);
+        }
+        self
+    }
+
+    /// Sets the page properties, e.g. the background fill.
+    pub fn set_page_properties(mut self, page_properties: PageProperties) -> Self {
+        self.page.page_properties = Some(page_properties);
+        self
+    }
+
+    /// Sets the revision ID.
+    pub fn set_revision_id(mut self, revision_id: impl Into<String>) -> Self {
+        self.page.revision_id = Some(revision_id.into());
+        self
+    }
+
+    /// Sets the notes page of a slide, e.g. one built with `PageBuilder::notes`.
+    pub fn set_notes_page(mut self, notes_page: Page) -> Self {
+        self.slide_properties().notes_page = Some(Box::new(notes_page));
+        self
+    }
+
+    /// Sets the shape of a notes page that holds the speaker notes.
+    pub fn set_speaker_notes_object_id(mut self, object_id: impl Into<String>) -> Self {
+        self.page.notes_properties = Some(NotesProperties {
+            speaker_notes_object_id: Some(object_id.into()),
+            extra: Default::default(),
+        });
+        self
+    }
+
+    /// Appends an element, e.g. one built with `ShapeBuilder` or `TableBuilder`.
+    pub fn add_element(mut self, element: PageElement) -> Self {
+        self.page
+            .page_elements
+            .get_or_insert_with(Vec::new)
+            .push(element);
+        self
+    }
+
+    /// Builds the `Page`.
+    pub fn build(self) -> Page {
+        self.page
+    }
+
+    fn slide_properties(&mut self) -> &mut SlideProperties {
+        self.page
+            .slide_properties
+            .get_or_insert_with(|| SlideProperties {
+                layout_object_id: None,
+                master_object_id: None,
+                notes_page: None,
+                is_skipped: None,
+                extra: Default::default(),
+            })
+    }
+}
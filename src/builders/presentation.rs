@@ -0,0 +1,91 @@
+use super::{emu_size, DEFAULT_PAGE_HEIGHT_EMU, DEFAULT_PAGE_WIDTH_EMU};
+use crate::models::page::Page;
+use crate::models::presentation::Presentation;
+
+/// Builder for a `Presentation`. The page size defaults to 16:9.
+#[derive(Debug, Clone)]
+pub struct PresentationBuilder {
+    presentation: Presentation,
+}
+
+impl PresentationBuilder {
+    /// Creates a builder for an empty presentation with the given ID.
+    pub fn new(presentation_id: impl Into<String>) -> Self {
+        Self {
+            presentation: Presentation {
+                presentation_id: presentation_id.into(),
+                page_size: Some(emu_size(DEFAULT_PAGE_WIDTH_EMU, DEFAULT_PAGE_HEIGHT_EMU)),
+                slides: Some(Vec::new()),
+                title: None,
+                masters: None,
+                layouts: None,
+                locale: None,
+                revision_id: None,
+                notes_master: None,
+                extra: Default::default(),
+            },
+        }
+    }
+
+    /// Sets the title of the presentation.
+    pub fn set_title(mut self, title: impl Into<String>) -> Self {
+        self.presentation.title = Some(title.into());
+        self
+    }
+
+    /// Sets the page size, in EMU.
+    pub fn set_page_size(mut self, width: f64, height: f64) -> Self {
+        self.presentation.page_size = Some(emu_size(width, height));
+        self
+    }
+
+    /// Sets the locale, e.g. `en-US`.
+    pub fn set_locale(mut self, locale: impl Into<String>) -> Self {
+        self.presentation.locale = Some(locale.into());
+        self
+    }
+
+    /// Sets the revision ID.
+    pub fn set_revision_id(mut self, revision_id: impl Into<String>) -> Self {
+        self.presentation.revision_id = Some(revision_id.into());
+        self
+    }
+
+    /// Appends a slide, e.g. one built with `PageBuilder::slide`.
+    pub fn add_slide(mut self, slide: Page) -> Self {
+        self.presentation
+            .slides
+            .get_or_insert_with(Vec::new)
+            .push(slide);
+        self
+    }
+
+    /// Appends a layout, e.g. one built with `PageBuilder::layout`.
+    pub fn add_layout(mut self, layout: Page) -> Self {
+        self.presentation
+            .layouts
+            .get_or_insert_with(Vec::new)
+            .push(layout);
+        self
+    }
+
+    /// Appends a master, e.g. one built with `PageBuilder::master`.
+    pub fn add_master(mut self, master: Page) -> Self {
+        self.presentation
+            .masters
+            .get_or_insert_with(Vec::new)
+            .push(master);
+        self
+    }
+
+    /// Sets the notes master.
+    pub fn set_notes_master(mut self, notes_master: Page) -> Self {
+        self.presentation.notes_master = Some(notes_master);
+        self
+    }
+
+    /// Builds the `Presentation`.
+    pub fn build(self) -> Presentation {
+        self.presentation
+    }
+}
@@ -0,0 +1,97 @@
+use super::{emu_size, ElementFrame};
+use crate::models::common::AffineTransform;
+use crate::models::elements::{PageElement, PageElementKind};
+use crate::models::placeholder::{Placeholder, PlaceholderType};
+use crate::models::shape::{Shape, ShapeType};
+use crate::models::shape_properties::ShapeProperties;
+use crate::models::text::TextContent;
+
+/// Builder for a shape page element, such as a text box or rectangle.
+#[derive(Debug, Clone)]
+pub struct ShapeBuilder {
+    frame: ElementFrame,
+    shape: Shape,
+}
+
+impl ShapeBuilder {
+    /// Creates a builder for a shape of the given type with the default size.
+    pub fn new(object_id: impl Into<String>, shape_type: ShapeType) -> Self {
+        Self {
+            frame: ElementFrame::new(object_id.into()),
+            shape: Shape {
+                shape_type: Some(shape_type),
+                text: None,
+                shape_properties: None,
+                placeholder: None,
+                extra: Default::default(),
+            },
+        }
+    }
+
+    /// Creates a builder for a text box.
+    pub fn text_box(object_id: impl Into<String>) -> Self {
+        Self::new(object_id, ShapeType::TextBox)
+    }
+
+    /// Moves the top-left corner to `(x, y)` EMU.
+    pub fn set_position(mut self, x: f64, y: f64) -> Self {
+        self.frame.set_position(x, y);
+        self
+    }
+
+    /// Sets the width and height, in EMU.
+    pub fn set_size(mut self, width: f64, height: f64) -> Self {
+        self.frame.size = emu_size(width, height);
+        self
+    }
+
+    /// Replaces the transform, e.g. to rotate or scale the shape.
+    pub fn set_transform(mut self, transform: AffineTransform) -> Self {
+        self.frame.transform = transform;
+        self
+    }
+
+    /// Sets the alt-text title.
+    pub fn set_title(mut self, title: impl Into<String>) -> Self {
+        self.frame.title = Some(title.into());
+        self
+    }
+
+    /// Sets the alt-text description.
+    pub fn set_description(mut self, description: impl Into<String>) -> Self {
+        self.frame.description = Some(description.into());
+        self
+    }
+
+    /// Sets the text, e.g. one built with `TextBuilder`.
+    pub fn set_text(mut self, text: TextContent) -> Self {
+        self.shape.text = Some(text);
+        self
+    }
+
+    /// Sets the shape properties, such as fill and outline.
+    pub fn set_shape_properties(mut self, shape_properties: ShapeProperties) -> Self {
+        self.shape.shape_properties = Some(shape_properties);
+        self
+    }
+
+    /// Makes the shape a placeholder that inherits from `parent_object_id`, if given.
+    pub fn set_placeholder(
+        mut self,
+        placeholder_type: PlaceholderType,
+        parent_object_id: Option<String>,
+    ) -> Self {
+        self.shape.placeholder = Some(Placeholder {
+            placeholder_type: Some(placeholder_type),
+            index: None,
+            parent_object_id,
+            extra: Default::default(),
+        });
+        self
+    }
+
+    /// Builds the `PageElement`.
+    pub fn build(self) -> PageElement {
+        self.frame.into_element(PageElementKind::Shape(self.shape))
+    }
+}
@@ -0,0 +1,122 @@
+use super::{emu, emu_size, ElementFrame, DEFAULT_COLUMN_WIDTH_EMU, DEFAULT_ROW_HEIGHT_EMU};
+use crate::engine::new_table;
+use crate::models::elements::{PageElement, PageElementKind};
+use crate::models::table_properties::TableRowProperties;
+use crate::models::text::TextContent;
+
+/// Builder for a table page element. Every cell spans one row and one column, and the
+/// element's size is the sum of the column widths and row heights.
+#[derive(Debug, Clone)]
+pub struct TableBuilder {
+    frame: ElementFrame,
+    column_widths: Vec<f64>,
+    row_heights: Vec<f64>,
+    /// Cell texts by row, then column.
+    texts: Vec<Vec<Option<TextContent>>>,
+}
+
+impl TableBuilder {
+    /// Creates a builder for an empty `rows` x `columns` table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rows` or `columns` is zero, or larger than the API's `i32` counts.
+    pub fn new(object_id: impl Into<String>, rows: usize, columns: usize) -> Self {
+        assert!(
+            rows > 0 && columns > 0 && i32::try_from(rows.max(columns)).is_ok(),
+            "a table needs at least one row and one column, got {}x{}",
+            rows,
+            columns
+        );
+        Self {
+            frame: ElementFrame::new(object_id.into()),
+            column_widths: vec![DEFAULT_COLUMN_WIDTH_EMU; columns],
+            row_heights: vec![DEFAULT_ROW_HEIGHT_EMU; rows],
+            texts: vec![vec![None; columns]; rows],
+        }
+    }
+
+    /// Moves the top-left corner to `(x, y)` EMU.
+    pub fn set_position(mut self, x: f64, y: f64) -> Self {
+        self.frame.set_position(x, y);
+        self
+    }
+
+    /// Sets the alt-text title.
+    pub fn set_title(mut self, title: impl Into<String>) -> Self {
+        self.frame.title = Some(title.into());
+        self
+    }
+
+    /// Sets the alt-text description.
+    pub fn set_description(mut self, description: impl Into<String>) -> Self {
+        self.frame.description = Some(description.into());
+        self
+    }
+
+    /// Sets the width of a column, in EMU.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the column is outside the table.
+    pub fn set_column_width(mut self, column: usize, width: f64) -> Self {
+        self.column_widths[column] = width;
+        self
+    }
+
+    /// Sets the height of a row, in EMU.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row is outside the table.
+    pub fn set_row_height(mut self, row: usize, height: f64) -> Self {
+        self.row_heights[row] = height;
+        self
+    }
+
+    /// Sets the text of a cell, e.g. one built with `TextBuilder`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is outside the table.
+    pub fn set_cell_text(mut self, row: usize, column: usize, text: TextContent) -> Self {
+        self.texts[row][column] = Some(text);
+        self
+    }
+
+    /// Builds the `PageElement`.
+    pub fn build(self) -> PageElement {
+        let mut frame = self.frame;
+        frame.size = emu_size(
+            self.column_widths.iter().sum(),
+            self.row_heights.iter().sum(),
+        );
+        // The counts were checked in `new`.
+        let mut table = new_table(
+            self.row_heights.len() as i32,
+            self.column_widths.len() as i32,
+        )
+        .expect("table dimensions are positive");
+
+        for (column, width) in table
+            .table_columns
+            .iter_mut()
+            .flatten()
+            .zip(&self.column_widths)
+        {
+            column.column_width = Some(emu(*width));
+        }
+        let rows = table.table_rows.iter_mut().flatten();
+        for ((row, height), texts) in rows.zip(&self.row_heights).zip(self.texts) {
+            row.row_height = Some(emu(*height));
+            row.table_row_properties = Some(TableRowProperties {
+                min_row_height: Some(emu(*height)),
+                extra: Default::default(),
+            });
+            for (cell, text) in row.table_cells.iter_mut().flatten().zip(texts) {
+                cell.text = text;
+            }
+        }
+        frame.into_element(PageElementKind::Table(table))
+    }
+}
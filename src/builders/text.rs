@@ -0,0 +1,160 @@
+use crate::engine::EditableText;
+use crate::models::bullet::Bullet;
+use crate::models::properties::{ParagraphStyle, TextStyle};
+use crate::models::text::TextContent;
+use crate::models::text_element::{ParagraphMarker, TextElement, TextElementKind, TextRun};
+
+#[derive(Debug, Clone, Default)]
+struct Paragraph {
+    style: Option<ParagraphStyle>,
+    bullet: Option<Bullet>,
+    runs: Vec<(String, Option<TextStyle>)>,
+}
+
+/// Builder for the `TextContent` of a shape or table cell.
+///
+/// Text is added paragraph by paragraph; `build` ends each paragraph with `\n`, adds its
+/// `ParagraphMarker`, merges neighbouring runs with the same style and computes every
+/// `start_index`/`end_index` in UTF-16 code units, as the API does.
+#[derive(Debug, Clone, Default)]
+pub struct TextBuilder {
+    paragraphs: Vec<Paragraph>,
+}
+
+impl TextBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Starts a new paragraph holding `text`. Each `\n` in `text` starts another paragraph.
+    pub fn add_paragraph(mut self, text: &str) -> Self {
+        self.paragraphs.push(Paragraph::default());
+        self.push_text(text, None);
+        self
+    }
+
+    /// Appends unstyled text to the current paragraph. Each `\n` starts a new paragraph.
+    pub fn add_run(mut self, text: &str) -> Self {
+        self.push_text(text, None);
+        self
+    }
+
+    /// Appends styled text to the current paragraph. Each `\n` starts a new paragraph.
+    pub fn add_styled_run(mut self, text: &str, style: TextStyle) -> Self {
+        self.push_text(text, Some(style));
+        self
+    }
+
+    /// Sets the style of the current paragraph.
+    pub fn set_paragraph_style(mut self, style: ParagraphStyle) -> Self {
+        self.current_paragraph().style = Some(style);
+        self
+    }
+
+    /// Sets the bullet of the current paragraph.
+    pub fn set_bullet(mut self, bullet: Bullet) -> Self {
+        self.current_paragraph().bullet = Some(bullet);
+        self
+    }
+
+    /// Builds the `TextContent`. Without paragraphs it has no text elements.
+    pub fn build(self) -> TextContent {
+        let mut elements = Vec::new();
+        for paragraph in self.paragraphs {
+            elements.push(element(TextElementKind::ParagraphMarker(ParagraphMarker {
+                style: paragraph.style,
+                bullet: paragraph.bullet,
+                extra: Default::default(),
+            })));
+            // The paragraph's `\n` takes the style of its last run.
+            let end_style = paragraph.runs.last().and_then(|(_, style)| style.clone());
+            let runs = paragraph
+                .runs
+                .into_iter()
+                .chain(std::iter::once(("\n".to_string(), end_style)));
+            for (content, style) in runs {
+                elements.push(element(TextElementKind::TextRun(TextRun {
+                    content: Some(content),
+                    style,
+                    extra: Default::default(),
+                })));
+            }
+        }
+        let draft = TextContent {
+            text_elements: Some(elements),
+            lists: None,
+            extra: Default::default(),
+        };
+        EditableText::from_content(Some(&draft))
+            .into_content()
+            .unwrap_or(TextContent {
+                text_elements: None,
+                lists: None,
+                extra: Default::default(),
+            })
+    }
+
+    fn push_text(&mut self, text: &str, style: Option<TextStyle>) {
+        for (i, line) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.paragraphs.push(Paragraph::default());
+            }
+            if !line.is_empty() {
+                self.current_paragraph()
+                    .runs
+                    .push((line.to_string(), style.clone()));
+            }
+        }
+    }
+
+    fn current_paragraph(&mut self) -> &mut Paragraph {
+        if self.paragraphs.is_empty() {
+            self.paragraphs.push(Paragraph::default());
+        }
+        self.paragraphs
+            .last_mut()
+            .expect("a paragraph was just added")
+    }
+}
+
+fn element(kind: TextElementKind) -> TextElement {
+    TextElement {
+        start_index: None,
+        end_index: None,
+        kind: Some(kind),
+        extra: Default::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_build_computes_indices_and_paragraph_markers() {
+        let bold = TextStyle {
+            bold: Some(true),
+            ..Default::default()
+        };
+        let text = TextBuilder::new()
+            .add_paragraph("Hi ")
+            .add_styled_run("there", bold)
+            .add_paragraph("😀 a\nb")
+            .build();
+
+        assert_eq!(
+            serde_json::to_value(&text).unwrap(),
+            json!({"textElements": [
+                {"endIndex": 9, "paragraphMarker": {}},
+                {"endIndex": 3, "textRun": {"content": "Hi "}},
+                {"startIndex": 3, "endIndex": 9, "textRun": {"content": "there\n", "style": {"bold": true}}},
+                {"startIndex": 9, "endIndex": 14, "paragraphMarker": {}},
+                {"startIndex": 9, "endIndex": 14, "textRun": {"content": "😀 a\n"}},
+                {"startIndex": 14, "endIndex": 16, "paragraphMarker": {}},
+                {"startIndex": 14, "endIndex": 16, "textRun": {"content": "b\n"}}
+            ]})
+        );
+        assert!(TextBuilder::new().build().text_elements.is_none());
+    }
+}
@@ -0,0 +1,292 @@
+//! Revision-aware on-disk cache of fetched presentations.
+//!
+//! Raw JSON bodies are stored as `<dir>/<presentation_id>/<revision_id>.json`, so every
+//! revision that was ever fetched stays available for offline diffing until it is evicted.
+//! `SlidesClient::get_presentation_cached` uses the cache to skip full downloads when the
+//! presentation's `revisionId` has not changed.
+
+use crate::errors::{Result, SlidesApiError};
+use crate::models::presentation::Presentation;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Prefix of the cache key used when a response carries no `revisionId`
+/// (the API only returns it to users with edit access).
+const CONTENT_KEY_PREFIX: &str = "content-";
+
+/// A cached revision of a presentation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedRevision {
+    /// The ID of the presentation.
+    pub presentation_id: String,
+    /// The revision ID, or a `content-<hash>` key when the API returned none.
+    pub revision_id: String,
+    /// The file holding the raw JSON body.
+    pub path: PathBuf,
+    /// The size of the raw JSON body in bytes.
+    pub size_bytes: u64,
+    /// When the revision was stored.
+    pub fetched_at: SystemTime,
+}
+
+impl CachedRevision {
+    /// Returns `true` if the key is a real API revision ID (and can be compared with
+    /// the presentation's current `revisionId`).
+    pub fn has_revision_id(&self) -> bool {
+        !self.revision_id.starts_with(CONTENT_KEY_PREFIX)
+    }
+}
+
+/// Only the revision ID is needed to key a stored body.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RevisionProbe {
+    revision_id: Option<String>,
+}
+
+/// An on-disk cache of raw presentation bodies keyed by presentation and revision ID.
+#[derive(Debug, Clone)]
+pub struct PresentationCache {
+    dir: PathBuf,
+    max_total_bytes: Option<u64>,
+    max_age: Option<Duration>,
+}
+
+impl PresentationCache {
+    /// Opens (and creates, if needed) a cache rooted at `dir`. No eviction limits are set.
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            max_total_bytes: None,
+            max_age: None,
+        })
+    }
+
+    /// Evicts the oldest revisions once the cache grows beyond `max_total_bytes`.
+    pub fn set_max_total_bytes(mut self, max_total_bytes: Option<u64>) -> Self {
+        self.max_total_bytes = max_total_bytes;
+        self
+    }
+
+    /// Evicts revisions stored longer than `max_age` ago.
+    pub fn set_max_age(mut self, max_age: Option<Duration>) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// The root directory of the cache.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Stores a raw presentation body and applies the eviction limits.
+    ///
+    /// # Arguments
+    /// * `presentation_id` - The ID of the presentation the body belongs to.
+    /// * `body` - The raw JSON returned by `presentations.get`.
+    ///
+    /// # Returns
+    /// The stored `CachedRevision`.
+    pub fn store(&self, presentation_id: &str, body: &[u8]) -> Result<CachedRevision> {
+        let probe: RevisionProbe = serde_json::from_slice(body)?;
+        let revision_id = probe
+            .revision_id
+            .unwrap_or_else(|| format!("{}{:016x}", CONTENT_KEY_PREFIX, fnv1a_64(body)));
+
+        let presentation_dir = self.presentation_dir(presentation_id)?;
+        fs::create_dir_all(&presentation_dir)?;
+        let path = presentation_dir.join(format!("{}.json", sanitize_key(&revision_id)?));
+        fs::write(&path, body)?;
+
+        let revision = CachedRevision {
+            presentation_id: presentation_id.to_string(),
+            revision_id,
+            size_bytes: body.len() as u64,
+            fetched_at: fs::metadata(&path)?.modified()?,
+            path,
+        };
+        self.evict()?;
+        Ok(revision)
+    }
+
+    /// Loads a specific cached revision.
+    ///
+    /// # Returns
+    /// `Ok(None)` if the revision is not cached.
+    pub fn load(&self, presentation_id: &str, revision_id: &str) -> Result<Option<Presentation>> {
+        let path = self
+            .presentation_dir(presentation_id)?
+            .join(format!("{}.json", sanitize_key(revision_id)?));
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let body = fs::read(&path)?;
+        Ok(Some(serde_json::from_slice(&body)?))
+    }
+
+    /// Returns the most recently stored revision of a presentation, if any.
+    pub fn latest(&self, presentation_id: &str) -> Result<Option<(CachedRevision, Presentation)>> {
+        let Some(revision) = self.list_revisions(presentation_id)?.pop() else {
+            return Ok(None);
+        };
+        let body = fs::read(&revision.path)?;
+        let presentation = serde_json::from_slice(&body)?;
+        Ok(Some((revision, presentation)))
+    }
+
+    /// Lists the cached revisions of a presentation, oldest first.
+    pub fn list_revisions(&self, presentation_id: &str) -> Result<Vec<CachedRevision>> {
+        let presentation_dir = self.presentation_dir(presentation_id)?;
+        if !presentation_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut revisions = Vec::new();
+        for entry in fs::read_dir(&presentation_dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let Some(revision_id) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let metadata = fs::metadata(&path)?;
+            revisions.push(CachedRevision {
+                presentation_id: presentation_id.to_string(),
+                revision_id: revision_id.to_string(),
+                size_bytes: metadata.len(),
+                fetched_at: metadata.modified()?,
+                path,
+            });
+        }
+        revisions.sort_by(|a, b| {
+            a.fetched_at
+                .cmp(&b.fetched_at)
+                .then_with(|| a.revision_id.cmp(&b.revision_id))
+        });
+        Ok(revisions)
+    }
+
+    /// Lists the IDs of all presentations with at least one cached revision.
+    pub fn list_presentations(&self) -> Result<Vec<String>> {
+        let mut ids = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                if let Some(id) = entry.file_name().to_str() {
+                    ids.push(id.to_string());
+                }
+            }
+        }
+        ids.sort();
+        Ok(ids)
+    }
+
+    /// Removes revisions older than the configured maximum age, then the oldest
+    /// revisions until the cache fits within the configured maximum size.
+    ///
+    /// # Returns
+    /// The number of revisions removed.
+    pub fn evict(&self) -> Result<usize> {
+        if self.max_age.is_none() && self.max_total_bytes.is_none() {
+            return Ok(0);
+        }
+        let mut revisions = Vec::new();
+        for presentation_id in self.list_presentations()? {
+            revisions.extend(self.list_revisions(&presentation_id)?);
+        }
+        revisions.sort_by_key(|r| r.fetched_at);
+
+        let now = SystemTime::now();
+        let mut total_bytes: u64 = revisions.iter().map(|r| r.size_bytes).sum();
+        let mut removed = 0;
+        for revision in &revisions {
+            let expired = self.max_age.is_some_and(|max_age| {
+                now.duration_since(revision.fetched_at)
+                    .is_ok_and(|age| age > max_age)
+            });
+            let over_size = self
+                .max_total_bytes
+                .is_some_and(|max_total_bytes| total_bytes > max_total_bytes);
+            if !expired && !over_size {
+                continue;
+            }
+            fs::remove_file(&revision.path)?;
+            total_bytes -= revision.size_bytes;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    fn presentation_dir(&self, presentation_id: &str) -> Result<PathBuf> {
+        Ok(self.dir.join(sanitize_key(presentation_id)?))
+    }
+}
+
+/// Presentation and revision IDs are URL-safe tokens; anything else could escape the cache directory.
+fn sanitize_key(key: &str) -> Result<&str> {
+    if key.is_empty()
+        || !key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(SlidesApiError::InvalidInput(format!(
+            "Invalid cache key '{}'",
+            key
+        )));
+    }
+    Ok(key)
+}
+
+/// FNV-1a hash, used to key bodies without a revision ID.
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_list_and_evict_revisions() {
+        let dir = std::env::temp_dir().join(format!("gslides-cache-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let cache = PresentationCache::new(&dir).unwrap();
+
+        let rev_a = br#"{"presentationId": "deck", "revisionId": "rev_a", "title": "A"}"#;
+        let rev_b = br#"{"presentationId": "deck", "revisionId": "rev_b", "title": "B"}"#;
+        cache.store("deck", rev_a).unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+        cache.store("deck", rev_b).unwrap();
+
+        let revisions = cache.list_revisions("deck").unwrap();
+        let ids: Vec<&str> = revisions.iter().map(|r| r.revision_id.as_str()).collect();
+        assert_eq!(ids, vec!["rev_a", "rev_b"]);
+        let (latest, presentation) = cache.latest("deck").unwrap().unwrap();
+        assert_eq!(latest.revision_id, "rev_b");
+        assert_eq!(presentation.title.as_deref(), Some("B"));
+        assert!(cache.load("deck", "rev_a").unwrap().is_some());
+
+        // Without a revision ID the body is keyed by its content.
+        std::thread::sleep(Duration::from_millis(20));
+        let stored = cache
+            .store("deck", br#"{"presentationId": "deck"}"#)
+            .unwrap();
+        assert!(!stored.has_revision_id());
+
+        // Shrinking the size limit evicts the oldest revisions first.
+        let cache = cache.set_max_total_bytes(Some(40));
+        assert_eq!(cache.evict().unwrap(), 2);
+        let remaining = cache.list_revisions("deck").unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert!(!remaining[0].has_revision_id());
+
+        assert!(cache.store("../escape", rev_a).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
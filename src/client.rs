@@ -1,15 +1,35 @@
+use crate::auth::{ServiceAccountTokenProvider, TokenProvider};
+use crate::cache::PresentationCache;
 use crate::errors::{Result, SlidesApiError};
+use crate::field_mask::{PageFieldMask, PresentationField, PresentationFieldMask};
+use crate::models::page::Page;
 use crate::models::presentation::Presentation;
+use crate::models::requests::{BatchUpdatePresentationRequest, Request, WriteControl};
+use crate::models::responses::BatchUpdatePresentationResponse;
+use crate::models::thumbnail::{Thumbnail, ThumbnailProperties};
+use crate::parse::parse_presentation;
+use crate::retry::{parse_retry_after, Idempotency, RateLimiter, RetryPolicy};
+use crate::sink::{ResponseOutcome, ResponseSink};
 // use log::debug;
-use reqwest::header::{ACCEPT, AUTHORIZATION};
+use futures_util::stream::{self, StreamExt};
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use reqwest::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::Method;
 use serde::Deserialize;
-use std::env;
-use std::fs; // Import the file system module
-use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 
-#[cfg(not(target_arch = "wasm32"))]
-#[cfg(feature = "yup-oauth2")]
-use yup_oauth2::{read_service_account_key, ServiceAccountAuthenticator};
+/// The production Slides API endpoint.
+pub const DEFAULT_BASE_URL: &str = "https://slides.googleapis.com";
+
+/// Scopes requested for read-only calls.
+const READ_SCOPES: &[&str] = &[
+    "https://www.googleapis.com/auth/presentations.readonly",
+    "https://www.googleapis.com/auth/drive.readonly",
+];
+
+/// Scopes requested for calls that modify presentations.
+const WRITE_SCOPES: &[&str] = &["https://www.googleapis.com/auth/presentations"];
 
 /// Helper struct to attempt parsing standard Google API error responses.
 #[derive(Deserialize, Debug)]
@@ -26,11 +46,605 @@ struct GoogleApiErrorDetail {
     status: String,
 }
 
+/// Progress of a `SlidesClient::fetch_many` call, reported after each fetch.
+#[derive(Debug, Clone, Copy)]
+pub struct FetchProgress<'a> {
+    /// The presentation whose fetch just completed.
+    pub presentation_id: &'a str,
+    /// Whether the fetch succeeded.
+    pub succeeded: bool,
+    /// Number of fetches completed so far, including this one.
+    pub completed: usize,
+    /// Total number of presentations to fetch.
+    pub total: usize,
+}
+
+/// Builder for creating a `SlidesClient`.
+#[derive(Default)]
+pub struct SlidesClientBuilder {
+    http_client: Option<reqwest::Client>,
+    token_provider: Option<Arc<dyn TokenProvider>>,
+    base_url: Option<String>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limiter: Option<RateLimiter>,
+    response_sink: Option<Arc<dyn ResponseSink>>,
+}
+
+impl SlidesClientBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the HTTP client used for API calls. Defaults to `reqwest::Client::new()`.
+    pub fn set_http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// Sets the source of access tokens. Required.
+    pub fn set_token_provider(mut self, token_provider: impl TokenProvider + 'static) -> Self {
+        self.token_provider = Some(Arc::new(token_provider));
+        self
+    }
+
+    /// Sets a token provider that is already shared with other clients.
+    pub fn set_shared_token_provider(mut self, token_provider: Arc<dyn TokenProvider>) -> Self {
+        self.token_provider = Some(token_provider);
+        self
+    }
+
+    /// Overrides the API base URL (e.g. `http://127.0.0.1:8080` for a local mock server).
+    /// Defaults to `DEFAULT_BASE_URL`.
+    pub fn set_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Sets how transient failures are retried. Defaults to `RetryPolicy::default()`.
+    pub fn set_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Limits the rate at which this client (and all of its clones) sends requests.
+    /// Unlimited by default.
+    pub fn set_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Registers a hook that receives the raw body of every fetched presentation
+    /// (e.g. `FileDumpSink` to capture fixtures). Nothing is captured by default.
+    pub fn set_response_sink(mut self, response_sink: impl ResponseSink + 'static) -> Self {
+        self.response_sink = Some(Arc::new(response_sink));
+        self
+    }
+
+    /// Builds the `SlidesClient`.
+    /// Returns an error if no token provider was set.
+    pub fn build(self) -> Result<SlidesClient> {
+        let token_provider = self
+            .token_provider
+            .ok_or_else(|| SlidesApiError::AuthSetupError("Token provider not set".to_string()))?;
+        let base_url = self
+            .base_url
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string())
+            .trim_end_matches('/')
+            .to_string();
+        Ok(SlidesClient {
+            http_client: self.http_client.unwrap_or_default(),
+            token_provider,
+            base_url,
+            retry_policy: self.retry_policy.unwrap_or_default(),
+            rate_limiter: self.rate_limiter.map(Arc::new),
+            response_sink: self.response_sink,
+        })
+    }
+}
+
+/// A reusable Google Slides API client.
+///
+/// Holds one HTTP client and one token provider, so connections and cached tokens
+/// are shared across calls. Cloning is cheap.
+#[derive(Clone)]
+pub struct SlidesClient {
+    http_client: reqwest::Client,
+    token_provider: Arc<dyn TokenProvider>,
+    base_url: String,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    response_sink: Option<Arc<dyn ResponseSink>>,
+}
+
+impl SlidesClient {
+    /// Returns a new `SlidesClientBuilder`.
+    pub fn builder() -> SlidesClientBuilder {
+        SlidesClientBuilder::new()
+    }
+
+    /// The base URL this client sends requests to.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Fetches a presentation resource (`presentations.get`).
+    ///
+    /// # Arguments
+    ///
+    /// * `presentation_id` - The ID of the presentation to fetch.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed `Presentation` on success, or a `SlidesApiError` on failure.
+    pub async fn get_presentation(&self, presentation_id: &str) -> Result<Presentation> {
+        let bytes = self.fetch_presentation_bytes(presentation_id, None).await?;
+        self.parse_presentation_body(presentation_id, &bytes)
+    }
+
+    /// Fetches a presentation through an on-disk `PresentationCache`.
+    ///
+    /// If a revision of the presentation is cached, only its current `revisionId` is
+    /// fetched (a cheap field-masked request); when it matches, the cached copy is
+    /// returned without downloading the deck. Otherwise the full presentation is fetched
+    /// and stored as a new revision. Presentations whose responses carry no `revisionId`
+    /// (read-only access) are always fetched in full, as are presentations whose cached
+    /// copy cannot be read.
+    ///
+    /// # Arguments
+    ///
+    /// * `presentation_id` - The ID of the presentation to fetch.
+    /// * `cache` - The cache to read from and store into.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the current `Presentation`.
+    pub async fn get_presentation_cached(
+        &self,
+        presentation_id: &str,
+        cache: &PresentationCache,
+    ) -> Result<Presentation> {
+        let latest = cache.latest(presentation_id).unwrap_or_else(|e| {
+            log::warn!(
+                "Ignoring unreadable cache entry for presentation {}: {}",
+                presentation_id,
+                e
+            );
+            None
+        });
+        if let Some((cached, presentation)) = latest {
+            if cached.has_revision_id() {
+                let mask = PresentationFieldMask::new().field(PresentationField::RevisionId);
+                let current = self
+                    .get_presentation_with_fields(presentation_id, &mask)
+                    .await?;
+                if current.revision_id.as_deref() == Some(cached.revision_id.as_str()) {
+                    log::debug!(
+                        "Presentation {} unchanged at revision {}; using cache.",
+                        presentation_id,
+                        cached.revision_id
+                    );
+                    return Ok(presentation);
+                }
+            }
+        }
+
+        let bytes = self.fetch_presentation_bytes(presentation_id, None).await?;
+        let presentation = self.parse_presentation_body(presentation_id, &bytes)?;
+        cache.store(presentation_id, &bytes)?;
+        Ok(presentation)
+    }
+
+    /// Fetches many presentations concurrently, with at most `concurrency` requests in flight.
+    ///
+    /// All fetches share this client's HTTP connection pool and token provider; the
+    /// access token is requested once up front so the fetches reuse the cached token.
+    /// A failed fetch does not abort the others.
+    ///
+    /// # Arguments
+    ///
+    /// * `presentation_ids` - The IDs of the presentations to fetch.
+    /// * `concurrency` - Maximum number of concurrent fetches (at least 1).
+    /// * `on_progress` - Called after each fetch completes, in completion order.
+    ///
+    /// # Returns
+    ///
+    /// One `(presentation_id, Result<Presentation>)` pair per input ID, in input order.
+    pub async fn fetch_many<I, S>(
+        &self,
+        presentation_ids: I,
+        concurrency: usize,
+        on_progress: impl Fn(FetchProgress<'_>),
+    ) -> Vec<(String, Result<Presentation>)>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let presentation_ids: Vec<String> = presentation_ids.into_iter().map(Into::into).collect();
+        let total = presentation_ids.len();
+        if total == 0 {
+            return Vec::new();
+        }
+        if let Err(e) = self.token_provider.access_token(READ_SCOPES).await {
+            log::warn!("Failed to obtain an access token before fetching: {}", e);
+        }
+
+        let mut fetches = stream::iter(presentation_ids.into_iter().enumerate())
+            .map(|(index, presentation_id)| async move {
+                let result = self.get_presentation(&presentation_id).await;
+                (index, presentation_id, result)
+            })
+            .buffer_unordered(concurrency.max(1));
+
+        let mut results = Vec::with_capacity(total);
+        while let Some((index, presentation_id, result)) = fetches.next().await {
+            on_progress(FetchProgress {
+                presentation_id: &presentation_id,
+                succeeded: result.is_ok(),
+                completed: results.len() + 1,
+                total,
+            });
+            results.push((index, presentation_id, result));
+        }
+        results.sort_by_key(|(index, _, _)| *index);
+        results
+            .into_iter()
+            .map(|(_, presentation_id, result)| (presentation_id, result))
+            .collect()
+    }
+
+    /// Fetches only the fields of a presentation selected by `fields`.
+    /// Unselected fields are left as `None` in the returned model.
+    ///
+    /// # Arguments
+    ///
+    /// * `presentation_id` - The ID of the presentation to fetch.
+    /// * `fields` - The field mask, e.g. title and slide IDs only.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the partially populated `Presentation`.
+    pub async fn get_presentation_with_fields(
+        &self,
+        presentation_id: &str,
+        fields: &PresentationFieldMask,
+    ) -> Result<Presentation> {
+        let bytes = self
+            .fetch_presentation_bytes(presentation_id, Some(fields.to_string()))
+            .await?;
+        self.parse_presentation_body(presentation_id, &bytes)
+    }
+
+    /// Fetches a single page (`presentations.pages.get`).
+    ///
+    /// # Arguments
+    ///
+    /// * `presentation_id` - The ID of the presentation containing the page.
+    /// * `page_object_id` - The object ID of the slide, layout, master or notes page.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed `Page`.
+    pub async fn get_page(&self, presentation_id: &str, page_object_id: &str) -> Result<Page> {
+        self.fetch_page(presentation_id, page_object_id, None).await
+    }
+
+    /// Fetches only the fields of a single page selected by `fields`.
+    pub async fn get_page_with_fields(
+        &self,
+        presentation_id: &str,
+        page_object_id: &str,
+        fields: &PageFieldMask,
+    ) -> Result<Page> {
+        self.fetch_page(presentation_id, page_object_id, Some(fields.to_string()))
+            .await
+    }
+
+    /// Generates a thumbnail of a page (`presentations.pages.getThumbnail`).
+    ///
+    /// Note that this call counts against the "expensive read requests" quota.
+    ///
+    /// # Arguments
+    ///
+    /// * `presentation_id` - The ID of the presentation containing the page.
+    /// * `page_object_id` - The object ID of the page.
+    /// * `properties` - Thumbnail size and MIME type; unset options use the API defaults.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `Thumbnail` (content URL, width and height).
+    pub async fn get_page_thumbnail(
+        &self,
+        presentation_id: &str,
+        page_object_id: &str,
+        properties: &ThumbnailProperties,
+    ) -> Result<Thumbnail> {
+        validate_presentation_id(presentation_id)?;
+        validate_page_object_id(page_object_id)?;
+        let mut params = Vec::new();
+        if let Some(mime_type) = &properties.mime_type {
+            params.push(("thumbnailProperties.mimeType", enum_query_value(mime_type)?));
+        }
+        if let Some(thumbnail_size) = &properties.thumbnail_size {
+            params.push((
+                "thumbnailProperties.thumbnailSize",
+                enum_query_value(thumbnail_size)?,
+            ));
+        }
+        let api_url = with_query(
+            format!(
+                "{}/v1/presentations/{}/pages/{}/thumbnail",
+                self.base_url,
+                encode_path_segment(presentation_id),
+                encode_path_segment(page_object_id)
+            ),
+            &params,
+        )?;
+        let bytes = self
+            .execute(Method::GET, &api_url, None, READ_SCOPES)
+            .await?;
+        Ok(serde_json::from_slice::<Thumbnail>(&bytes)?)
+    }
+
+    /// Downloads the image of a thumbnail returned by `get_page_thumbnail`.
+    /// The content URL is pre-signed, so no access token is sent.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the raw image bytes (PNG by default).
+    pub async fn download_thumbnail(&self, thumbnail: &Thumbnail) -> Result<Vec<u8>> {
+        let content_url = thumbnail.content_url.as_deref().ok_or_else(|| {
+            SlidesApiError::InvalidInput("Thumbnail has no content URL".to_string())
+        })?;
+        let response = self.http_client.get(content_url).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await?;
+            return Err(api_error_from_body(status, &error_text));
+        }
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    async fn fetch_page(
+        &self,
+        presentation_id: &str,
+        page_object_id: &str,
+        fields: Option<String>,
+    ) -> Result<Page> {
+        validate_presentation_id(presentation_id)?;
+        validate_page_object_id(page_object_id)?;
+        let api_url = with_fields(
+            format!(
+                "{}/v1/presentations/{}/pages/{}",
+                self.base_url,
+                encode_path_segment(presentation_id),
+                encode_path_segment(page_object_id)
+            ),
+            fields,
+        )?;
+        let bytes = self
+            .execute(Method::GET, &api_url, None, READ_SCOPES)
+            .await?;
+        Ok(serde_json::from_slice::<Page>(&bytes)?)
+    }
+
+    /// Deserializes a presentation body and reports it to the response sink, if any.
+    fn parse_presentation_body(&self, presentation_id: &str, bytes: &[u8]) -> Result<Presentation> {
+        let parsed = parse_presentation(bytes);
+        if let Some(sink) = &self.response_sink {
+            let outcome = match &parsed {
+                Err(SlidesApiError::PresentationParse(problem)) => {
+                    ResponseOutcome::DeserializationFailed(problem)
+                }
+                _ => ResponseOutcome::Parsed,
+            };
+            sink.on_response(presentation_id, bytes, &outcome);
+        }
+        parsed
+    }
+
+    /// Applies a list of updates to a presentation (`presentations.batchUpdate`).
+    ///
+    /// The requests are applied atomically by the API: if any request is invalid, none
+    /// of them are applied.
+    ///
+    /// # Arguments
+    ///
+    /// * `presentation_id` - The ID of the presentation to update.
+    /// * `requests` - The typed updates to apply, in order.
+    /// * `write_control` - Optional revision guard; the update fails if the presentation
+    ///   has been modified since `required_revision_id`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `BatchUpdatePresentationResponse`, whose `replies` map 1:1
+    /// with `requests`, or a `SlidesApiError` on failure.
+    pub async fn batch_update(
+        &self,
+        presentation_id: &str,
+        requests: Vec<Request>,
+        write_control: Option<WriteControl>,
+    ) -> Result<BatchUpdatePresentationResponse> {
+        validate_presentation_id(presentation_id)?;
+        if requests.is_empty() {
+            return Err(SlidesApiError::InvalidInput(
+                "batchUpdate requires at least one request".to_string(),
+            ));
+        }
+
+        let api_url = format!(
+            "{}/v1/presentations/{}:batchUpdate",
+            self.base_url,
+            encode_path_segment(presentation_id)
+        );
+        // A batch pinned to a revision fails instead of applying twice, so it is safe to
+        // retry; otherwise a 5xx may hide a batch the API already applied.
+        let idempotency = if write_control
+            .as_ref()
+            .is_some_and(|control| control.required_revision_id.is_some())
+        {
+            Idempotency::Idempotent
+        } else {
+            Idempotency::NonIdempotent
+        };
+        let body = BatchUpdatePresentationRequest {
+            requests,
+            write_control,
+        };
+        let bytes = self
+            .execute_with_idempotency(
+                Method::POST,
+                &api_url,
+                Some(serde_json::to_vec(&body)?),
+                WRITE_SCOPES,
+                idempotency,
+            )
+            .await?;
+        Ok(serde_json::from_slice::<BatchUpdatePresentationResponse>(
+            &bytes,
+        )?)
+    }
+
+    /// Fetches the raw JSON body of a presentation.
+    async fn fetch_presentation_bytes(
+        &self,
+        presentation_id: &str,
+        fields: Option<String>,
+    ) -> Result<Vec<u8>> {
+        validate_presentation_id(presentation_id)?;
+        let api_url = with_fields(
+            format!(
+                "{}/v1/presentations/{}",
+                self.base_url,
+                encode_path_segment(presentation_id)
+            ),
+            fields,
+        )?;
+        self.execute(Method::GET, &api_url, None, READ_SCOPES).await
+    }
+
+    /// Sends an authorized request and returns the response body of a 2xx response,
+    /// retrying transient failures according to the client's `RetryPolicy`.
+    ///
+    /// Requests with idempotent methods (`GET`) are retried on every transient failure,
+    /// others only on `429`; use `execute_with_idempotency` to override this.
+    pub(crate) async fn execute(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<Vec<u8>>,
+        scopes: &[&str],
+    ) -> Result<Vec<u8>> {
+        let idempotency = Idempotency::of_method(&method);
+        self.execute_with_idempotency(method, url, body, scopes, idempotency)
+            .await
+    }
+
+    /// Like `execute`, with the failures that may be retried chosen by `idempotency`.
+    ///
+    /// Errors of requests that were retried at least once are wrapped in
+    /// `SlidesApiError::RetryFailed`.
+    pub(crate) async fn execute_with_idempotency(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<Vec<u8>>,
+        scopes: &[&str],
+        idempotency: Idempotency,
+    ) -> Result<Vec<u8>> {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let (error, retry_after) = match self
+                .execute_once(method.clone(), url, body.clone(), scopes)
+                .await
+            {
+                Ok(bytes) => return Ok(bytes),
+                Err(failure) => failure,
+            };
+
+            if attempts > self.retry_policy.max_retries
+                || !self.retry_policy.should_retry(&error, idempotency)
+            {
+                if attempts == 1 {
+                    return Err(error);
+                }
+                return Err(SlidesApiError::RetryFailed {
+                    attempts,
+                    last_status: error.status(),
+                    source: Box::new(error),
+                });
+            }
+
+            let delay = self.retry_policy.delay(attempts, retry_after);
+            log::warn!(
+                "Request to {} failed (attempt {}): {}. Retrying in {:?}.",
+                url,
+                attempts,
+                error,
+                delay
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Sends a single authorized request.
+    /// On failure, also returns the delay requested by a `Retry-After` header, if any.
+    async fn execute_once(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<Vec<u8>>,
+        scopes: &[&str],
+    ) -> std::result::Result<Vec<u8>, (SlidesApiError, Option<Duration>)> {
+        let access_token = self
+            .token_provider
+            .access_token(scopes)
+            .await
+            .map_err(|e| (e, None))?;
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+
+        let mut request = self
+            .http_client
+            .request(method, url)
+            .header(AUTHORIZATION, format!("Bearer {}", access_token))
+            .header(ACCEPT, "application/json");
+        if let Some(body) = body {
+            request = request.header(CONTENT_TYPE, "application/json").body(body);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| (SlidesApiError::Network(e), None))?;
+
+        // Handle response
+        let status = response.status();
+        if status.is_success() {
+            let bytes = response
+                .bytes()
+                .await
+                .map_err(|e| (SlidesApiError::Network(e), None))?;
+            Ok(bytes.to_vec())
+        } else {
+            // Handle API-level errors (non-2xx status codes)
+            let retry_after = parse_retry_after(response.headers());
+            let error_text = response
+                .text()
+                .await
+                .map_err(|e| (SlidesApiError::Network(e), retry_after))?;
+            Err((api_error_from_body(status, &error_text), retry_after))
+        }
+    }
+}
+
 /// Fetches a presentation resource from the Google Slides API using Service Account credentials.
 ///
 /// Reads the service account key file path from the `GOOGLE_APPLICATION_CREDENTIALS`
 /// environment variable. Ensure `dotenvy::dotenv().ok();` has been called beforehand.
-/// If JSON deserialization fails, writes the raw JSON response to `deserialization_error.json`.
+///
+/// This builds a new authenticator on every call and does not capture responses; use a
+/// long-lived `SlidesClient` (optionally with a `ResponseSink`) when you need either.
 ///
 /// # Arguments
 ///
@@ -48,91 +662,639 @@ pub async fn get_presentation_sa(
     presentation_id: &str,
     http_client: &reqwest::Client, // Keep reqwest client for the main API call
 ) -> Result<Presentation> {
+    validate_presentation_id(presentation_id)?;
+    service_account_client(http_client)
+        .await?
+        .get_presentation(presentation_id)
+        .await
+}
+
+/// Applies a list of updates to a presentation using Service Account credentials.
+///
+/// Requires the `presentations` (read/write) scope to be granted to the service account,
+/// and the presentation to be shared with it. See `SlidesClient::batch_update`.
+///
+/// # Arguments
+///
+/// * `presentation_id` - The ID of the presentation to update.
+/// * `requests` - The typed updates to apply, in order.
+/// * `write_control` - Optional revision guard.
+/// * `http_client` - An asynchronous `reqwest::Client` instance.
+///
+/// # Returns
+///
+/// A `Result` containing the `BatchUpdatePresentationResponse`, or a `SlidesApiError` on failure.
+pub async fn batch_update_presentation_sa(
+    presentation_id: &str,
+    requests: Vec<Request>,
+    write_control: Option<WriteControl>,
+    http_client: &reqwest::Client,
+) -> Result<BatchUpdatePresentationResponse> {
+    validate_presentation_id(presentation_id)?;
+    service_account_client(http_client)
+        .await?
+        .batch_update(presentation_id, requests, write_control)
+        .await
+}
+
+/// Builds a `SlidesClient` authenticated with the service account key referenced by
+/// the `GOOGLE_APPLICATION_CREDENTIALS` environment variable.
+async fn service_account_client(http_client: &reqwest::Client) -> Result<SlidesClient> {
+    SlidesClient::builder()
+        .set_http_client(http_client.clone())
+        .set_token_provider(ServiceAccountTokenProvider::from_env().await?)
+        .build()
+}
+
+/// Appends the `fields` query parameter to `url`, if a mask was given.
+fn with_fields(url: String, fields: Option<String>) -> Result<String> {
+    let params: Vec<(&str, String)> = fields
+        .filter(|fields| !fields.is_empty())
+        .map(|fields| ("fields", fields))
+        .into_iter()
+        .collect();
+    with_query(url, &params)
+}
+
+/// Characters escaped in a URL path segment. `:` is escaped too, as it separates custom
+/// methods such as `:batchUpdate` from the resource ID.
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b':')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'@')
+    .add(b'[')
+    .add(b'\\')
+    .add(b']')
+    .add(b'^')
+    .add(b'`')
+    .add(b'{')
+    .add(b'|')
+    .add(b'}');
+
+/// Percent-encodes an ID for use as one segment of a URL path.
+pub(crate) fn encode_path_segment(segment: &str) -> String {
+    utf8_percent_encode(segment, PATH_SEGMENT).to_string()
+}
+
+/// Appends the given query parameters (URL-encoded) to `url`.
+fn with_query(url: String, params: &[(&str, String)]) -> Result<String> {
+    if params.is_empty() {
+        return Ok(url);
+    }
+    reqwest::Url::parse_with_params(&url, params)
+        .map(String::from)
+        .map_err(|e| SlidesApiError::InvalidInput(format!("Invalid URL '{}': {}", url, e)))
+}
+
+/// Renders a SCREAMING_SNAKE_CASE API enum as its wire value (e.g. `ThumbnailSize::Large` -> `LARGE`).
+fn enum_query_value<T: serde::Serialize>(value: &T) -> Result<String> {
+    match serde_json::to_value(value)? {
+        serde_json::Value::String(s) => Ok(s),
+        other => Err(SlidesApiError::InvalidInput(format!(
+            "Expected an enum value, got {}",
+            other
+        ))),
+    }
+}
+
+fn validate_presentation_id(presentation_id: &str) -> Result<()> {
     if presentation_id.is_empty() {
         return Err(SlidesApiError::InvalidInput(
             "Presentation ID cannot be empty".to_string(),
         ));
     }
+    Ok(())
+}
 
-    // --- Service Account Authentication (yup-oauth2 v12+) ---
-    let key_file_path = env::var("GOOGLE_APPLICATION_CREDENTIALS")?;
-    let sa_key = read_service_account_key(Path::new(&key_file_path))
-        .await
-        .map_err(|e| {
-            SlidesApiError::AuthSetupError(format!(
-                "Failed to read service account key from '{}': {}",
-                key_file_path, e
-            ))
-        })?;
-    let auth = ServiceAccountAuthenticator::builder(sa_key).build().await?;
-    let scopes = &[
-        "https://www.googleapis.com/auth/presentations.readonly",
-        "https://www.googleapis.com/auth/drive.readonly",
-    ];
-    let token = auth.token(scopes).await?;
-    let access_token = token
-        .token()
-        .expect("OAuth token unexpectedly missing token field after successful retrieval");
-    // --- End Authentication Section ---
-
-    let api_url = format!(
-        "https://slides.googleapis.com/v1/presentations/{}",
-        presentation_id
-    );
-
-    // Perform the main API GET request
-    let response = http_client
-        .get(&api_url)
-        .header(AUTHORIZATION, format!("Bearer {}", access_token))
-        .header(ACCEPT, "application/json")
-        .send()
-        .await
-        .map_err(SlidesApiError::Network)?;
-
-    // Handle response
-    let status = response.status();
-    if status.is_success() {
-        let bytes = response.bytes().await.map_err(SlidesApiError::Network)?;
-
-        // Attempt to deserialize.
-        match serde_json::from_slice::<Presentation>(&bytes) {
-            Ok(presentation) => {
-                let filename = "changed_presentation.json";
-                fs::write(filename, &bytes).expect("Error write file");
-                Ok(presentation)
-            }
-            Err(e) => {
-                // --- Write failing JSON to file ---
-                let filename = "deserialization_error.json";
-                eprintln!("-----------------------------------------");
-                eprintln!("JSON Deserialization Error: {}", e);
-                match fs::write(filename, &bytes) {
-                    Ok(_) => eprintln!(
-                        "Raw JSON response body saved to '{}' for debugging.",
-                        filename
-                    ),
-                    Err(io_err) => eprintln!(
-                        "Failed to write error JSON to file '{}': {}",
-                        filename, io_err
-                    ),
-                }
-                // Optionally print a snippet to stderr as well
-                let json_snippet = String::from_utf8_lossy(&bytes[..bytes.len().min(500)]);
-                eprintln!("Failing JSON snippet:\n{}", json_snippet);
-                eprintln!("-----------------------------------------");
+fn validate_page_object_id(page_object_id: &str) -> Result<()> {
+    if page_object_id.is_empty() {
+        return Err(SlidesApiError::InvalidInput(
+            "Page object ID cannot be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Builds a `SlidesApiError::ApiError` from a non-2xx response body,
+/// preferring the message of a standard Google API error payload.
+fn api_error_from_body(status: reqwest::StatusCode, error_text: &str) -> SlidesApiError {
+    let message = match serde_json::from_str::<GoogleApiErrorResponse>(error_text) {
+        Ok(google_error) => google_error.error.message,
+        Err(_) => format!("API request failed with status {}: {}", status, error_text),
+    };
+    SlidesApiError::ApiError { status, message }
+}
+
+#[cfg(test)]
+#[cfg(feature = "emulator")]
+mod tests {
+    use super::*;
+    use crate::auth::StaticTokenProvider;
+    use crate::emulator::SlidesEmulator;
+    use crate::models::page::PageType;
+
+    const DECK: &str = r#"{
+        "presentationId": "deck",
+        "title": "Quarterly Review",
+        "slides": [{"objectId": "s1", "pageType": "SLIDE", "pageElements": []}, {"objectId": "s2"}]
+    }"#;
+
+    async fn start_emulator() -> SlidesEmulator {
+        SlidesEmulator::builder()
+            .add_presentation_json(DECK)
+            .unwrap()
+            .start()
+            .await
+            .unwrap()
+    }
+
+    fn client_for(emulator: &SlidesEmulator) -> SlidesClient {
+        SlidesClient::builder()
+            .set_token_provider(StaticTokenProvider::new("test-token"))
+            .set_base_url(emulator.base_url())
+            .set_retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_builder_requires_token_provider_and_normalizes_base_url() {
+        let error = SlidesClient::builder().build().err().unwrap();
+        assert!(matches!(error, SlidesApiError::AuthSetupError(_)));
+
+        let client = SlidesClient::builder()
+            .set_token_provider(StaticTokenProvider::new("test-token"))
+            .build()
+            .unwrap();
+        assert_eq!(client.base_url(), DEFAULT_BASE_URL);
+
+        let client = SlidesClient::builder()
+            .set_token_provider(StaticTokenProvider::new("test-token"))
+            .set_base_url("http://127.0.0.1:8085/")
+            .build()
+            .unwrap();
+        assert_eq!(client.base_url(), "http://127.0.0.1:8085");
+    }
+
+    #[tokio::test]
+    async fn test_get_presentation_uses_base_url_override() {
+        let emulator = start_emulator().await;
+        let client = client_for(&emulator);
+
+        let presentation = client.get_presentation("deck").await.unwrap();
+        assert_eq!(presentation.presentation_id, "deck");
+        assert_eq!(presentation.title.as_deref(), Some("Quarterly Review"));
+        assert_eq!(presentation.slides.unwrap().len(), 2);
+        assert_eq!(emulator.request_count(), 1);
+
+        let error = client.get_presentation("missing").await.unwrap_err();
+        assert_eq!(error.status(), Some(reqwest::StatusCode::NOT_FOUND));
+        assert!(matches!(
+            client.get_presentation("").await,
+            Err(SlidesApiError::InvalidInput(_))
+        ));
+        assert_eq!(emulator.request_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_field_masked_presentation_and_page_fetches() {
+        use crate::field_mask::PageField;
+
+        let emulator = start_emulator().await;
+        let client = client_for(&emulator);
+
+        let mask = PresentationFieldMask::new()
+            .field(PresentationField::Title)
+            .field(PresentationField::Slides(vec![PageField::ObjectId]));
+        let partial = client
+            .get_presentation_with_fields("deck", &mask)
+            .await
+            .unwrap();
+        assert_eq!(partial.title.as_deref(), Some("Quarterly Review"));
+        assert_eq!(partial.revision_id, None);
+        assert_eq!(partial.slides.unwrap().len(), 2);
+
+        let page = client.get_page("deck", "s1").await.unwrap();
+        assert_eq!(page.object_id, "s1");
+        assert_eq!(page.page_type, Some(PageType::Slide));
+        assert!(page.page_elements.is_some());
+
+        let page_mask = PageFieldMask::new().field(PageField::PageType);
+        let page = client
+            .get_page_with_fields("deck", "s1", &page_mask)
+            .await
+            .unwrap();
+        assert_eq!(page.page_type, Some(PageType::Slide));
+        assert!(page.page_elements.is_none());
+
+        let requests = emulator.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests.iter().all(|request| request.method == "GET"));
+        assert_eq!(
+            requests[0].target,
+            "/v1/presentations/deck?fields=presentationId%2Ctitle%2Cslides%28objectId%29"
+        );
+        assert_eq!(
+            requests[0].query_param("fields").as_deref(),
+            Some("presentationId,title,slides(objectId)")
+        );
+        assert_eq!(requests[1].target, "/v1/presentations/deck/pages/s1");
+        assert_eq!(requests[2].path(), "/v1/presentations/deck/pages/s1");
+        assert_eq!(
+            requests[2].query_param("fields").as_deref(),
+            Some("objectId,pageType")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_page_thumbnail_query_and_download() {
+        use crate::models::thumbnail::{ThumbnailMimeType, ThumbnailSize};
+
+        let emulator = start_emulator().await;
+        let client = client_for(&emulator);
+
+        let properties = ThumbnailProperties {
+            thumbnail_size: Some(ThumbnailSize::Medium),
+            mime_type: Some(ThumbnailMimeType::Png),
+            ..Default::default()
+        };
+        let thumbnail = client
+            .get_page_thumbnail("deck", "s1", &properties)
+            .await
+            .unwrap();
+        assert_eq!(thumbnail.width, Some(800));
+        assert_eq!(thumbnail.height, Some(450));
+        let png = client.download_thumbnail(&thumbnail).await.unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
 
-                // Return the specific deserialization error
-                Err(SlidesApiError::JsonDeserialization(e))
+        // Unset properties are left to the API defaults.
+        let default_thumbnail = client
+            .get_page_thumbnail("deck", "s1", &ThumbnailProperties::default())
+            .await
+            .unwrap();
+        assert_eq!(default_thumbnail.width, Some(1600));
+
+        let requests = emulator.requests();
+        assert_eq!(
+            requests[0].path(),
+            "/v1/presentations/deck/pages/s1/thumbnail"
+        );
+        assert_eq!(
+            requests[0]
+                .query_param("thumbnailProperties.thumbnailSize")
+                .as_deref(),
+            Some("MEDIUM")
+        );
+        assert_eq!(
+            requests[0]
+                .query_param("thumbnailProperties.mimeType")
+                .as_deref(),
+            Some("PNG")
+        );
+        assert_eq!(requests[1].method, "GET");
+        assert_eq!(requests[1].path(), "/thumbnails/deck/s1.png");
+        assert_eq!(
+            requests[2].target,
+            "/v1/presentations/deck/pages/s1/thumbnail"
+        );
+
+        let error = client
+            .get_page_thumbnail("deck", "missing", &properties)
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(reqwest::StatusCode::NOT_FOUND));
+        let no_url = Thumbnail {
+            content_url: None,
+            ..default_thumbnail
+        };
+        assert!(matches!(
+            client.download_thumbnail(&no_url).await,
+            Err(SlidesApiError::InvalidInput(_))
+        ));
+    }
+
+    /// Hands out a static token after a short delay, tracking how many calls overlap.
+    #[derive(Default)]
+    struct CountingTokenProvider {
+        in_flight: std::sync::atomic::AtomicUsize,
+        max_in_flight: std::sync::atomic::AtomicUsize,
+    }
+
+    impl TokenProvider for CountingTokenProvider {
+        fn access_token<'a>(&'a self, _scopes: &'a [&'a str]) -> crate::auth::TokenFuture<'a> {
+            use std::sync::atomic::Ordering;
+            Box::pin(async move {
+                let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                self.in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok("test-token".to_string())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_many_keeps_input_order_and_isolates_failures() {
+        let emulator = start_emulator().await;
+        for id in ["a", "b", "c", "d"] {
+            emulator
+                .insert_presentation_json(&format!(r#"{{"presentationId": "{}"}}"#, id))
+                .unwrap();
+        }
+        let token_provider = Arc::new(CountingTokenProvider::default());
+        let client = SlidesClient::builder()
+            .set_shared_token_provider(token_provider.clone())
+            .set_base_url(emulator.base_url())
+            .set_retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap();
+
+        let progress = std::sync::Mutex::new(Vec::new());
+        let ids = ["a", "b", "missing", "c", "deck", "d"];
+        let results = client
+            .fetch_many(ids, 2, |update| {
+                assert_eq!(update.total, 6);
+                progress.lock().unwrap().push((
+                    update.presentation_id.to_string(),
+                    update.succeeded,
+                    update.completed,
+                ));
+            })
+            .await;
+
+        let result_ids: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(result_ids, ids);
+        for (id, result) in &results {
+            match result {
+                Ok(presentation) => assert_eq!(&presentation.presentation_id, id),
+                Err(error) => {
+                    assert_eq!(id, "missing");
+                    assert_eq!(error.status(), Some(reqwest::StatusCode::NOT_FOUND));
+                }
             }
         }
-        // --- End corrected success handling ---
-    } else {
-        // Handle API-level errors (non-2xx status codes)
-        let error_text = response.text().await.map_err(SlidesApiError::Network)?;
-        let message = match serde_json::from_str::<GoogleApiErrorResponse>(&error_text) {
-            Ok(google_error) => google_error.error.message,
-            Err(_) => format!("API request failed with status {}: {}", status, error_text),
+        assert_eq!(results.iter().filter(|(_, r)| r.is_err()).count(), 1);
+
+        let progress = progress.into_inner().unwrap();
+        let completed: Vec<usize> = progress.iter().map(|(_, _, n)| *n).collect();
+        assert_eq!(completed, vec![1, 2, 3, 4, 5, 6]);
+        assert!(progress
+            .iter()
+            .all(|(id, succeeded, _)| *succeeded == (id != "missing")));
+
+        // At most `concurrency` fetches ask for a token at the same time.
+        let max_in_flight = token_provider
+            .max_in_flight
+            .load(std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(max_in_flight, 2);
+    }
+
+    /// A response captured by `RecordingSink`.
+    #[derive(Debug, PartialEq)]
+    struct CapturedResponse {
+        presentation_id: String,
+        body: Vec<u8>,
+        failed_path: Option<String>,
+    }
+
+    /// Records every response it receives.
+    #[derive(Clone, Default)]
+    struct RecordingSink(Arc<std::sync::Mutex<Vec<CapturedResponse>>>);
+
+    impl ResponseSink for RecordingSink {
+        fn on_response(&self, presentation_id: &str, body: &[u8], outcome: &ResponseOutcome<'_>) {
+            self.0.lock().unwrap().push(CapturedResponse {
+                presentation_id: presentation_id.to_string(),
+                body: body.to_vec(),
+                failed_path: match outcome {
+                    ResponseOutcome::Parsed => None,
+                    ResponseOutcome::DeserializationFailed(problem) => Some(problem.path_string()),
+                },
+            });
+        }
+    }
+
+    #[tokio::test]
+    async fn test_response_sink_receives_raw_bodies_and_outcomes() {
+        let emulator = start_emulator().await;
+        emulator
+            .insert_presentation_json(r#"{"presentationId": "broken", "slides": 1}"#)
+            .unwrap();
+        let sink = RecordingSink::default();
+        let client = SlidesClient::builder()
+            .set_token_provider(StaticTokenProvider::new("test-token"))
+            .set_base_url(emulator.base_url())
+            .set_response_sink(sink.clone())
+            .build()
+            .unwrap();
+
+        client.get_presentation("deck").await.unwrap();
+        let error = client.get_presentation("broken").await.unwrap_err();
+        assert!(matches!(error, SlidesApiError::PresentationParse(_)));
+
+        let captured = sink.0.lock().unwrap();
+        assert_eq!(captured.len(), 2);
+        assert_eq!(captured[0].presentation_id, "deck");
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&captured[0].body).unwrap(),
+            emulator.presentation_json("deck").unwrap()
+        );
+        assert_eq!(captured[0].failed_path, None);
+        assert_eq!(captured[1].presentation_id, "broken");
+        assert_eq!(captured[1].failed_path.as_deref(), Some("slides"));
+    }
+
+    #[tokio::test]
+    async fn test_nothing_is_written_without_a_response_sink() {
+        // The files `FileDumpSink` writes to by default, and that older versions always wrote.
+        let default_paths = ["changed_presentation.json", "deserialization_error.json"];
+        let modified = || {
+            default_paths
+                .iter()
+                .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+                .collect::<Vec<_>>()
         };
-        Err(SlidesApiError::ApiError { status, message })
+        let before = modified();
+
+        let emulator = start_emulator().await;
+        emulator
+            .insert_presentation_json(r#"{"presentationId": "broken", "slides": 1}"#)
+            .unwrap();
+        let client = client_for(&emulator);
+        client.get_presentation("deck").await.unwrap();
+        assert!(client.get_presentation("broken").await.is_err());
+
+        assert_eq!(modified(), before);
+    }
+
+    #[tokio::test]
+    async fn test_batch_update_is_only_retried_when_safe() {
+        use crate::models::requests::{CreateSlideRequest, WriteControl};
+
+        let emulator = start_emulator().await;
+        let client = SlidesClient::builder()
+            .set_token_provider(StaticTokenProvider::new("test-token"))
+            .set_base_url(emulator.base_url())
+            .set_retry_policy(RetryPolicy {
+                initial_backoff: Duration::from_millis(1),
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+        let create_slide = || vec![Request::CreateSlide(CreateSlideRequest::default())];
+
+        // Without a revision guard a 5xx may hide an applied batch, so it is not retried.
+        emulator.fail_next(1, reqwest::StatusCode::SERVICE_UNAVAILABLE);
+        let error = client
+            .batch_update("deck", create_slide(), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, SlidesApiError::ApiError { .. }));
+        assert_eq!(emulator.request_count(), 1);
+
+        // Rate-limit rejections are always retried.
+        emulator.fail_next(1, reqwest::StatusCode::TOO_MANY_REQUESTS);
+        client
+            .batch_update("deck", create_slide(), None)
+            .await
+            .unwrap();
+        assert_eq!(emulator.request_count(), 3);
+
+        // A batch pinned to a revision cannot be applied twice, so 5xx responses are retried.
+        let revision_id = client.get_presentation("deck").await.unwrap().revision_id;
+        emulator.fail_next(1, reqwest::StatusCode::SERVICE_UNAVAILABLE);
+        client
+            .batch_update(
+                "deck",
+                create_slide(),
+                Some(WriteControl {
+                    required_revision_id: revision_id,
+                }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(emulator.request_count(), 6);
+        assert_eq!(
+            emulator.presentation_json("deck").unwrap()["slides"]
+                .as_array()
+                .unwrap()
+                .len(),
+            4
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cached_fetch_only_probes_the_revision_when_unchanged() {
+        let emulator = start_emulator().await;
+        let client = client_for(&emulator);
+        let dir = std::env::temp_dir().join(format!(
+            "gslides-client-cache-test-{}-unchanged",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        let cache = PresentationCache::new(&dir).unwrap();
+
+        let first = client
+            .get_presentation_cached("deck", &cache)
+            .await
+            .unwrap();
+        let second = client
+            .get_presentation_cached("deck", &cache)
+            .await
+            .unwrap();
+        assert_eq!(second.title.as_deref(), Some("Quarterly Review"));
+        assert_eq!(second.revision_id, first.revision_id);
+
+        let requests = emulator.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].query_param("fields"), None);
+        assert_eq!(
+            requests[1].query_param("fields").as_deref(),
+            Some("presentationId,revisionId")
+        );
+        assert_eq!(cache.list_revisions("deck").unwrap().len(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cached_fetch_stores_a_changed_revision() {
+        let emulator = start_emulator().await;
+        let client = client_for(&emulator);
+        let dir = std::env::temp_dir().join(format!(
+            "gslides-client-cache-test-{}-changed",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        let cache = PresentationCache::new(&dir).unwrap();
+
+        client
+            .get_presentation_cached("deck", &cache)
+            .await
+            .unwrap();
+        emulator
+            .insert_presentation_json(
+                r#"{"presentationId": "deck", "revisionId": "rev_2", "title": "Renamed"}"#,
+            )
+            .unwrap();
+        let changed = client
+            .get_presentation_cached("deck", &cache)
+            .await
+            .unwrap();
+        assert_eq!(changed.title.as_deref(), Some("Renamed"));
+
+        let fields: Vec<_> = emulator
+            .requests()
+            .iter()
+            .map(|request| request.query_param("fields"))
+            .collect();
+        assert_eq!(
+            fields,
+            [None, Some("presentationId,revisionId".to_string()), None]
+        );
+        let revisions: Vec<_> = cache
+            .list_revisions("deck")
+            .unwrap()
+            .into_iter()
+            .map(|revision| revision.revision_id)
+            .collect();
+        assert_eq!(revisions.len(), 2);
+        assert!(revisions.contains(&"rev_2".to_string()));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cached_fetch_falls_back_to_network_on_corrupt_entry() {
+        let emulator = start_emulator().await;
+        let client = client_for(&emulator);
+        let dir = std::env::temp_dir().join(format!(
+            "gslides-client-cache-test-{}-corrupt",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        let cache = PresentationCache::new(&dir).unwrap();
+
+        let stored = client
+            .get_presentation_cached("deck", &cache)
+            .await
+            .unwrap();
+        let revisions = cache.list_revisions("deck").unwrap();
+        std::fs::write(&revisions[0].path, b"{ not json").unwrap();
+
+        let presentation = client
+            .get_presentation_cached("deck", &cache)
+            .await
+            .unwrap();
+        assert_eq!(presentation.revision_id, stored.revision_id);
+        assert_eq!(emulator.requests()[1].query_param("fields"), None);
+        // The full fetch replaced the corrupt copy.
+        assert!(cache.latest("deck").unwrap().is_some());
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 }
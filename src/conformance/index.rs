@@ -0,0 +1,347 @@
+//! Reads the JSON shape of the model types from their Rust source.
+//!
+//! Only the serde attributes this crate uses are understood: `rename_all`, `rename`,
+//! `alias`, `flatten`, `skip` and `untagged`.
+
+use super::ConformanceError;
+use std::collections::HashMap;
+use std::fmt;
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Fields, GenericArgument, Item, Meta, PathArguments, Token, Type};
+
+/// The JSON type of a Rust field.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum RustType {
+    String,
+    Integer,
+    Number,
+    Boolean,
+    /// `serde_json::Value` and other types that accept any JSON.
+    Any,
+    Array(Box<RustType>),
+    /// A map keyed by strings (or integers written as strings).
+    Map(Box<RustType>),
+    /// A struct or enum of the models, by name.
+    Named(String),
+}
+
+impl fmt::Display for RustType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RustType::String => write!(f, "String"),
+            RustType::Integer => write!(f, "integer"),
+            RustType::Number => write!(f, "float"),
+            RustType::Boolean => write!(f, "bool"),
+            RustType::Any => write!(f, "Value"),
+            RustType::Array(item) => write!(f, "Vec<{}>", item),
+            RustType::Map(value) => write!(f, "Map<{}>", value),
+            RustType::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// A JSON field of a model: a struct field, or a variant of an externally tagged enum.
+#[derive(Debug, Clone)]
+pub(crate) struct RustField {
+    pub(crate) json_name: String,
+    pub(crate) ty: RustType,
+}
+
+/// A struct, or an enum serialized as a single-key object.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RustObject {
+    pub(crate) fields: Vec<RustField>,
+    /// Types whose fields are flattened into this object.
+    pub(crate) flattened: Vec<String>,
+}
+
+/// An enum serialized as a string.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RustStringEnum {
+    pub(crate) values: Vec<String>,
+}
+
+/// Every model type found in the sources, by Rust name.
+#[derive(Debug, Default)]
+pub(crate) struct ModelIndex {
+    pub(crate) objects: HashMap<String, RustObject>,
+    pub(crate) string_enums: HashMap<String, RustStringEnum>,
+}
+
+impl ModelIndex {
+    /// Parses model sources, given as `(file name, source)` pairs.
+    pub(crate) fn from_sources<'a>(
+        sources: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Self, ConformanceError> {
+        let mut index = ModelIndex::default();
+        let mut manual_structs = Vec::new();
+        for (file_name, source) in sources {
+            let file = syn::parse_file(source).map_err(|e| ConformanceError::Source {
+                file_name: file_name.to_string(),
+                message: e.to_string(),
+            })?;
+            for item in file.items {
+                match item {
+                    Item::Struct(item) if derives_serde(&item.attrs) => {
+                        let object = struct_object(&item.attrs, &item.fields);
+                        index.objects.insert(item.ident.to_string(), object);
+                    }
+                    // A struct with hand-written serde impls, e.g. `Link`, is described
+                    // by a `<Name>Fields` helper struct.
+                    Item::Struct(item) => manual_structs.push(item.ident.to_string()),
+                    Item::Enum(item) => index.add_enum(&item),
+                    _ => {}
+                }
+            }
+        }
+        for name in manual_structs {
+            if let Some(helper) = index.objects.get(&format!("{}Fields", name)).cloned() {
+                index.objects.insert(name, helper);
+            }
+        }
+        Ok(index)
+    }
+
+    fn add_enum(&mut self, item: &syn::ItemEnum) {
+        // Enums with hand-written serde impls (e.g. `PageElementKind`) key their
+        // variants in camelCase, like the derived ones.
+        let rename_all = serde_value(&item.attrs, "rename_all")
+            .or_else(|| (!derives_serde(&item.attrs)).then(|| "camelCase".to_string()));
+        let mut values = Vec::new();
+        let mut object = RustObject::default();
+        for variant in &item.variants {
+            if has_serde_flag(&variant.attrs, "untagged") || has_serde_flag(&variant.attrs, "skip")
+            {
+                continue;
+            }
+            let name = serde_value(&variant.attrs, "rename").unwrap_or_else(|| {
+                rename_variant(&variant.ident.to_string(), rename_all.as_deref())
+            });
+            match &variant.fields {
+                Fields::Unit => {
+                    values.push(name);
+                    values.extend(serde_values(&variant.attrs, "alias"));
+                }
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                    object.fields.push(RustField {
+                        json_name: name,
+                        ty: rust_type(&fields.unnamed[0].ty),
+                    });
+                }
+                _ => {}
+            }
+        }
+        let name = item.ident.to_string();
+        if object.fields.is_empty() {
+            self.string_enums.insert(name, RustStringEnum { values });
+        } else {
+            self.objects.insert(name, object);
+        }
+    }
+
+    /// Returns true if a model type of this name exists.
+    pub(crate) fn contains(&self, name: &str) -> bool {
+        self.objects.contains_key(name) || self.string_enums.contains_key(name)
+    }
+
+    /// The JSON fields of an object type, including flattened ones.
+    pub(crate) fn fields_of(&self, name: &str) -> Vec<RustField> {
+        let mut fields = Vec::new();
+        self.collect_fields(name, &mut fields, 0);
+        fields
+    }
+
+    fn collect_fields(&self, name: &str, fields: &mut Vec<RustField>, depth: usize) {
+        let Some(object) = self.objects.get(name) else {
+            return;
+        };
+        fields.extend(object.fields.iter().cloned());
+        if depth < 8 {
+            for flattened in &object.flattened {
+                self.collect_fields(flattened, fields, depth + 1);
+            }
+        }
+    }
+}
+
+fn struct_object(attrs: &[Attribute], fields: &Fields) -> RustObject {
+    let rename_all = serde_value(attrs, "rename_all");
+    let mut object = RustObject::default();
+    let Fields::Named(fields) = fields else {
+        return object;
+    };
+    for field in &fields.named {
+        if has_serde_flag(&field.attrs, "skip") {
+            continue;
+        }
+        let ty = rust_type(&field.ty);
+        if has_serde_flag(&field.attrs, "flatten") {
+            // Flattened maps (`extra: ExtraFields`) only keep unknown fields.
+            if let RustType::Named(name) = ty {
+                object.flattened.push(name);
+            }
+            continue;
+        }
+        let ident = field
+            .ident
+            .as_ref()
+            .map(|i| i.to_string())
+            .unwrap_or_default();
+        let ident = ident.trim_start_matches("r#");
+        let json_name = serde_value(&field.attrs, "rename")
+            .unwrap_or_else(|| rename_field(ident, rename_all.as_deref()));
+        object.fields.push(RustField { json_name, ty });
+    }
+    object
+}
+
+fn derives_serde(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("derive")
+            && attr
+                .parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+                .is_ok_and(|paths| {
+                    paths.iter().any(|path| {
+                        path.segments.last().is_some_and(|segment| {
+                            segment.ident == "Serialize" || segment.ident == "Deserialize"
+                        })
+                    })
+                })
+    })
+}
+
+/// The `#[serde(...)]` entries of `attrs`.
+fn serde_metas(attrs: &[Attribute]) -> Vec<Meta> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .collect()
+}
+
+fn has_serde_flag(attrs: &[Attribute], flag: &str) -> bool {
+    serde_metas(attrs)
+        .iter()
+        .any(|meta| matches!(meta, Meta::Path(path) if path.is_ident(flag)))
+}
+
+fn serde_values(attrs: &[Attribute], key: &str) -> Vec<String> {
+    serde_metas(attrs)
+        .into_iter()
+        .filter_map(|meta| match meta {
+            Meta::NameValue(pair) if pair.path.is_ident(key) => match pair.value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(value),
+                    ..
+                }) => Some(value.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+fn serde_value(attrs: &[Attribute], key: &str) -> Option<String> {
+    serde_values(attrs, key).into_iter().next()
+}
+
+fn rust_type(ty: &Type) -> RustType {
+    let Type::Path(path) = ty else {
+        return RustType::Any;
+    };
+    let Some(segment) = path.path.segments.last() else {
+        return RustType::Any;
+    };
+    let arguments: Vec<&Type> = match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => arguments
+            .args
+            .iter()
+            .filter_map(|argument| match argument {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let name = segment.ident.to_string();
+    match (name.as_str(), arguments.as_slice()) {
+        ("Option" | "Box", [inner]) => rust_type(inner),
+        ("Vec", [item]) => RustType::Array(Box::new(rust_type(item))),
+        ("HashMap" | "IndexMap" | "BTreeMap", [_, value]) => {
+            RustType::Map(Box::new(rust_type(value)))
+        }
+        ("String", _) => RustType::String,
+        ("i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" | "usize", _) => {
+            RustType::Integer
+        }
+        ("f32" | "f64", _) => RustType::Number,
+        ("bool", _) => RustType::Boolean,
+        ("Value" | "Map" | "ExtraFields", _) => RustType::Any,
+        _ => RustType::Named(name),
+    }
+}
+
+/// Splits a snake_case field or PascalCase variant name into lowercase words.
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for ch in name.chars() {
+        if ch == '_' {
+            words.push(std::mem::take(&mut current));
+        } else if ch.is_uppercase() && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+            current.extend(ch.to_lowercase());
+        } else {
+            current.extend(ch.to_lowercase());
+        }
+    }
+    words.push(current);
+    words.retain(|word| !word.is_empty());
+    words
+}
+
+fn apply_rule(words: &[String], rule: &str) -> Option<String> {
+    let capitalized = || {
+        words
+            .iter()
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                    .unwrap_or_default()
+            })
+            .collect::<String>()
+    };
+    Some(match rule {
+        "camelCase" => {
+            let pascal = capitalized();
+            let mut chars = pascal.chars();
+            chars
+                .next()
+                .map(|first| first.to_lowercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+        "PascalCase" => capitalized(),
+        "snake_case" => words.join("_"),
+        "SCREAMING_SNAKE_CASE" => words.join("_").to_uppercase(),
+        "kebab-case" => words.join("-"),
+        "lowercase" => words.concat(),
+        "UPPERCASE" => words.concat().to_uppercase(),
+        _ => return None,
+    })
+}
+
+fn rename_field(ident: &str, rule: Option<&str>) -> String {
+    rule.and_then(|rule| apply_rule(&words(ident), rule))
+        .unwrap_or_else(|| ident.to_string())
+}
+
+fn rename_variant(ident: &str, rule: Option<&str>) -> String {
+    rule.and_then(|rule| apply_rule(&words(ident), rule))
+        .unwrap_or_else(|| ident.to_string())
+}
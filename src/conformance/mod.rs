@@ -0,0 +1,596 @@
+//! Conformance checks of the models against the Slides API discovery document.
+//!
+//! The checker reads the serde shape of every type in `src/models` from its source (embedded
+//! at build time) and compares it with each schema of a discovery document: fields the API
+//! defines but the models lack, enum values without a variant, and fields whose Rust type
+//! cannot hold the API's values. `tests` runs it on the vendored copy of the Slides v1
+//! document in `schemas/slides_v1_discovery.json` and fails if the copy is missing.
+//!
+//! ```no_run
+//! use gslides_tools::conformance::ConformanceChecker;
+//!
+//! # fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let discovery = std::fs::read_to_string("schemas/slides_v1_discovery.json")?;
+//! let report = ConformanceChecker::new()?.check(&discovery)?;
+//! println!("{}", report);
+//! # Ok(())
+//! # }
+//! ```
+
+mod index;
+
+use index::{ModelIndex, RustType};
+use serde_json::{Map, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use thiserror::Error;
+
+/// The model sources checked by `ConformanceChecker::new`, as `(file name, source)` pairs.
+pub const MODEL_SOURCES: &[(&str, &str)] = &[
+    ("bullet.rs", include_str!("../models/bullet.rs")),
+    ("colors.rs", include_str!("../models/colors.rs")),
+    ("common.rs", include_str!("../models/common.rs")),
+    ("elements.rs", include_str!("../models/elements.rs")),
+    ("font.rs", include_str!("../models/font.rs")),
+    ("group.rs", include_str!("../models/group.rs")),
+    ("image.rs", include_str!("../models/image.rs")),
+    (
+        "image_properties.rs",
+        include_str!("../models/image_properties.rs"),
+    ),
+    ("line.rs", include_str!("../models/line.rs")),
+    ("link.rs", include_str!("../models/link.rs")),
+    ("list.rs", include_str!("../models/list.rs")),
+    ("page.rs", include_str!("../models/page.rs")),
+    (
+        "page_properties.rs",
+        include_str!("../models/page_properties.rs"),
+    ),
+    ("picture.rs", include_str!("../models/picture.rs")),
+    ("placeholder.rs", include_str!("../models/placeholder.rs")),
+    ("presentation.rs", include_str!("../models/presentation.rs")),
+    ("properties.rs", include_str!("../models/properties.rs")),
+    ("requests.rs", include_str!("../models/requests.rs")),
+    ("responses.rs", include_str!("../models/responses.rs")),
+    ("shape.rs", include_str!("../models/shape.rs")),
+    (
+        "shape_properties.rs",
+        include_str!("../models/shape_properties.rs"),
+    ),
+    ("sheets_chart.rs", include_str!("../models/sheets_chart.rs")),
+    (
+        "speaker_spotlight.rs",
+        include_str!("../models/speaker_spotlight.rs"),
+    ),
+    ("table.rs", include_str!("../models/table.rs")),
+    (
+        "table_properties.rs",
+        include_str!("../models/table_properties.rs"),
+    ),
+    ("text.rs", include_str!("../models/text.rs")),
+    ("text_element.rs", include_str!("../models/text_element.rs")),
+    ("thumbnail.rs", include_str!("../models/thumbnail.rs")),
+    ("video.rs", include_str!("../models/video.rs")),
+    ("wordart.rs", include_str!("../models/wordart.rs")),
+];
+
+/// Errors raised while reading the discovery document or the model sources.
+#[derive(Error, Debug)]
+pub enum ConformanceError {
+    /// The discovery document is not valid JSON.
+    #[error("Failed to parse discovery document: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// The discovery document has no `schemas` object.
+    #[error("Invalid discovery document: {0}")]
+    InvalidDiscovery(String),
+
+    /// A model source file could not be parsed as Rust.
+    #[error("Failed to parse model source {file_name}: {message}")]
+    Source { file_name: String, message: String },
+}
+
+/// What is wrong with a schema or field of the models.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IssueKind {
+    /// No model type matches the schema.
+    MissingSchema,
+    /// The schema has a field the model type lacks. Holds the field's API type.
+    MissingField { expected: String },
+    /// An enum of the API has a value the Rust enum has no variant for.
+    MissingEnumValue { value: String },
+    /// The Rust type of a field cannot hold the API's values.
+    TypeMismatch { expected: String, found: String },
+    /// The model type has a field the schema does not define.
+    UnknownField,
+}
+
+/// One difference between a discovery schema and the models.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ConformanceIssue {
+    /// The discovery schema id, e.g. `Shadow`.
+    pub schema: String,
+    /// The JSON field name, if the issue is about a field.
+    pub field: Option<String>,
+    pub kind: IssueKind,
+}
+
+impl fmt::Display for ConformanceIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.field {
+            Some(field) => write!(f, "{}.{}: ", self.schema, field)?,
+            None => write!(f, "{}: ", self.schema)?,
+        }
+        match &self.kind {
+            IssueKind::MissingSchema => write!(f, "no model type"),
+            IssueKind::MissingField { expected } => write!(f, "missing field ({})", expected),
+            IssueKind::MissingEnumValue { value } => write!(f, "missing enum value {}", value),
+            IssueKind::TypeMismatch { expected, found } => {
+                write!(
+                    f,
+                    "type mismatch: API has {}, model has {}",
+                    expected, found
+                )
+            }
+            IssueKind::UnknownField => write!(f, "field not in the API"),
+        }
+    }
+}
+
+/// The result of checking the models against a discovery document.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConformanceReport {
+    /// The number of schemas in the discovery document.
+    pub schemas_checked: usize,
+    /// Discovery schema ids mapped to the model type they were compared with.
+    pub matched: HashMap<String, String>,
+    /// Every difference found, sorted by schema and field.
+    pub issues: Vec<ConformanceIssue>,
+}
+
+impl ConformanceReport {
+    /// Returns true if the models cover every schema, field and enum value of the API.
+    pub fn is_complete(&self) -> bool {
+        self.issues
+            .iter()
+            .all(|issue| issue.kind == IssueKind::UnknownField)
+    }
+
+    /// The issues of one schema.
+    pub fn issues_for<'a>(
+        &'a self,
+        schema: &'a str,
+    ) -> impl Iterator<Item = &'a ConformanceIssue> + 'a {
+        self.issues
+            .iter()
+            .filter(move |issue| issue.schema == schema)
+    }
+
+    fn count(&self, matches: impl Fn(&IssueKind) -> bool) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| matches(&issue.kind))
+            .count()
+    }
+}
+
+impl fmt::Display for ConformanceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} schemas, {} matched: {} missing schemas, {} missing fields, \
+             {} missing enum values, {} type mismatches, {} unknown fields",
+            self.schemas_checked,
+            self.matched.len(),
+            self.count(|kind| *kind == IssueKind::MissingSchema),
+            self.count(|kind| matches!(kind, IssueKind::MissingField { .. })),
+            self.count(|kind| matches!(kind, IssueKind::MissingEnumValue { .. })),
+            self.count(|kind| matches!(kind, IssueKind::TypeMismatch { .. })),
+            self.count(|kind| *kind == IssueKind::UnknownField),
+        )?;
+        for issue in &self.issues {
+            writeln!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares model sources with discovery documents.
+#[derive(Debug)]
+pub struct ConformanceChecker {
+    index: ModelIndex,
+}
+
+impl ConformanceChecker {
+    /// Creates a checker for the models of this crate.
+    pub fn new() -> Result<Self, ConformanceError> {
+        Self::from_sources(MODEL_SOURCES.iter().copied())
+    }
+
+    /// Creates a checker for other model sources, given as `(file name, source)` pairs.
+    pub fn from_sources<'a>(
+        sources: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Self, ConformanceError> {
+        Ok(Self {
+            index: ModelIndex::from_sources(sources)?,
+        })
+    }
+
+    /// Checks the models against a discovery document.
+    ///
+    /// A schema is compared with the model type of the same name, or else with the type
+    /// a matched model uses for a field that references it.
+    ///
+    /// # Arguments
+    ///
+    /// * `discovery_json` - The discovery document, e.g. from
+    ///   `https://slides.googleapis.com/$discovery/rest?version=v1`.
+    ///
+    /// # Returns
+    ///
+    /// The report, or an error if the document is not a discovery document.
+    pub fn check(&self, discovery_json: &str) -> Result<ConformanceReport, ConformanceError> {
+        let discovery: Value = serde_json::from_str(discovery_json)?;
+        let schemas = discovery
+            .get("schemas")
+            .and_then(Value::as_object)
+            .ok_or_else(|| ConformanceError::InvalidDiscovery("no `schemas` object".to_string()))?;
+
+        let matched = self.match_schemas(schemas);
+        let mut issues = Vec::new();
+        for (id, schema) in schemas {
+            let Some(rust_name) = matched.get(id) else {
+                issues.push(issue(id, None, IssueKind::MissingSchema));
+                continue;
+            };
+            if let Some(values) = enum_values(schema) {
+                self.check_enum_values(id, None, &values, rust_name, &mut issues);
+                continue;
+            }
+            let fields = self.index.fields_of(rust_name);
+            for (name, property) in properties(schema) {
+                match fields.iter().find(|field| field.json_name == *name) {
+                    Some(field) => {
+                        self.check_type(id, name, property, &field.ty, &matched, &mut issues)
+                    }
+                    None => issues.push(issue(
+                        id,
+                        Some(name),
+                        IssueKind::MissingField {
+                            expected: describe(property),
+                        },
+                    )),
+                }
+            }
+            let unknown: BTreeSet<&str> = fields
+                .iter()
+                .map(|field| field.json_name.as_str())
+                .filter(|name| properties(schema).all(|(known, _)| known != name))
+                .collect();
+            for name in unknown {
+                issues.push(issue(id, Some(name), IssueKind::UnknownField));
+            }
+        }
+        issues.sort();
+        issues.dedup();
+
+        Ok(ConformanceReport {
+            schemas_checked: schemas.len(),
+            matched,
+            issues,
+        })
+    }
+
+    /// Maps schema ids to model types: by name first, then through the types of the
+    /// fields that reference a schema, until nothing changes.
+    fn match_schemas(&self, schemas: &Map<String, Value>) -> HashMap<String, String> {
+        let mut matched: HashMap<String, String> = schemas
+            .keys()
+            .filter(|id| self.index.contains(id))
+            .map(|id| (id.clone(), id.clone()))
+            .collect();
+        loop {
+            let mut found = Vec::new();
+            for (id, rust_name) in &matched {
+                let fields = self.index.fields_of(rust_name);
+                for (name, property) in properties(&schemas[id]) {
+                    if let Some(field) = fields.iter().find(|field| field.json_name == *name) {
+                        referenced_types(property, &field.ty, &mut found);
+                    }
+                }
+            }
+            let before = matched.len();
+            for (reference, rust_name) in found {
+                if schemas.contains_key(&reference) && self.index.contains(&rust_name) {
+                    matched.entry(reference).or_insert(rust_name);
+                }
+            }
+            if matched.len() == before {
+                return matched;
+            }
+        }
+    }
+
+    fn check_type(
+        &self,
+        schema: &str,
+        field: &str,
+        property: &Value,
+        ty: &RustType,
+        matched: &HashMap<String, String>,
+        issues: &mut Vec<ConformanceIssue>,
+    ) {
+        let mismatch = || {
+            issue(
+                schema,
+                Some(field),
+                IssueKind::TypeMismatch {
+                    expected: describe(property),
+                    found: ty.to_string(),
+                },
+            )
+        };
+        if *ty == RustType::Any || property.get("type").and_then(Value::as_str) == Some("any") {
+            return;
+        }
+        if let Some(reference) = property.get("$ref").and_then(Value::as_str) {
+            // Schemas missing from the document are compared by name.
+            let expected = matched.get(reference).map_or(reference, String::as_str);
+            let ok = matches!(ty, RustType::Named(name)
+                if name == expected && self.index.objects.contains_key(name));
+            if !ok {
+                issues.push(mismatch());
+            }
+            return;
+        }
+        if let Some(values) = enum_values(property) {
+            match ty {
+                RustType::Named(name) if self.index.string_enums.contains_key(name) => {
+                    self.check_enum_values(schema, Some(field), &values, name, issues)
+                }
+                _ => issues.push(mismatch()),
+            }
+            return;
+        }
+        let ok = match (property.get("type").and_then(Value::as_str), ty) {
+            (Some("string"), RustType::String)
+            | (Some("integer"), RustType::Integer)
+            | (Some("number"), RustType::Number)
+            | (Some("boolean"), RustType::Boolean) => true,
+            (Some("array"), RustType::Array(item)) => {
+                if let Some(items) = property.get("items") {
+                    self.check_type(schema, field, items, item, matched, issues);
+                }
+                true
+            }
+            (Some("object"), RustType::Map(value)) => {
+                if let Some(values) = property.get("additionalProperties") {
+                    self.check_type(schema, field, values, value, matched, issues);
+                }
+                true
+            }
+            _ => false,
+        };
+        if !ok {
+            issues.push(mismatch());
+        }
+    }
+
+    fn check_enum_values(
+        &self,
+        schema: &str,
+        field: Option<&str>,
+        values: &[&str],
+        rust_name: &str,
+        issues: &mut Vec<ConformanceIssue>,
+    ) {
+        let Some(rust_enum) = self.index.string_enums.get(rust_name) else {
+            issues.push(issue(
+                schema,
+                field,
+                IssueKind::TypeMismatch {
+                    expected: format!("enum ({})", values.join(", ")),
+                    found: rust_name.to_string(),
+                },
+            ));
+            return;
+        };
+        for value in values {
+            if !rust_enum.values.iter().any(|known| known == value) {
+                issues.push(issue(
+                    schema,
+                    field,
+                    IssueKind::MissingEnumValue {
+                        value: value.to_string(),
+                    },
+                ));
+            }
+        }
+    }
+}
+
+fn issue(schema: &str, field: Option<&str>, kind: IssueKind) -> ConformanceIssue {
+    ConformanceIssue {
+        schema: schema.to_string(),
+        field: field.map(str::to_string),
+        kind,
+    }
+}
+
+/// The properties of an object schema.
+fn properties(schema: &Value) -> impl Iterator<Item = (&String, &Value)> {
+    schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+}
+
+fn enum_values(property: &Value) -> Option<Vec<&str>> {
+    property
+        .get("enum")
+        .and_then(Value::as_array)
+        .map(|values| values.iter().filter_map(Value::as_str).collect())
+}
+
+/// Collects `(schema id, Rust type)` pairs for the `$ref`s of a property.
+fn referenced_types(property: &Value, ty: &RustType, found: &mut Vec<(String, String)>) {
+    match (property, ty) {
+        (_, RustType::Named(name)) => {
+            if let Some(reference) = property.get("$ref").and_then(Value::as_str) {
+                found.push((reference.to_string(), name.clone()));
+            }
+        }
+        (_, RustType::Array(item)) => {
+            if let Some(items) = property.get("items") {
+                referenced_types(items, item, found);
+            }
+        }
+        (_, RustType::Map(value)) => {
+            if let Some(values) = property.get("additionalProperties") {
+                referenced_types(values, value, found);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Describes the API type of a property, e.g. `string (int64)` or `array of Page`.
+fn describe(property: &Value) -> String {
+    if let Some(reference) = property.get("$ref").and_then(Value::as_str) {
+        return reference.to_string();
+    }
+    if enum_values(property).is_some() {
+        return "enum".to_string();
+    }
+    let format = property.get("format").and_then(Value::as_str);
+    match property.get("type").and_then(Value::as_str) {
+        Some("array") => format!(
+            "array of {}",
+            property.get("items").map_or("any".to_string(), describe)
+        ),
+        Some("object") => match property.get("additionalProperties") {
+            Some(values) => format!("map of {}", describe(values)),
+            None => "object".to_string(),
+        },
+        Some("string") if matches!(format, Some("int64" | "uint64")) => {
+            format!("string ({})", format.unwrap_or_default())
+        }
+        Some(kind) => kind.to_string(),
+        None => "any".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    /// A fragment in the shape of the Slides v1 discovery document.
+    const DISCOVERY_FRAGMENT: &str = r#"{
+        "schemas": {
+            "Shadow": {
+                "id": "Shadow",
+                "type": "object",
+                "properties": {
+                    "type": { "type": "string", "enum": ["SHADOW_TYPE_UNSPECIFIED", "OUTER"] },
+                    "alignment": { "type": "string", "enum": ["TOP_LEFT", "CENTER"] },
+                    "transform": { "$ref": "AffineTransform" },
+                    "blurRadius": { "$ref": "Dimension" },
+                    "color": { "$ref": "OpaqueColor" },
+                    "alpha": { "type": "number", "format": "float" },
+                    "rotateWithShape": { "type": "boolean" },
+                    "propertyState": {
+                        "type": "string",
+                        "enum": ["RENDERED", "NOT_RENDERED", "INHERIT"]
+                    }
+                }
+            },
+            "Outline": {
+                "id": "Outline",
+                "type": "object",
+                "properties": {
+                    "outlineFill": { "$ref": "OutlineFill" },
+                    "weight": { "$ref": "Dimension" },
+                    "dashStyle": { "type": "string", "enum": ["SOLID", "DOT"] },
+                    "propertyState": { "type": "string", "enum": ["RENDERED"] }
+                }
+            },
+            "OutlineFill": {
+                "id": "OutlineFill",
+                "type": "object",
+                "properties": {
+                    "solidFill": { "$ref": "SolidFill" },
+                    "gradientFill": { "$ref": "GradientFill" }
+                }
+            },
+            "GradientFill": { "id": "GradientFill", "type": "object", "properties": {} },
+            "Size": {
+                "id": "Size",
+                "type": "object",
+                "properties": {
+                    "width": { "$ref": "Dimension" },
+                    "height": { "type": "integer", "format": "int32" }
+                }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_check_reports_model_gaps() {
+        let report = ConformanceChecker::new()
+            .unwrap()
+            .check(DISCOVERY_FRAGMENT)
+            .unwrap();
+        let issues: Vec<String> = report.issues.iter().map(ToString::to_string).collect();
+
+        assert!(issues
+            .contains(&"Shadow.type: type mismatch: API has enum, model has String".to_string()));
+        assert!(
+            issues.contains(&"OutlineFill.gradientFill: missing field (GradientFill)".to_string())
+        );
+        assert!(issues.contains(&"GradientFill: no model type".to_string()));
+        assert!(issues.contains(
+            &"Size.height: type mismatch: API has integer, model has Dimension".to_string()
+        ));
+        assert!(report.issues_for("Outline").next().is_none());
+        assert_eq!(report.matched.get("Size").map(String::as_str), Some("Size"));
+        assert!(!report.is_complete());
+        assert!(report
+            .to_string()
+            .starts_with("5 schemas, 4 matched: 1 missing schemas"));
+
+        let error = ConformanceChecker::new().unwrap().check("{}").unwrap_err();
+        assert!(matches!(error, ConformanceError::InvalidDiscovery(_)));
+    }
+
+    /// Checks the models against the vendored discovery document and compares the report
+    /// with `schemas/slides_v1_conformance.txt`. Set `UPDATE_CONFORMANCE_SNAPSHOT=1` to
+    /// rewrite the snapshot after changing the models.
+    #[test]
+    fn test_vendored_discovery_snapshot() {
+        let schemas = Path::new(env!("CARGO_MANIFEST_DIR")).join("schemas");
+        let discovery = std::fs::read_to_string(schemas.join("slides_v1_discovery.json")).expect(
+            "schemas/slides_v1_discovery.json is missing; vendor it with `curl -o \
+                 schemas/slides_v1_discovery.json \
+                 'https://slides.googleapis.com/$discovery/rest?version=v1'`",
+        );
+        let report = ConformanceChecker::new()
+            .unwrap()
+            .check(&discovery)
+            .unwrap()
+            .to_string();
+        let snapshot_path = schemas.join("slides_v1_conformance.txt");
+        if std::env::var_os("UPDATE_CONFORMANCE_SNAPSHOT").is_some() {
+            std::fs::write(&snapshot_path, &report).unwrap();
+            return;
+        }
+        let snapshot = std::fs::read_to_string(&snapshot_path).expect(
+            "schemas/slides_v1_conformance.txt is missing; create it with UPDATE_CONFORMANCE_SNAPSHOT=1",
+        );
+        assert_eq!(
+            report, snapshot,
+            "model conformance changed; rerun with UPDATE_CONFORMANCE_SNAPSHOT=1 to accept"
+        );
+    }
+}
@@ -1,13 +1,15 @@
 use crate::models::{
     common::AffineTransform,
     elements::{PageElement, PageElementKind},
-    page::Page,
+    page::{Page, PageType},
     presentation::Presentation,
     shape::{Shape, ShapeType},
     table::Table, // Added for Table support
     text::TextContent,
     text_element::{TextElement as ModelTextElement, TextElementKind as ModelTextElementKind},
 };
+use crate::notes::speaker_notes_text;
+use crate::visit::{walk_page, VisitContext, Visitor};
 use std::cmp::Ordering;
 use std::fmt::Write; // Import Write trait for formatting
 
@@ -196,22 +198,31 @@ pub fn extract_text_from_page_element(element: &PageElement) -> Option<String> {
     }
 }
 
-/// Extracts and concatenates text from all relevant elements on a single slide, sorted vertically.
-pub fn extract_text_from_slide(slide: &Page) -> Option<String> {
-    let mut slide_parts: Vec<String> = Vec::new();
-
-    if let Some(elements) = &slide.page_elements {
-        let mut sorted_elements: Vec<&PageElement> = elements.iter().collect();
-        sorted_elements.sort_by(|a, b| compare_elements_by_y(a, b));
+/// Collects the elements placed directly on a page. Group children are left out, as
+/// their text has never been part of the extracted slide text.
+#[derive(Default)]
+struct TopLevelElements<'a>(Vec<&'a PageElement>);
 
-        for element in sorted_elements {
-            if let Some(text) = extract_text_from_page_element(element) {
-                // The text from extractors (shape or table_to_markdown) should be formatted.
-                // Add the non-empty text block directly.
-                slide_parts.push(text);
-            }
+impl<'a> Visitor<'a> for TopLevelElements<'a> {
+    fn visit_element(&mut self, element: &'a PageElement, context: &VisitContext<'_>) {
+        if context.groups.is_empty() {
+            self.0.push(element);
         }
     }
+}
+
+/// Extracts and concatenates text from all relevant elements on a single slide, sorted vertically.
+pub fn extract_text_from_slide(slide: &Page) -> Option<String> {
+    let mut elements = TopLevelElements::default();
+    walk_page(slide, &PageType::Slide, &mut elements);
+    let mut sorted_elements = elements.0;
+    sorted_elements.sort_by(|a, b| compare_elements_by_y(a, b));
+
+    // The text from extractors (shape or table_to_markdown) is already formatted.
+    let slide_parts: Vec<String> = sorted_elements
+        .into_iter()
+        .filter_map(extract_text_from_page_element)
+        .collect();
 
     if !slide_parts.is_empty() {
         // Join the parts (shapes, formatted tables) with a single newline
@@ -223,6 +234,13 @@ pub fn extract_text_from_slide(slide: &Page) -> Option<String> {
 
 // --- Public API Function ---
 
+/// Options for `extract_text_from_presentation_with_options`.
+#[derive(Debug, Clone, Default)]
+pub struct TextExtractionOptions {
+    /// Adds each slide's speaker notes as a `### Notes` section after the slide's text.
+    pub include_speaker_notes: bool,
+}
+
 /// Extracts text from all slides in a presentation, formats it as Markdown.
 /// Includes presentation title and slide headers, sorted vertically within slides.
 /// Tables are formatted using Markdown table syntax.
@@ -235,6 +253,24 @@ pub fn extract_text_from_slide(slide: &Page) -> Option<String> {
 ///
 /// A `String` containing the extracted text formatted in a Markdown structure.
 pub fn extract_text_from_presentation(presentation: &Presentation) -> String {
+    extract_text_from_presentation_with_options(presentation, &TextExtractionOptions::default())
+}
+
+/// Extracts text from all slides in a presentation as Markdown, like
+/// `extract_text_from_presentation`, with optional extra sections.
+///
+/// # Arguments
+///
+/// * `presentation` - A reference to the `Presentation` object.
+/// * `options` - What to include besides the slide text.
+///
+/// # Returns
+///
+/// A `String` containing the extracted text formatted in a Markdown structure.
+pub fn extract_text_from_presentation_with_options(
+    presentation: &Presentation,
+    options: &TextExtractionOptions,
+) -> String {
     let mut full_text = String::new();
 
     // Add Presentation Header
@@ -248,7 +284,12 @@ pub fn extract_text_from_presentation(presentation: &Presentation) -> String {
     let mut first_slide = true;
     if let Some(slides) = &presentation.slides {
         for (index, slide) in slides.iter().enumerate() {
-            if let Some(slide_content) = extract_text_from_slide(slide) {
+            let slide_content = extract_text_from_slide(slide);
+            let notes = options
+                .include_speaker_notes
+                .then(|| speaker_notes_text(slide))
+                .flatten();
+            if slide_content.is_some() || notes.is_some() {
                 // Add separator before the second slide onwards
                 if !first_slide {
                     // Use double newline before separator for better spacing after potentially long tables
@@ -261,9 +302,15 @@ pub fn extract_text_from_presentation(presentation: &Presentation) -> String {
                 // Add extra newline after header for spacing before content (like tables)
                 writeln!(full_text, "## Slide {}\n", index + 1).expect("Writing to String failed");
                 // Add Slide Content (which might be multi-line Markdown table)
-                writeln!(full_text, "{}", slide_content).expect("Writing to String failed");
+                if let Some(slide_content) = slide_content {
+                    writeln!(full_text, "{}", slide_content).expect("Writing to String failed");
+                }
+                if let Some(notes) = notes {
+                    writeln!(full_text, "\n### Notes\n\n{}", notes)
+                        .expect("Writing to String failed");
+                }
             }
-            // If extract_text_from_slide returns None, we simply skip adding that slide's section
+            // Slides without text (or notes) get no section
         }
     }
 
@@ -6,11 +6,8 @@ use log::{debug, warn};
 use super::{
     constants::*,
     error::Result, // Keep SvgConversionError if needed for specific errors here
-    structure::{
-        find_placeholder_element, get_placeholder_default_text_style, ElementsMap, LayoutsMap,
-        MastersMap,
-    },
-    text::{convert_text_content_to_html, merge_paragraph_styles}, // Keep this import
+    structure::{find_placeholder_element, ElementsMap, LayoutsMap, MastersMap},
+    text::convert_text_content_to_html,
     utils::{
         apply_transform, dimension_to_pt, dimension_to_svg_units, escape_svg_text, format_color,
         AsShape,
@@ -27,7 +24,9 @@ use crate::models::{
     shape_properties::*,
     table::Table,
     table_properties::{TableBorderFillContent, TableBorderProperties}, // Added for table borders
-    text_element::TextElementKind, // Required for checking ParagraphMarker in shape style override
+};
+use crate::style::{
+    first_paragraph_style, get_placeholder_default_text_style, merge_paragraph_styles,
 };
 use std::fmt::Write;
 
@@ -44,7 +43,11 @@ fn build_individual_border_style(
             return "none".to_string();
         }
 
-        let (base_color_str, alpha) = match &props.table_border_fill {
+        let (base_color_str, alpha) = match props
+            .table_border_fill
+            .as_ref()
+            .and_then(|fill| fill.fill_kind.as_ref())
+        {
             Some(TableBorderFillContent::SolidFill(solid_fill)) => (
                 format_color(solid_fill.color.as_ref(), color_scheme),
                 solid_fill.alpha.unwrap_or(1.0),
@@ -110,12 +113,19 @@ fn build_shape_style(
 
     // --- Background Fill ---
     // Check property state first. If not rendered, treat as transparent.
-    let render_fill =
-        props.shape_background_fill.property_state.as_ref() != Some(&PropertyState::NotRendered);
+    let render_fill = props
+        .shape_background_fill
+        .as_ref()
+        .and_then(|fill| fill.property_state.as_ref())
+        != Some(&PropertyState::NotRendered);
 
     let (fill_color, fill_opacity_str) = if render_fill {
         // Access the fill_kind Option within shape_background_fill
-        match props.shape_background_fill.fill_kind.as_ref() {
+        match props
+            .shape_background_fill
+            .as_ref()
+            .and_then(|fill| fill.fill_kind.as_ref())
+        {
             Some(fill_content) => {
                 // Match on the enum variant inside fill_kind
                 match fill_content {
@@ -150,8 +160,8 @@ fn build_shape_style(
     }
 
     // --- Outline ---
-    // Access outline directly since it's not Option in ShapeProperties
-    let outline = &props.outline;
+    // A missing outline behaves like an outline without any properties set
+    let outline = props.outline.clone().unwrap_or_default();
 
     // Check if outline should be rendered based on propertyState
     let render_outline = outline.property_state.as_ref() != Some(&PropertyState::NotRendered);
@@ -277,10 +287,12 @@ fn convert_shape_to_svg(
         let tx = dimension_to_svg_units(Some(&Dimension {
             magnitude: Some(tf.translate_x.unwrap_or(0.0)),
             unit: Some(translate_unit.clone()),
+            extra: Default::default(),
         }));
         let ty = dimension_to_svg_units(Some(&Dimension {
             magnitude: Some(tf.translate_y.unwrap_or(0.0)),
             unit: Some(translate_unit),
+            extra: Default::default(),
         }));
         (sx, sy, shx, shy, tx, ty)
     } else {
@@ -406,58 +418,46 @@ fn convert_shape_to_svg(
     let mut inherited_content_alignment = ContentAlignment::ContentAlignmentUnspecified;
 
     if let Some(placeholder) = &shape.placeholder {
-        if let Some(layout_id) = slide_layout_id {
-            if let Some(placeholder_element) = find_placeholder_element(
-                placeholder,
-                layout_id,
-                layouts_map,
-                masters_map,
-                elements_map,
-            ) {
-                if let Some(placeholder_base_style) =
-                    get_placeholder_default_text_style(placeholder_element)
-                {
-                    effective_text_style_base = placeholder_base_style;
-                }
+        // Pages without a layout (notes pages) still inherit through the direct parent lookup.
+        if let Some(placeholder_element) = find_placeholder_element(
+            placeholder,
+            slide_layout_id.unwrap_or_default(),
+            layouts_map,
+            masters_map,
+            elements_map,
+        ) {
+            if let Some(placeholder_base_style) =
+                get_placeholder_default_text_style(placeholder_element)
+            {
+                effective_text_style_base = placeholder_base_style;
+            }
 
-                // Extract paragraph style from the placeholder element
-                if let Some(placeholder_shape) = placeholder_element.element_kind.as_shape() {
-                    if let Some(props) = &placeholder_shape.shape_properties {
-                        inherited_content_alignment = props.content_alignment.clone();
-                    }
-                    if let Some(text) = &placeholder_shape.text {
-                        if let Some(elements) = &text.text_elements {
-                            for text_element in elements {
-                                if let Some(TextElementKind::ParagraphMarker(pm)) =
-                                    &text_element.kind
-                                {
-                                    if let Some(style) = &pm.style {
-                                        placeholder_paragraph_style = Some(style.clone());
-                                        // Found the first paragraph style in placeholder
-                                        break;
-                                    }
-                                }
-                            }
-                        }
-                    }
+            // Extract paragraph style from the placeholder element
+            if let Some(placeholder_shape) = placeholder_element.element_kind.as_shape() {
+                if let Some(props) = &placeholder_shape.shape_properties {
+                    inherited_content_alignment =
+                        props.content_alignment.clone().unwrap_or_default();
                 }
-            } else {
-                warn!(
-                    "Placeholder parent ID '{}' not found for shape ID: {}",
-                    placeholder.parent_object_id.as_deref().unwrap_or("N/A"),
-                    element_id
-                );
+                placeholder_paragraph_style = placeholder_shape
+                    .text
+                    .as_ref()
+                    .and_then(first_paragraph_style)
+                    .cloned();
             }
         } else {
             warn!(
-                "Shape ID '{}' has placeholder but slide_layout_id is missing for style lookup.",
+                "Placeholder parent ID '{}' not found for shape ID: {}",
+                placeholder.parent_object_id.as_deref().unwrap_or("N/A"),
                 element_id
             );
         }
     }
 
     // Resolve final content alignment: shape's specific setting overrides inherited.
-    let shape_specific_alignment = shape_props_ref.content_alignment.clone();
+    let shape_specific_alignment = shape_props_ref
+        .content_alignment
+        .clone()
+        .unwrap_or_default();
     let final_content_alignment =
         if shape_specific_alignment != ContentAlignment::ContentAlignmentUnspecified {
             shape_specific_alignment
@@ -475,29 +475,18 @@ fn convert_shape_to_svg(
         let text_padding_left = 3.0;
 
         if width_units > 0.0 && height_units > 0.0 {
-            // Find the shape's own primary paragraph style (if any)
-            let mut shape_paragraph_style: Option<ParagraphStyle> = None;
-            if let Some(elements) = &text.text_elements {
-                for element in elements {
-                    if let Some(TextElementKind::ParagraphMarker(pm)) = &element.kind {
-                        if let Some(style) = &pm.style {
-                            shape_paragraph_style = Some(style.clone());
-                            // Use the first paragraph style found in the shape itself
-                            break;
-                        }
-                    }
-                }
-            }
-
-            // Merge the shape's style onto the placeholder's style
+            // Merge the shape's own primary paragraph style onto the placeholder's style
             // This merged style becomes the initial style for the text content rendering
             let final_initial_para_style = merge_paragraph_styles(
-                shape_paragraph_style.as_ref(),
+                first_paragraph_style(text),
                 placeholder_paragraph_style.as_ref(), // Pass Option<&ParagraphStyle>
             );
 
             // *** Extract font_scale and line_spacing_reduction from shape_properties.autofit ***
-            let autofit_props_opt = shape.shape_properties.as_ref().map(|props| &props.autofit);
+            let autofit_props_opt = shape
+                .shape_properties
+                .as_ref()
+                .and_then(|props| props.autofit.as_ref());
             let font_scale = autofit_props_opt.and_then(|af_props| af_props.font_scale);
             let line_spacing_reduction =
                 autofit_props_opt.and_then(|af_props| af_props.line_spacing_reduction);
@@ -871,20 +860,9 @@ fn convert_table_to_svg(
 
                     if let Some(text) = &cell.text {
                         let cell_text_style_base = TextStyle::default();
-                        let mut cell_para_style: Option<ParagraphStyle> = None;
-                        if let Some(elements) = &text.text_elements {
-                            for element in elements {
-                                if let Some(TextElementKind::ParagraphMarker(pm)) = &element.kind {
-                                    if let Some(style) = &pm.style {
-                                        cell_para_style = Some(style.clone());
-                                        break;
-                                    }
-                                }
-                            }
-                        }
                         convert_text_content_to_html(
                             text,
-                            cell_para_style.as_ref(),
+                            first_paragraph_style(text),
                             &cell_text_style_base,
                             color_scheme,
                             None,
@@ -1042,10 +1020,12 @@ fn convert_line_to_svg(
         let e = dimension_to_svg_units(Some(&Dimension {
             magnitude: Some(tf.translate_x.unwrap_or(0.0)),
             unit: Some(translate_unit.clone()),
+            extra: Default::default(),
         }));
         let f = dimension_to_svg_units(Some(&Dimension {
             magnitude: Some(tf.translate_y.unwrap_or(0.0)),
             unit: Some(translate_unit),
+            extra: Default::default(),
         }));
 
         // Transformed start point (local 0, 0) -> (e, f)
@@ -1349,6 +1329,19 @@ pub(crate) fn convert_page_element_to_svg(
             element.size.as_ref(),
             svg_output,
         )?,
+        PageElementKind::Unknown { kind, .. } => {
+            warn!(
+                "Unknown page element kind '{}' for element ID: {}. Rendering placeholder.",
+                kind, element.object_id
+            );
+            render_placeholder(
+                &format!("Unknown ({})", kind),
+                &element.object_id,
+                element.transform.as_ref(),
+                element.size.as_ref(),
+                svg_output,
+            )?
+        }
     }
 
     Ok(())
@@ -4,7 +4,7 @@
 //! the conversion logic into submodules for clarity and maintainability.
 //!
 //! # Current Features & Limitations:
-//! *   Converts slides to individual SVG files.
+//! *   Converts slides to individual SVG files, or the slides' notes pages (`SvgRenderMode::NotesPages`).
 //! *   Handles basic shapes (text content only, no geometry yet), tables (via HTML `foreignObject`), lines, and images.
 //! *   Supports text styling (font, size, color, bold, italic, underline, etc.).
 //! *   Handles text alignment (start, center, end).
@@ -25,9 +25,25 @@ mod utils;
 pub use error::{Result, SvgConversionError};
 
 // Import necessary items from submodules and models
+use crate::models::common::{Dimension, Size, Unit};
+use crate::models::page::Page;
 use crate::models::presentation::Presentation;
+use crate::notes::notes_page;
+use constants::{NOTES_PAGE_HEIGHT_EMU, NOTES_PAGE_WIDTH_EMU};
 use structure::{build_lookup_maps, convert_slide_to_svg}; // Import internal functions
 
+/// The pages `convert_presentation_to_svg_with_mode` renders.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SvgRenderMode {
+    /// One SVG per slide.
+    #[default]
+    Slides,
+    /// One SVG per slide notes page (the slide image placeholder and the speaker notes),
+    /// for the slides that have one. The API does not report the notes page size, so
+    /// pages are drawn at the default portrait size of 7.5 x 10 inches.
+    NotesPages,
+}
+
 /// Converts a Google Slides `Presentation` object into a vector of SVG strings,
 /// with each string representing one slide.
 ///
@@ -42,20 +58,59 @@ use structure::{build_lookup_maps, convert_slide_to_svg}; // Import internal fun
 /// or an `SvgConversionError` if a critical error occurs during conversion. Errors
 /// during individual slide conversion will halt the process and return the error.
 pub fn convert_presentation_to_svg(presentation: &Presentation) -> Result<Vec<String>> {
+    convert_presentation_to_svg_with_mode(presentation, SvgRenderMode::Slides)
+}
+
+/// Converts the slides or the notes pages of a `Presentation` into a vector of SVG strings,
+/// one per page, in slide order.
+///
+/// # Arguments
+/// * `presentation` - A reference to the `Presentation` object fetched from the Google Slides API.
+/// * `mode` - Which pages to render.
+///
+/// # Returns
+/// A `Result<Vec<String>>` containing the SVG content for each page upon success,
+/// or an `SvgConversionError` if a page cannot be converted.
+pub fn convert_presentation_to_svg_with_mode(
+    presentation: &Presentation,
+    mode: SvgRenderMode,
+) -> Result<Vec<String>> {
     let mut svg_slides = Vec::new();
 
     // 1. Build lookup maps for efficient access to layouts, masters, and elements.
     let (layouts_map, masters_map, elements_map) = build_lookup_maps(presentation);
 
-    // 2. Iterate through slides and convert each one.
-    if let Some(slides) = &presentation.slides {
-        svg_slides.reserve(slides.len()); // Pre-allocate vector capacity
+    // 2. Pick the pages to render and their canvas size.
+    let notes_page_size = Size {
+        width: Some(emu(NOTES_PAGE_WIDTH_EMU)),
+        height: Some(emu(NOTES_PAGE_HEIGHT_EMU)),
+        extra: Default::default(),
+    };
+    let (pages, page_size): (Vec<&Page>, _) = match mode {
+        SvgRenderMode::Slides => (
+            presentation.slides.iter().flatten().collect(),
+            presentation.page_size.as_ref(),
+        ),
+        SvgRenderMode::NotesPages => (
+            presentation
+                .slides
+                .iter()
+                .flatten()
+                .filter_map(notes_page)
+                .collect(),
+            Some(&notes_page_size),
+        ),
+    };
+
+    // 3. Iterate through the pages and convert each one.
+    if !pages.is_empty() {
+        svg_slides.reserve(pages.len()); // Pre-allocate vector capacity
 
-        for (index, slide) in slides.iter().enumerate() {
-            // Convert a single slide using the pre-built context.
+        for (index, slide) in pages.into_iter().enumerate() {
+            // Convert a single page using the pre-built context.
             match convert_slide_to_svg(
                 slide,
-                presentation.page_size.as_ref(), // Pass presentation size context
+                page_size, // Pass page size context
                 &layouts_map,
                 &masters_map,
                 &elements_map,
@@ -65,13 +120,13 @@ pub fn convert_presentation_to_svg(presentation: &Presentation) -> Result<Vec<St
                     // Log the error and return immediately, halting the conversion.
                     // Consider alternative strategies like collecting errors or skipping problematic slides.
                     eprintln!(
-                        "Error converting slide {} (ID: {}): {}",
+                        "Error converting page {} (ID: {}): {}",
                         index + 1,
                         slide.object_id,
                         e
                     );
                     return Err(SvgConversionError::Internal(format!(
-                        "Failed to convert slide {} (ID: {}): {}",
+                        "Failed to convert page {} (ID: {}): {}",
                         index + 1,
                         slide.object_id,
                         e
@@ -80,11 +135,19 @@ pub fn convert_presentation_to_svg(presentation: &Presentation) -> Result<Vec<St
             }
         }
     } else {
-        // Presentation has no slides, return an empty vector.
+        // Presentation has no pages to render, return an empty vector.
         // Optionally, could return an error or warning if this is unexpected.
-        eprintln!("Warning: Presentation has no slides to convert.");
+        eprintln!("Warning: Presentation has no pages to convert.");
     }
 
-    // 3. Return the collected SVG strings.
+    // 4. Return the collected SVG strings.
     Ok(svg_slides)
 }
+
+fn emu(magnitude: f64) -> Dimension {
+    Dimension {
+        magnitude: Some(magnitude),
+        unit: Some(Unit::Emu),
+        extra: Default::default(),
+    }
+}
@@ -2,24 +2,22 @@
 //! including building lookup maps, resolving inheritance (placeholders),
 //! and converting slides.
 
-use log::{debug, warn};
-
 use super::{
     constants::*,
     elements::convert_page_element_to_svg,
     error::{Result, SvgConversionError},
-    utils::{dimension_to_pt, format_color, AsShape},
+    utils::dimension_to_pt,
 };
 use crate::models::{
-    bullet::Bullet,
-    colors::{ColorScheme, OpaqueColor, OpaqueColorContent, ThemeColorType},
+    colors::{RgbColor, ThemeColorType},
     elements::{PageElement, PageElementKind},
-    page::Page,
+    page::{Page, PageType},
     placeholder::Placeholder,
     presentation::Presentation,
-    properties::TextStyle,
-    text_element::TextElementKind,
 };
+use crate::notes::notes_page;
+use crate::theme::find_color_scheme_page;
+use crate::visit::{walk_page, VisitContext, Visitor};
 use std::{collections::HashMap, fmt::Write};
 
 // Type aliases for lookup maps for clarity
@@ -27,27 +25,27 @@ pub(crate) type LayoutsMap<'a> = HashMap<String, &'a Page>;
 pub(crate) type MastersMap<'a> = HashMap<String, &'a Page>;
 pub(crate) type ElementsMap<'a> = HashMap<String, &'a PageElement>;
 
-/// Recursively collects all page elements (including those inside groups) into a map.
-fn collect_elements_recursive<'a>(elements: &'a [PageElement], map: &mut ElementsMap<'a>) {
-    for element in elements {
-        // Use entry API for potential efficiency and clarity, though clone might be necessary if IDs aren't unique across types?
+/// Collects all page elements (including those inside groups) into a map.
+struct ElementCollector<'a, 'm> {
+    map: &'m mut ElementsMap<'a>,
+}
+
+impl<'a> Visitor<'a> for ElementCollector<'a, '_> {
+    fn visit_element(&mut self, element: &'a PageElement, _context: &VisitContext<'_>) {
         // Assuming object_ids are unique presentation-wide.
-        map.insert(element.object_id.clone(), element);
-        if let PageElementKind::ElementGroup(group) = &element.element_kind {
-            collect_elements_recursive(&group.children, map);
-        }
+        self.map.insert(element.object_id.clone(), element);
     }
 }
 
-/// Collects page elements from a list of pages (slides, layouts, or masters) into the elements map.
-fn collect_page_elements<'a>(pages: Option<&'a Vec<Page>>, elements_map: &mut ElementsMap<'a>) {
-    if let Some(page_list) = pages {
-        for page in page_list {
-            if let Some(elements) = &page.page_elements {
-                // Use the recursive helper to handle nested groups correctly
-                collect_elements_recursive(elements, elements_map);
-            }
-        }
+/// Collects page elements from a list of pages (slides, layouts, masters, or notes pages) into the elements map.
+fn collect_page_elements<'a>(
+    pages: impl IntoIterator<Item = &'a Page>,
+    page_type: PageType,
+    elements_map: &mut ElementsMap<'a>,
+) {
+    let mut collector = ElementCollector { map: elements_map };
+    for page in pages {
+        walk_page(page, &page_type, &mut collector);
     }
 }
 
@@ -76,11 +74,31 @@ pub(crate) fn build_lookup_maps(
         }
     }
 
-    // Build a map of *all* page elements for quick lookup across slides, layouts, and masters.
+    // Build a map of *all* page elements for quick lookup across slides, layouts, masters,
+    // notes pages and the notes master.
     let mut elements_map: ElementsMap = HashMap::new();
-    collect_page_elements(presentation.slides.as_ref(), &mut elements_map);
-    collect_page_elements(presentation.layouts.as_ref(), &mut elements_map);
-    collect_page_elements(presentation.masters.as_ref(), &mut elements_map);
+    let slides = presentation.slides.iter().flatten();
+    collect_page_elements(slides.clone(), PageType::Slide, &mut elements_map);
+    collect_page_elements(
+        presentation.layouts.iter().flatten(),
+        PageType::Layout,
+        &mut elements_map,
+    );
+    collect_page_elements(
+        presentation.masters.iter().flatten(),
+        PageType::Master,
+        &mut elements_map,
+    );
+    collect_page_elements(
+        slides.filter_map(notes_page),
+        PageType::Notes,
+        &mut elements_map,
+    );
+    collect_page_elements(
+        presentation.notes_master.as_ref(),
+        PageType::NotesMaster,
+        &mut elements_map,
+    );
 
     (layouts_map, masters_map, elements_map)
 }
@@ -167,137 +185,6 @@ pub(crate) fn find_placeholder_element<'a>(
     None
 }
 
-/// Extracts the *default* text style from a placeholder element (typically a Shape).
-/// This is used as the base style for text within shapes that inherit from this placeholder.
-/// Prioritizes the style defined in the associated list's nesting level 0 bulletStyle,
-/// as this commonly holds the placeholder's default text attributes (font, size, color).
-/// Falls back to the style of the first `TextRun` if the list style method fails.
-///
-/// # Arguments
-/// * `placeholder_element` - The placeholder `PageElement` (likely a Shape) on the layout/master.
-///
-/// # Returns
-/// An `Option<TextStyle>` containing the cloned default style, or `None` if no style could be found.
-pub(crate) fn get_placeholder_default_text_style(
-    placeholder_element: &PageElement,
-) -> Option<TextStyle> {
-    debug!(
-        "[get_placeholder_default_text_style] Attempting for placeholder ID: {}",
-        placeholder_element.object_id
-    );
-
-    if let Some(shape) = placeholder_element.element_kind.as_shape() {
-        if let Some(text) = &shape.text {
-            // --- Strategy 1 (New Priority): Use the style of the first TextRun ---
-            // This is often the most representative style for the placeholder's base text.
-            debug!(
-                "[get_placeholder_default_text_style] Placeholder '{}': Trying first TextRun style lookup.",
-                 placeholder_element.object_id
-             );
-            if let Some(text_elements) = &text.text_elements {
-                if let Some(first_tr_style) =
-                    text_elements
-                        .iter()
-                        .find_map(|element| match &element.kind {
-                            Some(TextElementKind::TextRun(tr)) => tr.style.as_ref(),
-                            _ => None,
-                        })
-                {
-                    debug!(
-                        "[get_placeholder_default_text_style] Placeholder '{}': SUCCESS using first TextRun style: {:?}",
-                         placeholder_element.object_id, first_tr_style
-                     );
-                    return Some(first_tr_style.clone()); // Return this style
-                } else {
-                    debug!(
-                        "[get_placeholder_default_text_style] Placeholder '{}': No styled TextRun found.",
-                         placeholder_element.object_id
-                     );
-                }
-            } else {
-                debug!(
-                    "[get_placeholder_default_text_style] Placeholder '{}': No text elements found for TextRun lookup.",
-                     placeholder_element.object_id
-                 );
-            }
-
-            // --- Strategy 2 (Fallback): Use List Style for Nesting Level 0 ---
-            // If no TextRun style was found, check if there's list information we can use.
-            debug!(
-                "[get_placeholder_default_text_style] Placeholder '{}': Falling back to List/Bullet style lookup.",
-                 placeholder_element.object_id
-             );
-            let list_info: Option<(&String, i32)> =
-                text.text_elements.as_ref().and_then(|elements| {
-                    elements.iter().find_map(|element| {
-                        if let Some(TextElementKind::ParagraphMarker(pm)) = &element.kind {
-                            pm.bullet.as_ref().and_then(|b: &Bullet| {
-                                b.list_id
-                                    .as_ref()
-                                    .map(|id| (id, b.nesting_level.unwrap_or(0)))
-                            })
-                        } else {
-                            None
-                        }
-                    })
-                });
-
-            if let Some((list_id, _nesting_level)) = list_info {
-                // nesting_level isn't directly used here, we hardcode lookup for 0
-                debug!(
-                    "[get_placeholder_default_text_style] Placeholder '{}': Found list_id '{}' from ParagraphMarker.",
-                     placeholder_element.object_id, list_id
-                 );
-                if let Some(lists) = &text.lists {
-                    if let Some(level_0_style) = lists
-                        .get(list_id)
-                        .and_then(|list_props| list_props.nesting_level.as_ref())
-                        .and_then(|nesting_map| nesting_map.get(&0)) // Specifically level 0
-                        .and_then(|level_0_props| level_0_props.bullet_style.as_ref())
-                    {
-                        debug!(
-                            "[get_placeholder_default_text_style] Placeholder '{}': SUCCESS using fallback list '{}', level 0: {:?}",
-                             placeholder_element.object_id, list_id, level_0_style
-                         );
-                        return Some(level_0_style.clone()); // Return this style
-                    } else {
-                        debug!(
-                            "[get_placeholder_default_text_style] Placeholder '{}': List '{}' found, but no style defined for level 0.",
-                              placeholder_element.object_id, list_id
-                         );
-                    }
-                } else {
-                    debug!(
-                        "[get_placeholder_default_text_style] Placeholder '{}': Found list_id '{}', but text.lists is None.",
-                          placeholder_element.object_id, list_id
-                     );
-                }
-            } else {
-                debug!(
-                    "[get_placeholder_default_text_style] Placeholder '{}': No ParagraphMarker with list info found for fallback.",
-                      placeholder_element.object_id
-                 );
-            }
-        } else {
-            debug!(
-                "[get_placeholder_default_text_style] Placeholder '{}': Shape has no text content.",
-                placeholder_element.object_id
-            );
-        }
-    } else {
-        debug!(
-            "[get_placeholder_default_text_style] Placeholder '{}': Element is not a Shape.",
-            placeholder_element.object_id
-        );
-    }
-
-    warn!( // Keep as warn if no style is found at all after trying both strategies
-        "[get_placeholder_default_text_style] No default text style could be determined for placeholder '{}'.",
-         placeholder_element.object_id
-     );
-    None
-}
-
 /// Converts a single slide (`Page`) object into an SVG string representation.
 /// Sets up the SVG canvas, background, and iterates through page elements for conversion.
 /// Resolves the active `ColorScheme` based on slide/layout/master hierarchy.
@@ -359,67 +246,23 @@ pub(crate) fn convert_slide_to_svg(
 
     // --- Determine the Active Color Scheme ---
     // Hierarchy: Slide -> Layout -> Master -> Default (if none found)
-    let mut active_color_scheme: Option<&ColorScheme> = None;
-
-    // 1. Check Slide properties (most specific)
-    if let Some(props) = &slide.page_properties {
-        if props.color_scheme.is_some() {
-            active_color_scheme = props.color_scheme.as_ref();
-        }
-    }
-
-    // 2. If not on slide, check Layout properties
     let slide_layout_id = slide
         .slide_properties
         .as_ref()
         .and_then(|p| p.layout_object_id.as_ref());
-    let mut master_id_from_layout: Option<&String> = None; // To store master ID if found via layout
-
-    if active_color_scheme.is_none() {
-        if let Some(layout_id) = slide_layout_id {
-            if let Some(layout) = layouts_map.get(layout_id) {
-                if let Some(props) = &layout.page_properties {
-                    if props.color_scheme.is_some() {
-                        active_color_scheme = props.color_scheme.as_ref();
-                    }
-                }
-                // Store the master ID referenced by this layout for step 3
-                master_id_from_layout = layout
-                    .layout_properties
-                    .as_ref()
-                    .and_then(|lp| lp.master_object_id.as_ref());
-            }
-        }
-    }
-
-    // 3. If not on slide or layout, check Master properties
-    if active_color_scheme.is_none() {
-        // Use master linked directly from slide OR master linked via layout
-        let master_id = slide
-            .slide_properties
-            .as_ref()
-            .and_then(|p| p.master_object_id.as_ref())
-            .or(master_id_from_layout); // Fallback to master from layout
-
-        if let Some(id) = master_id {
-            if let Some(master) = masters_map.get(id) {
-                if let Some(props) = &master.page_properties {
-                    if props.color_scheme.is_some() {
-                        active_color_scheme = props.color_scheme.as_ref();
-                    }
-                }
-            }
-        }
-    }
-
-    // If still no scheme found after checking hierarchy, active_color_scheme remains None.
-    // Functions using it should handle this (e.g., by using default colors).
+    let active_color_scheme = find_color_scheme_page(slide, |id| {
+        layouts_map.get(id).or_else(|| masters_map.get(id)).copied()
+    })
+    .and_then(|page| page.page_properties.as_ref())
+    .and_then(|props| props.color_scheme.as_ref());
+    // If no scheme is found, functions using it fall back to default colors.
 
     // --- Render Slide Background ---
     // Use the ColorScheme to find the background color, defaulting to white.
     // TODO: Handle complex backgrounds (gradients, images) defined in PageBackgroundFill.
     let background_fill_color = active_color_scheme
-        .and_then(|cs| cs.resolve_theme_color(ThemeColorType::Background1)) // Use helper
+        .and_then(|cs| cs.rgb(&ThemeColorType::Background1))
+        .map(RgbColor::to_hex)
         .unwrap_or_else(|| DEFAULT_BACKGROUND_COLOR.to_string());
 
     writeln!(
@@ -458,22 +301,3 @@ pub(crate) fn convert_slide_to_svg(
     writeln!(svg_string, "</svg>")?;
     Ok(svg_string)
 }
-
-// Helper function added to ColorScheme (consider moving to models/colors.rs if appropriate)
-impl ColorScheme {
-    /// Resolves a `ThemeColorType` to its corresponding RGB hex color string within this scheme.
-    /// Returns `None` if the color type is not found in the scheme.
-    fn resolve_theme_color(&self, theme_color_type: ThemeColorType) -> Option<String> {
-        self.colors
-            .iter()
-            .find(|pair| pair.theme_color_type == theme_color_type)
-            .map(|found_pair| {
-                // Construct a temporary OpaqueColor to reuse the formatting logic
-                let opaque_color = OpaqueColor {
-                    color_kind: OpaqueColorContent::RgbColor(found_pair.color.clone()),
-                };
-                // Format this resolved color (pass None for scheme to avoid recursion)
-                format_color(Some(&opaque_color), None)
-            })
-    }
-}
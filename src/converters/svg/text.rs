@@ -14,6 +14,7 @@ use crate::models::{
     text::TextContent,
     text_element::TextElementKind,
 };
+use crate::style::{merge_paragraph_styles, merge_text_styles};
 use std::fmt::Write;
 
 /// Applies `TextStyle` properties to an SVG element's `style` attribute string.
@@ -187,185 +188,6 @@ pub(crate) fn apply_paragraph_style(
     Ok(adjusted_x) // Return the x-coordinate that corresponds to the calculated anchor
 }
 
-/// Merges two `TextStyle` instances, where `specific_style` overrides `inherited_style`.
-/// Properties set in `specific_style` take precedence. If a property is `None` in
-/// `specific_style`, the value from `inherited_style` is used.
-///
-/// # Arguments
-/// * `specific_style` - The overriding style (e.g., from a TextRun).
-/// * `inherited_style` - The base style (e.g., from a ParagraphMarker bullet or placeholder).
-///
-/// # Returns
-/// A new `TextStyle` instance representing the merged style.
-pub(crate) fn merge_text_styles(
-    specific_style: Option<&TextStyle>,
-    inherited_style: Option<&TextStyle>,
-) -> TextStyle {
-    debug!(
-        "[merge_text_styles] Merging:\n  Specific: {:?}\n  Inherited: {:?}",
-        specific_style, inherited_style
-    );
-
-    // Start with the inherited style or a default TextStyle if none provided.
-    let mut merged = inherited_style.cloned().unwrap_or_default();
-    // Store the original inherited font size for logging clarity if needed
-    // let original_inherited_font_size = merged.font_size.clone();
-
-    if let Some(specific) = specific_style {
-        // Iterate through specific style properties and override if they are Some
-
-        if specific.background_color.is_some() {
-            debug!(
-                "[merge_text_styles]   Overwriting background_color with Specific: {:?}",
-                specific.background_color
-            );
-            merged.background_color = specific.background_color.clone();
-        }
-        if specific.baseline_offset.is_some() {
-            debug!(
-                "[merge_text_styles]   Overwriting baseline_offset with Specific: {:?}",
-                specific.baseline_offset
-            );
-            merged.baseline_offset = specific.baseline_offset.clone();
-        }
-        if specific.bold.is_some() {
-            debug!(
-                "[merge_text_styles]   Overwriting bold with Specific: {:?}",
-                specific.bold
-            );
-            merged.bold = specific.bold;
-        }
-        if specific.font_family.is_some() {
-            debug!(
-                "[merge_text_styles]   Overwriting font_family with Specific: {:?}",
-                specific.font_family
-            );
-            merged.font_family = specific.font_family.clone();
-        }
-        // --- This is the critical part for font size ---
-        if specific.font_size.is_some() {
-            debug!(
-                "[merge_text_styles]   Overwriting font_size with Specific: {:?}",
-                specific.font_size
-            );
-            merged.font_size = specific.font_size.clone(); // Ensure specific always overwrites if present
-        } else {
-            // Log if we kept the inherited size
-            debug!(
-                "[merge_text_styles]   Keeping font_size from Inherited/PreviousMerge: {:?}",
-                merged.font_size
-            );
-        }
-        if specific.foreground_color.is_some() {
-            debug!(
-                "[merge_text_styles]   Overwriting foreground_color with Specific: {:?}",
-                specific.foreground_color
-            );
-            merged.foreground_color = specific.foreground_color.clone();
-        }
-        if specific.italic.is_some() {
-            debug!(
-                "[merge_text_styles]   Overwriting italic with Specific: {:?}",
-                specific.italic
-            );
-            merged.italic = specific.italic;
-        }
-        if specific.link.is_some() {
-            debug!(
-                "[merge_text_styles]   Overwriting link with Specific: {:?}",
-                specific.link
-            );
-            merged.link = specific.link.clone();
-        }
-        if specific.small_caps.is_some() {
-            debug!(
-                "[merge_text_styles]   Overwriting small_caps with Specific: {:?}",
-                specific.small_caps
-            );
-            merged.small_caps = specific.small_caps;
-        }
-        if specific.strikethrough.is_some() {
-            debug!(
-                "[merge_text_styles]   Overwriting strikethrough with Specific: {:?}",
-                specific.strikethrough
-            );
-            merged.strikethrough = specific.strikethrough;
-        }
-        if specific.underline.is_some() {
-            debug!(
-                "[merge_text_styles]   Overwriting underline with Specific: {:?}",
-                specific.underline
-            );
-            merged.underline = specific.underline;
-        }
-        if specific.weighted_font_family.is_some() {
-            debug!(
-                "[merge_text_styles]   Overwriting weighted_font_family with Specific: {:?}",
-                specific.weighted_font_family
-            );
-            merged.weighted_font_family = specific.weighted_font_family.clone();
-        }
-        // language_code merge logic (if needed)
-    } else {
-        debug!("[merge_text_styles] No specific style provided, using inherited directly.");
-    }
-
-    debug!("[merge_text_styles] Merged result: {:?}", merged);
-    merged
-}
-
-/// Merges two `ParagraphStyle` instances. Properties set in `specific_style` override
-/// those in `inherited_style`. If a property is `None` in `specific_style`,
-/// the value from `inherited_style` is used.
-///
-/// # Arguments
-/// * `specific_style` - The overriding style (e.g., from the shape or a specific paragraph marker).
-/// * `inherited_style` - The base style (e.g., from a placeholder or the initial call).
-///
-/// # Returns
-/// A new `ParagraphStyle` instance representing the merged style. Defaults if both are None.
-pub(crate) fn merge_paragraph_styles(
-    specific_style: Option<&ParagraphStyle>,
-    inherited_style: Option<&ParagraphStyle>,
-) -> ParagraphStyle {
-    // Start with the inherited style or a default ParagraphStyle if none provided.
-    let mut merged = inherited_style.cloned().unwrap_or_default();
-
-    if let Some(specific) = specific_style {
-        // Override properties from specific style if they are Some
-        if specific.alignment.is_some() {
-            merged.alignment = specific.alignment.clone();
-        }
-        if specific.direction.is_some() {
-            merged.direction = specific.direction.clone();
-        }
-        if specific.indent_end.is_some() {
-            merged.indent_end = specific.indent_end.clone();
-        }
-        if specific.indent_first_line.is_some() {
-            merged.indent_first_line = specific.indent_first_line.clone();
-        }
-        if specific.indent_start.is_some() {
-            merged.indent_start = specific.indent_start.clone();
-        }
-        if specific.line_spacing.is_some() {
-            merged.line_spacing = specific.line_spacing;
-        }
-        if specific.space_above.is_some() {
-            merged.space_above = specific.space_above.clone();
-        }
-        if specific.space_below.is_some() {
-            merged.space_below = specific.space_below.clone();
-        }
-        if specific.spacing_mode.is_some() {
-            merged.spacing_mode = specific.spacing_mode.clone();
-        }
-        // Add any other ParagraphStyle fields here if the model expands
-    }
-
-    merged
-}
-
 /// Converts the `TextContent` of a shape or table cell into SVG `<text>` and `<tspan>` elements.
 /// Handles basic paragraph breaks, text runs with styling, and alignment.
 /// Applies inheritance logic for text styles (placeholder -> paragraph -> text run).
@@ -897,11 +719,7 @@ pub(crate) fn convert_text_content_to_html(
                             )?;
                         }
                     }
-                    write!(
-                        temp_html_buffer,
-                        r#"<p style="{}">"#,
-                        p_style.trim_end()
-                    )?;
+                    write!(temp_html_buffer, r#"<p style="{}">"#, p_style.trim_end())?;
                     paragraph_open = true;
                 }
 
@@ -986,11 +804,7 @@ pub(crate) fn convert_text_content_to_html(
                             )?;
                         }
                     }
-                    write!(
-                        temp_html_buffer,
-                        r#"<p style="{}">"#,
-                        p_style.trim_end()
-                    )?;
+                    write!(temp_html_buffer, r#"<p style="{}">"#, p_style.trim_end())?;
                     paragraph_open = true;
                 }
 
@@ -79,29 +79,11 @@ pub fn dimension_to_svg_units(dim: Option<&Dimension>) -> f64 {
 pub fn format_color(color_opt: Option<&OpaqueColor>, color_scheme: Option<&ColorScheme>) -> String {
     match color_opt {
         Some(opaque_color) => match &opaque_color.color_kind {
-            OpaqueColorContent::RgbColor(rgb) => {
-                let r = (rgb.red.unwrap_or(0.0) * 255.0).round() as u8;
-                let g = (rgb.green.unwrap_or(0.0) * 255.0).round() as u8;
-                let b = (rgb.blue.unwrap_or(0.0) * 255.0).round() as u8;
-                format!("#{:02x}{:02x}{:02x}", r, g, b)
-            }
+            OpaqueColorContent::RgbColor(rgb) => rgb.to_hex(),
             OpaqueColorContent::ThemeColor(theme_color_type) => {
                 // Attempt to resolve theme color using the provided scheme
-                if let Some(scheme) = color_scheme {
-                    if let Some(theme_pair) = scheme
-                        .colors
-                        .iter()
-                        .find(|pair| pair.theme_color_type == *theme_color_type)
-                    {
-                        // Found the matching theme color pair. Format its RGB value.
-                        // Construct a temporary OpaqueColor to reuse the RGB formatting logic.
-                        let resolved_opaque_color = OpaqueColor {
-                            color_kind: OpaqueColorContent::RgbColor(theme_pair.color.clone()),
-                        };
-                        // Recursively call format_color, but pass None for scheme to prevent loops
-                        // in case a theme color somehow points back to another theme color (unlikely).
-                        return format_color(Some(&resolved_opaque_color), None);
-                    }
+                if let Some(rgb) = color_scheme.and_then(|scheme| scheme.rgb(theme_color_type)) {
+                    return rgb.to_hex();
                 }
                 // Fallback if scheme is missing or color type not found in the scheme
                 eprintln!("Warning: Theme color {:?} not found in scheme or scheme missing. Falling back to default.", theme_color_type);
@@ -182,10 +164,12 @@ pub fn apply_transform(
         tx_units = dimension_to_svg_units(Some(&Dimension {
             magnitude: Some(tf.translate_x.unwrap_or(0.0)), // Default magnitude to 0 if missing
             unit: Some(translate_unit.clone()),
+            extra: Default::default(),
         }));
         ty_units = dimension_to_svg_units(Some(&Dimension {
             magnitude: Some(tf.translate_y.unwrap_or(0.0)), // Default magnitude to 0 if missing
             unit: Some(translate_unit),
+            extra: Default::default(),
         }));
 
         // Construct the SVG transform matrix: matrix(a, b, c, d, e, f)
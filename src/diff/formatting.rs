@@ -127,7 +127,7 @@ fn parse_slide_path(path: &str) -> Option<(usize, String)> {
 fn map_path_to_friendly_name(path: &str) -> String {
     path.replace("pageElements", "Element")
         .replace("textElements", "TextElement")
-        .replace(".elementKind.shape.text", ".ShapeText")
+        .replace(".shape.text", ".ShapeText")
         .replace(".shape.shapeProperties", ".ShapeProps")
         .replace(".textRun.content", ".Content")
         .replace(".textRun.style", ".Style")
@@ -0,0 +1,460 @@
+//! A small typed client for the Google Drive API calls used around presentations:
+//! copying a template deck, exporting to PDF/PPTX, listing revisions and finding decks
+//! in a folder.
+//!
+//! `DriveClient` is obtained from a `SlidesClient` and shares its HTTP client, token
+//! provider, retry policy and rate limiter.
+
+use crate::client::{encode_path_segment, SlidesClient};
+use crate::errors::{Result, SlidesApiError};
+use crate::models::drive::{
+    CopyFileRequest, DriveFile, ExportFormat, FileList, Revision, RevisionList,
+    PRESENTATION_MIME_TYPE,
+};
+use crate::retry::Idempotency;
+use reqwest::Method;
+
+/// The production Drive API v3 endpoint.
+pub const DEFAULT_DRIVE_BASE_URL: &str = "https://www.googleapis.com/drive/v3";
+
+/// Scopes requested for read-only Drive calls.
+const DRIVE_READ_SCOPES: &[&str] = &["https://www.googleapis.com/auth/drive.readonly"];
+
+/// Scopes requested for Drive calls that create files.
+const DRIVE_WRITE_SCOPES: &[&str] = &["https://www.googleapis.com/auth/drive"];
+
+/// The `DriveFile` fields requested from the API (Drive only returns a few by default).
+const FILE_FIELDS: &str = "id,name,mimeType,parents,createdTime,modifiedTime,webViewLink,trashed";
+
+/// A client for the Drive API that reuses a `SlidesClient`'s connection and credentials.
+#[derive(Clone)]
+pub struct DriveClient {
+    slides_client: SlidesClient,
+    base_url: String,
+}
+
+impl DriveClient {
+    /// Creates a Drive client sharing `slides_client`'s HTTP client and auth.
+    pub fn new(slides_client: SlidesClient) -> Self {
+        Self {
+            slides_client,
+            base_url: DEFAULT_DRIVE_BASE_URL.to_string(),
+        }
+    }
+
+    /// Overrides the Drive API base URL, e.g. to point at a mock server.
+    pub fn set_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// The base URL this client sends requests to.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// The `SlidesClient` whose connection and credentials this client uses.
+    pub fn slides_client(&self) -> &SlidesClient {
+        &self.slides_client
+    }
+
+    /// Copies a file (`files.copy`), e.g. to instantiate a template deck.
+    ///
+    /// The call is never retried: after a 5xx or a timeout the copy may already exist, and
+    /// a retry would leave a duplicate in the user's Drive.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_id` - The ID of the file to copy (for Slides, the presentation ID).
+    /// * `request` - The name, parents and description of the copy.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the metadata of the new file; its `id` is the new presentation ID.
+    pub async fn copy_file(&self, file_id: &str, request: &CopyFileRequest) -> Result<DriveFile> {
+        validate_file_id(file_id)?;
+        let url = self.url(
+            &format!("files/{}/copy", encode_path_segment(file_id)),
+            &[
+                ("fields", FILE_FIELDS.to_string()),
+                ("supportsAllDrives", "true".to_string()),
+            ],
+        )?;
+        let bytes = self
+            .slides_client
+            .execute_with_idempotency(
+                Method::POST,
+                &url,
+                Some(serde_json::to_vec(request)?),
+                DRIVE_WRITE_SCOPES,
+                Idempotency::Never,
+            )
+            .await?;
+        Ok(serde_json::from_slice::<DriveFile>(&bytes)?)
+    }
+
+    /// Fetches the metadata of a file (`files.get`).
+    pub async fn get_file(&self, file_id: &str) -> Result<DriveFile> {
+        validate_file_id(file_id)?;
+        let url = self.url(
+            &format!("files/{}", encode_path_segment(file_id)),
+            &[
+                ("fields", FILE_FIELDS.to_string()),
+                ("supportsAllDrives", "true".to_string()),
+            ],
+        )?;
+        let bytes = self
+            .slides_client
+            .execute(Method::GET, &url, None, DRIVE_READ_SCOPES)
+            .await?;
+        Ok(serde_json::from_slice::<DriveFile>(&bytes)?)
+    }
+
+    /// Exports a Google Workspace file to another format (`files.export`).
+    /// The API limits exported content to 10 MB.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_id` - The ID of the file to export.
+    /// * `format` - The target format, e.g. `ExportFormat::Pdf`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the exported file's bytes.
+    pub async fn export_file(&self, file_id: &str, format: ExportFormat) -> Result<Vec<u8>> {
+        validate_file_id(file_id)?;
+        let url = self.url(
+            &format!("files/{}/export", encode_path_segment(file_id)),
+            &[("mimeType", format.mime_type().to_string())],
+        )?;
+        self.slides_client
+            .execute(Method::GET, &url, None, DRIVE_READ_SCOPES)
+            .await
+    }
+
+    /// Lists all revisions of a file (`revisions.list`), following pagination.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the revisions, oldest first.
+    pub async fn list_revisions(&self, file_id: &str) -> Result<Vec<Revision>> {
+        validate_file_id(file_id)?;
+        let mut revisions = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = vec![(
+                "fields",
+                "nextPageToken,revisions(id,mimeType,modifiedTime,keepForever,published,lastModifyingUser)"
+                    .to_string(),
+            )];
+            if let Some(token) = page_token.take() {
+                params.push(("pageToken", token));
+            }
+            let url = self.url(
+                &format!("files/{}/revisions", encode_path_segment(file_id)),
+                &params,
+            )?;
+            let bytes = self
+                .slides_client
+                .execute(Method::GET, &url, None, DRIVE_READ_SCOPES)
+                .await?;
+            let page = serde_json::from_slice::<RevisionList>(&bytes)?;
+            revisions.extend(page.revisions);
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => return Ok(revisions),
+            }
+        }
+    }
+
+    /// Lists all files matching a Drive search query (`files.list`), following pagination.
+    /// Files in shared drives are included.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A Drive search query, e.g. `name contains 'Report' and trashed = false`.
+    ///   See https://developers.google.com/drive/api/guides/search-files
+    pub async fn list_files(&self, query: &str) -> Result<Vec<DriveFile>> {
+        let mut files = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = vec![
+                ("q", query.to_string()),
+                ("fields", format!("nextPageToken,files({})", FILE_FIELDS)),
+                ("supportsAllDrives", "true".to_string()),
+                ("includeItemsFromAllDrives", "true".to_string()),
+            ];
+            if let Some(token) = page_token.take() {
+                params.push(("pageToken", token));
+            }
+            let url = self.url("files", &params)?;
+            let bytes = self
+                .slides_client
+                .execute(Method::GET, &url, None, DRIVE_READ_SCOPES)
+                .await?;
+            let page = serde_json::from_slice::<FileList>(&bytes)?;
+            files.extend(page.files);
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => return Ok(files),
+            }
+        }
+    }
+
+    /// Lists the (non-trashed) Slides presentations directly inside a folder.
+    ///
+    /// # Arguments
+    ///
+    /// * `folder_id` - The ID of the Drive folder.
+    pub async fn list_presentations_in_folder(&self, folder_id: &str) -> Result<Vec<DriveFile>> {
+        validate_file_id(folder_id)?;
+        let query = format!(
+            "'{}' in parents and mimeType = '{}' and trashed = false",
+            escape_query_value(folder_id),
+            PRESENTATION_MIME_TYPE
+        );
+        self.list_files(&query).await
+    }
+
+    /// Builds the URL of a Drive endpoint with the given query parameters.
+    fn url(&self, path: &str, params: &[(&str, String)]) -> Result<String> {
+        let url = format!("{}/{}", self.base_url, path);
+        reqwest::Url::parse_with_params(&url, params)
+            .map(String::from)
+            .map_err(|e| SlidesApiError::InvalidInput(format!("Invalid URL '{}': {}", url, e)))
+    }
+}
+
+impl SlidesClient {
+    /// Returns a `DriveClient` that shares this client's HTTP client and auth.
+    pub fn drive(&self) -> DriveClient {
+        DriveClient::new(self.clone())
+    }
+}
+
+fn validate_file_id(file_id: &str) -> Result<()> {
+    if file_id.is_empty() {
+        return Err(SlidesApiError::InvalidInput(
+            "File ID cannot be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Escapes a string literal for use inside single quotes in a Drive search query.
+fn escape_query_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+#[cfg(test)]
+#[cfg(feature = "emulator")]
+mod tests {
+    use super::*;
+    use crate::auth::StaticTokenProvider;
+    use crate::emulator::SlidesEmulator;
+    use crate::retry::RetryPolicy;
+    use reqwest::StatusCode;
+    use std::time::Duration;
+
+    async fn start_emulator() -> (SlidesEmulator, DriveClient) {
+        let emulator = SlidesEmulator::builder()
+            .add_presentation_json(r#"{"presentationId": "template", "title": "Template"}"#)
+            .unwrap()
+            .start()
+            .await
+            .unwrap();
+        let drive = SlidesClient::builder()
+            .set_token_provider(StaticTokenProvider::new("test-token"))
+            .set_base_url(emulator.base_url())
+            .set_retry_policy(RetryPolicy {
+                initial_backoff: Duration::from_millis(1),
+                ..Default::default()
+            })
+            .build()
+            .unwrap()
+            .drive()
+            .set_base_url(emulator.drive_base_url());
+        (emulator, drive)
+    }
+
+    #[tokio::test]
+    async fn test_copy_file_creates_presentation() {
+        let (emulator, drive) = start_emulator().await;
+        let request = CopyFileRequest {
+            name: Some("Q3 Report".to_string()),
+            parents: Some(vec!["folder".to_string()]),
+            ..Default::default()
+        };
+        let copy = drive.copy_file("template", &request).await.unwrap();
+        assert_eq!(copy.name.as_deref(), Some("Q3 Report"));
+        assert_eq!(copy.mime_type.as_deref(), Some(PRESENTATION_MIME_TYPE));
+        assert_eq!(copy.parents, Some(vec!["folder".to_string()]));
+        assert_eq!(drive.get_file(&copy.id).await.unwrap(), copy);
+
+        let copied = emulator.presentation_json(&copy.id).unwrap();
+        assert_eq!(copied["title"], "Q3 Report");
+
+        let requests = emulator.requests();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path(), "/drive/v3/files/template/copy");
+        assert_eq!(
+            requests[0].query_param("supportsAllDrives").as_deref(),
+            Some("true")
+        );
+        assert_eq!(
+            requests[0].query_param("fields").as_deref(),
+            Some(FILE_FIELDS)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_copy_file_maps_api_errors() {
+        let (_emulator, drive) = start_emulator().await;
+        let error = drive
+            .copy_file("missing", &CopyFileRequest::default())
+            .await
+            .unwrap_err();
+        let SlidesApiError::ApiError { status, message } = error else {
+            panic!("expected an API error");
+        };
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(message, "File not found: missing.");
+
+        assert!(matches!(
+            drive.copy_file("", &CopyFileRequest::default()).await,
+            Err(SlidesApiError::InvalidInput(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_copy_file_is_not_retried() {
+        let (emulator, drive) = start_emulator().await;
+        for status in [
+            StatusCode::SERVICE_UNAVAILABLE,
+            StatusCode::TOO_MANY_REQUESTS,
+        ] {
+            let before = emulator.request_count();
+            emulator.fail_next(1, status);
+            let error = drive
+                .copy_file("template", &CopyFileRequest::default())
+                .await
+                .unwrap_err();
+            assert!(matches!(error, SlidesApiError::ApiError { .. }));
+            assert_eq!(error.status(), Some(status));
+            assert_eq!(emulator.request_count(), before + 1);
+        }
+
+        // Reads through the same client are still retried.
+        emulator.fail_next(1, StatusCode::SERVICE_UNAVAILABLE);
+        drive.get_file("template").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_export_file_requests_mime_type() {
+        let (emulator, drive) = start_emulator().await;
+        let pdf = drive
+            .export_file("template", ExportFormat::Pdf)
+            .await
+            .unwrap();
+        assert_eq!(pdf, b"Export of template as application/pdf\n");
+        let pptx = drive
+            .export_file("template", ExportFormat::Pptx)
+            .await
+            .unwrap();
+        assert!(pptx.ends_with(b"presentationml.presentation\n"));
+
+        let requests = emulator.requests();
+        assert_eq!(requests[0].path(), "/drive/v3/files/template/export");
+        assert_eq!(
+            requests[0].query_param("mimeType").as_deref(),
+            Some("application/pdf")
+        );
+        assert_eq!(
+            requests[1].query_param("mimeType").as_deref(),
+            Some(ExportFormat::Pptx.mime_type())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_calls_follow_page_tokens() {
+        let mut builder = SlidesEmulator::builder().set_drive_page_size(2);
+        for id in ["deck_a", "deck_b", "deck_c"] {
+            builder = builder
+                .add_presentation_json(&format!(r#"{{"presentationId": "{}"}}"#, id))
+                .unwrap();
+        }
+        let emulator = builder.start().await.unwrap();
+        let drive = SlidesClient::builder()
+            .set_token_provider(StaticTokenProvider::new("test-token"))
+            .set_base_url(emulator.base_url())
+            .build()
+            .unwrap()
+            .drive()
+            .set_base_url(emulator.drive_base_url());
+
+        let files = drive.list_files("trashed = false").await.unwrap();
+        let ids: Vec<_> = files.iter().map(|file| file.id.as_str()).collect();
+        assert_eq!(ids, ["deck_a", "deck_b", "deck_c"]);
+        let page_tokens: Vec<_> = emulator
+            .requests()
+            .iter()
+            .map(|request| request.query_param("pageToken"))
+            .collect();
+        assert_eq!(page_tokens, [None, Some("2".to_string())]);
+
+        for revision in ["r2", "r3"] {
+            emulator
+                .insert_presentation_json(&format!(
+                    r#"{{"presentationId": "deck_a", "revisionId": "{}"}}"#,
+                    revision
+                ))
+                .unwrap();
+        }
+        let revisions = drive.list_revisions("deck_a").await.unwrap();
+        let ids: Vec<_> = revisions.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, ["emulator_rev_1", "r2", "r3"]);
+        assert_eq!(emulator.request_count(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_folder_query_escapes_quotes_and_backslashes() {
+        assert_eq!(escape_query_value(r"team's \ deck"), r"team\'s \\ deck");
+
+        let (emulator, drive) = start_emulator().await;
+        let folder = r"Bob's \ folder";
+        let request = CopyFileRequest {
+            parents: Some(vec![folder.to_string()]),
+            ..Default::default()
+        };
+        let copy = drive.copy_file("template", &request).await.unwrap();
+        drive
+            .copy_file("template", &CopyFileRequest::default())
+            .await
+            .unwrap();
+
+        let found = drive.list_presentations_in_folder(folder).await.unwrap();
+        assert_eq!(found, [copy]);
+        let query = emulator.requests().last().unwrap().query_param("q");
+        assert_eq!(
+            query.as_deref(),
+            Some(concat!(
+                r"'Bob\'s \\ folder' in parents and ",
+                "mimeType = 'application/vnd.google-apps.presentation' and trashed = false"
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_file_ids_are_percent_encoded_in_paths() {
+        let (emulator, drive) = start_emulator().await;
+        let error = drive.get_file("a b/c?d").await.unwrap_err();
+        let SlidesApiError::ApiError { message, .. } = error else {
+            panic!("expected an API error");
+        };
+        assert_eq!(message, "File not found: a b/c?d.");
+        assert_eq!(
+            emulator.requests()[0].path(),
+            "/drive/v3/files/a%20b%2Fc%3Fd"
+        );
+    }
+}
@@ -0,0 +1,1383 @@
+//! A local emulator of the Slides v1 REST API, for testing client code without network access.
+//!
+//! The emulator serves `presentations.get`, `presentations.pages.get` and
+//! `presentations.pages.getThumbnail` from JSON fixtures (kept as raw JSON, so fields the
+//! models don't know survive), accepts
+//! `presentations.batchUpdate`, and answers failures with Google-style error bodies
+//! (`{"error": {"code", "message", "status"}}`).
+//!
+//! For Drive, `files.copy`, `files.get`, `files.export`, `files.list` and `revisions.list`
+//! are served for the stored presentations under `drive_base_url`, so template decks can be
+//! copied and folders searched. `files.list` understands `'<id>' in parents`, `name`,
+//! `mimeType` and `trashed` clauses joined by `and`; list responses are split into pages of
+//! `set_drive_page_size` items.
+//!
+//! It also issues access tokens, so token providers can be pointed at it: an OAuth 2.0 token
+//! endpoint (`token_uri`, for service-account and refresh-token grants) and a GCE metadata
+//! server token endpoint (`metadata_token_url`).
+//!
+//! ```no_run
+//! use gslides_tools::emulator::SlidesEmulator;
+//!
+//! # async fn run() -> gslides_tools::Result<()> {
+//! let emulator = SlidesEmulator::builder()
+//!     .add_fixture_file("base_presentation.json")?
+//!     .start()
+//!     .await?;
+//! // Point a client at the emulator:
+//! // SlidesClient::builder().set_base_url(emulator.base_url()) ...
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! `batchUpdate` requests are applied with `engine::BatchUpdateEngine`, so the emulator
+//! supports the same requests; others are rejected with `INVALID_ARGUMENT`. `fields` masks
+//! are applied to nested fields too (`slides(objectId)`, `slides/pageElements`), and
+//! malformed masks are rejected.
+
+use crate::engine::BatchUpdateEngine;
+use crate::errors::{Result, SlidesApiError};
+use crate::models::presentation::Presentation;
+use crate::models::requests::{BatchUpdatePresentationRequest, WriteControl};
+use crate::models::responses::BatchUpdatePresentationResponse;
+use percent_encoding::percent_decode_str;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// Upper bound for request heads and bodies accepted by the emulator.
+const MAX_REQUEST_BYTES: usize = 64 * 1024 * 1024;
+
+/// Path of the emulated OAuth 2.0 token endpoint.
+const TOKEN_PATH: &str = "/token";
+
+/// Path of the emulated GCE metadata server token endpoint.
+const METADATA_TOKEN_PATH: &str = "/computeMetadata/v1/instance/service-accounts/default/token";
+
+/// Lifetime of issued access tokens, in seconds.
+const TOKEN_LIFETIME_SECS: u64 = 3600;
+
+/// The OAuth grant used by service accounts (a signed JWT assertion).
+const JWT_BEARER_GRANT: &str = "urn:ietf:params:oauth:grant-type:jwt-bearer";
+
+/// Path prefix of the emulated Drive v3 API.
+const DRIVE_PATH: &str = "/drive/v3";
+
+/// The MIME type Drive reports for Slides files.
+const PRESENTATION_MIME_TYPE: &str = "application/vnd.google-apps.presentation";
+
+/// The formats `files.export` accepts for Slides files.
+const EXPORT_MIME_TYPES: &[&str] = &[
+    "application/pdf",
+    "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+    "application/vnd.oasis.opendocument.presentation",
+    "text/plain",
+];
+
+/// Default number of items per page of Drive list responses.
+const DEFAULT_DRIVE_PAGE_SIZE: usize = 100;
+
+/// Path prefix of the (unauthenticated) thumbnail content URLs handed out by `getThumbnail`.
+const THUMBNAIL_CONTENT_PATH: &str = "/thumbnails/";
+
+/// The image served for every thumbnail: a 1x1 white PNG.
+const THUMBNAIL_PNG: &[u8] = &[
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1f, 0x15, 0xc4,
+    0x89, 0x00, 0x00, 0x00, 0x0b, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0xf8, 0x0f, 0x04, 0x00,
+    0x09, 0xfb, 0x03, 0xfd, 0xfb, 0x5e, 0x6b, 0x2b, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44,
+    0xae, 0x42, 0x60, 0x82,
+];
+
+/// Builder for creating a `SlidesEmulator`.
+pub struct SlidesEmulatorBuilder {
+    presentations: Vec<Value>,
+    address: SocketAddr,
+    require_auth: bool,
+    drive_page_size: usize,
+}
+
+impl Default for SlidesEmulatorBuilder {
+    fn default() -> Self {
+        Self {
+            presentations: Vec::new(),
+            address: SocketAddr::from(([127, 0, 0, 1], 0)),
+            require_auth: true,
+            drive_page_size: DEFAULT_DRIVE_PAGE_SIZE,
+        }
+    }
+}
+
+impl SlidesEmulatorBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds a presentation from its raw JSON (e.g. the body of a real `presentations.get`).
+    /// The presentation is served under its `presentationId`.
+    pub fn add_presentation_json(mut self, json: &str) -> Result<Self> {
+        let presentation: Value = serde_json::from_str(json)?;
+        presentation_id_of(&presentation)?;
+        self.presentations.push(presentation);
+        Ok(self)
+    }
+
+    /// Adds a presentation from a JSON fixture file such as `base_presentation.json`.
+    pub fn add_fixture_file(self, path: impl AsRef<Path>) -> Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        self.add_presentation_json(&json)
+    }
+
+    /// Sets the address to listen on. Defaults to `127.0.0.1:0` (a free port).
+    pub fn set_address(mut self, address: SocketAddr) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Whether requests without an `Authorization: Bearer` header are rejected with
+    /// `401 UNAUTHENTICATED`. Defaults to `true`.
+    pub fn set_require_auth(mut self, require_auth: bool) -> Self {
+        self.require_auth = require_auth;
+        self
+    }
+
+    /// Sets the maximum number of items per page of Drive `files.list` and `revisions.list`
+    /// responses (a smaller `pageSize` in the request wins). Defaults to 100.
+    pub fn set_drive_page_size(mut self, drive_page_size: usize) -> Self {
+        self.drive_page_size = drive_page_size.max(1);
+        self
+    }
+
+    /// Binds the listener and starts serving in the background.
+    /// The server stops when the returned `SlidesEmulator` is dropped.
+    pub async fn start(self) -> Result<SlidesEmulator> {
+        let listener = TcpListener::bind(self.address).await?;
+        let address = listener.local_addr()?;
+
+        let mut state = EmulatorState {
+            presentations: HashMap::new(),
+            injected_errors: VecDeque::new(),
+            revision_counter: 0,
+            object_counter: 0,
+            token_counter: 0,
+            file_parents: HashMap::new(),
+            revision_history: HashMap::new(),
+            engine: BatchUpdateEngine::new().set_id_prefix("emulator_object_"),
+            requests: Vec::new(),
+            require_auth: self.require_auth,
+            drive_page_size: self.drive_page_size,
+        };
+        for presentation in self.presentations {
+            state.insert(presentation)?;
+        }
+        let state = Arc::new(Mutex::new(state));
+
+        let server_state = Arc::clone(&state);
+        let server = tokio::spawn(async move {
+            loop {
+                let Ok((stream, _)) = listener.accept().await else {
+                    continue;
+                };
+                let state = Arc::clone(&server_state);
+                tokio::spawn(async move {
+                    if let Err(e) = serve_connection(stream, state).await {
+                        log::debug!("Emulator connection failed: {}", e);
+                    }
+                });
+            }
+        });
+
+        log::info!("Slides emulator listening on http://{}", address);
+        Ok(SlidesEmulator {
+            address,
+            state,
+            server,
+        })
+    }
+}
+
+/// A running Slides API emulator. Stops serving when dropped.
+pub struct SlidesEmulator {
+    address: SocketAddr,
+    state: Arc<Mutex<EmulatorState>>,
+    server: JoinHandle<()>,
+}
+
+impl SlidesEmulator {
+    /// Returns a new `SlidesEmulatorBuilder`.
+    pub fn builder() -> SlidesEmulatorBuilder {
+        SlidesEmulatorBuilder::new()
+    }
+
+    /// The address the emulator listens on.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// The base URL to pass to `SlidesClientBuilder::set_base_url`.
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// The base URL to pass to `DriveClient::set_base_url`.
+    pub fn drive_base_url(&self) -> String {
+        format!("{}{}", self.base_url(), DRIVE_PATH)
+    }
+
+    /// The URL of the OAuth 2.0 token endpoint, to use as `token_uri` in service account
+    /// keys and OAuth client secrets.
+    pub fn token_uri(&self) -> String {
+        format!("{}{}", self.base_url(), TOKEN_PATH)
+    }
+
+    /// The URL of the metadata server token endpoint, for
+    /// `MetadataServerTokenProvider::with_endpoint`.
+    pub fn metadata_token_url(&self) -> String {
+        format!("{}{}", self.base_url(), METADATA_TOKEN_PATH)
+    }
+
+    /// Returns the current raw JSON of a stored presentation, including applied updates.
+    pub fn presentation_json(&self, presentation_id: &str) -> Option<Value> {
+        self.state
+            .lock()
+            .unwrap()
+            .presentations
+            .get(presentation_id)
+            .cloned()
+    }
+
+    /// Adds or replaces a presentation while the emulator is running.
+    pub fn insert_presentation_json(&self, json: &str) -> Result<()> {
+        let presentation: Value = serde_json::from_str(json)?;
+        self.state.lock().unwrap().insert(presentation)
+    }
+
+    /// Makes the next `count` requests fail with a Google-style error of the given status,
+    /// e.g. `429` or `503` to exercise retries.
+    pub fn fail_next(&self, count: usize, status: StatusCode) {
+        let mut state = self.state.lock().unwrap();
+        for _ in 0..count {
+            state.injected_errors.push_back(status);
+        }
+    }
+
+    /// The number of requests received so far (including rejected ones).
+    pub fn request_count(&self) -> usize {
+        self.state.lock().unwrap().requests.len()
+    }
+
+    /// The requests received so far (including rejected ones), oldest first.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Drop for SlidesEmulator {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+/// Shared state of a running emulator.
+struct EmulatorState {
+    presentations: HashMap<String, Value>,
+    injected_errors: VecDeque<StatusCode>,
+    revision_counter: u64,
+    object_counter: u64,
+    token_counter: u64,
+    /// Parent folders of presentations created by `files.copy`.
+    file_parents: HashMap<String, Vec<String>>,
+    /// Every revision ID each presentation has had, oldest first.
+    revision_history: HashMap<String, Vec<String>>,
+    /// Applies `batchUpdate` requests; kept so generated object IDs stay unique.
+    engine: BatchUpdateEngine,
+    requests: Vec<RecordedRequest>,
+    require_auth: bool,
+    drive_page_size: usize,
+}
+
+impl EmulatorState {
+    /// Stores a presentation, assigning a revision ID if the fixture has none.
+    fn insert(&mut self, mut presentation: Value) -> Result<()> {
+        let presentation_id = presentation_id_of(&presentation)?;
+        if presentation.get("revisionId").is_none() {
+            let revision_id = self.next_revision_id();
+            presentation["revisionId"] = Value::String(revision_id);
+        }
+        self.store(presentation_id, presentation);
+        Ok(())
+    }
+
+    /// Stores a presentation under its ID and records its revision.
+    fn store(&mut self, presentation_id: String, presentation: Value) {
+        if let Some(revision_id) = presentation["revisionId"].as_str() {
+            let history = self
+                .revision_history
+                .entry(presentation_id.clone())
+                .or_default();
+            if history.last().map(String::as_str) != Some(revision_id) {
+                history.push(revision_id.to_string());
+            }
+        }
+        self.presentations.insert(presentation_id, presentation);
+    }
+
+    fn next_revision_id(&mut self) -> String {
+        self.revision_counter += 1;
+        format!("emulator_rev_{}", self.revision_counter)
+    }
+
+    /// Issues a new access token in the shape of a Google token response.
+    fn issue_token(&mut self) -> Value {
+        self.token_counter += 1;
+        json!({
+            "access_token": format!("emulator_token_{}", self.token_counter),
+            "expires_in": TOKEN_LIFETIME_SECS,
+            "token_type": "Bearer",
+        })
+    }
+}
+
+/// A request received by the emulator, as returned by `SlidesEmulator::requests`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedRequest {
+    /// The HTTP method, e.g. `GET`.
+    pub method: String,
+    /// The request target as sent: the path and the still-encoded query string.
+    pub target: String,
+}
+
+impl RecordedRequest {
+    /// The path of the request, without the query string.
+    pub fn path(&self) -> &str {
+        self.target.split('?').next().unwrap_or_default()
+    }
+
+    /// The decoded value of a query parameter, if present.
+    pub fn query_param(&self, name: &str) -> Option<String> {
+        let url = reqwest::Url::parse(&format!("http://emulator{}", self.target)).ok()?;
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    }
+}
+
+/// An error answered with a Google-style error body.
+#[derive(Debug)]
+struct ApiFailure {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiFailure {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    fn invalid_argument(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, message)
+    }
+
+    fn not_found() -> Self {
+        Self::new(StatusCode::NOT_FOUND, "Requested entity was not found.")
+    }
+
+    /// The body Google APIs send for failed requests.
+    fn to_body(&self) -> Value {
+        json!({
+            "error": {
+                "code": self.status.as_u16(),
+                "message": self.message,
+                "status": google_status(self.status),
+            }
+        })
+    }
+}
+
+/// Maps an HTTP status to the canonical Google RPC status name.
+fn google_status(status: StatusCode) -> &'static str {
+    match status.as_u16() {
+        400 => "INVALID_ARGUMENT",
+        401 => "UNAUTHENTICATED",
+        403 => "PERMISSION_DENIED",
+        404 => "NOT_FOUND",
+        409 => "ABORTED",
+        429 => "RESOURCE_EXHAUSTED",
+        500 => "INTERNAL",
+        501 => "UNIMPLEMENTED",
+        503 => "UNAVAILABLE",
+        504 => "DEADLINE_EXCEEDED",
+        _ => "UNKNOWN",
+    }
+}
+
+/// A parsed HTTP request.
+struct HttpRequest {
+    method: String,
+    target: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl HttpRequest {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Reads one request from the connection, answers it and closes the connection.
+async fn serve_connection(
+    mut stream: TcpStream,
+    state: Arc<Mutex<EmulatorState>>,
+) -> std::io::Result<()> {
+    let Some(request) = read_request(&mut stream).await? else {
+        return Ok(());
+    };
+    let (status, reply) = match handle_request(&request, &state) {
+        Ok(reply) => (StatusCode::OK, reply),
+        Err(failure) => (failure.status, Reply::Json(failure.to_body())),
+    };
+    let (content_type, body) = match reply {
+        Reply::Json(value) => (
+            "application/json; charset=UTF-8",
+            serde_json::to_vec(&value)?,
+        ),
+        Reply::Bytes { content_type, body } => (content_type, body),
+    };
+    let head = format!(
+        "HTTP/1.1 {} {}\r\ncontent-type: {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+        status.as_u16(),
+        status.canonical_reason().unwrap_or(""),
+        content_type,
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(&body).await?;
+    stream.shutdown().await
+}
+
+/// Reads an HTTP/1.1 request head and its `Content-Length` body.
+async fn read_request(stream: &mut TcpStream) -> std::io::Result<Option<HttpRequest>> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 8192];
+    let head_end = loop {
+        if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if buffer.len() > MAX_REQUEST_BYTES {
+            return Err(std::io::Error::other("Request head too large"));
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..head_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let target = request_line.next().unwrap_or_default().to_string();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect();
+
+    let content_length = headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    if content_length > MAX_REQUEST_BYTES {
+        return Err(std::io::Error::other("Request body too large"));
+    }
+    let mut body = buffer[head_end + 4..].to_vec();
+    while body.len() < content_length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(content_length);
+
+    Ok(Some(HttpRequest {
+        method,
+        target,
+        headers,
+        body,
+    }))
+}
+
+/// The body of a successful response.
+enum Reply {
+    Json(Value),
+    Bytes {
+        content_type: &'static str,
+        body: Vec<u8>,
+    },
+}
+
+/// Routes a request to the emulated endpoint.
+fn handle_request(
+    request: &HttpRequest,
+    state: &Mutex<EmulatorState>,
+) -> std::result::Result<Reply, ApiFailure> {
+    let mut state = state.lock().unwrap();
+    state.requests.push(RecordedRequest {
+        method: request.method.clone(),
+        target: request.target.clone(),
+    });
+    if let Some(status) = state.injected_errors.pop_front() {
+        return Err(ApiFailure::new(
+            status,
+            format!("Injected {} error.", google_status(status)),
+        ));
+    }
+    let url = reqwest::Url::parse(&format!("http://emulator{}", request.target))
+        .map_err(|_| ApiFailure::invalid_argument("Malformed request URL."))?;
+
+    // Token endpoints and thumbnail content URLs are called without an access token.
+    match (request.method.as_str(), url.path()) {
+        ("POST", TOKEN_PATH) => return oauth_token(&mut state, request).map(Reply::Json),
+        ("GET", METADATA_TOKEN_PATH) => {
+            return metadata_token(&mut state, request).map(Reply::Json)
+        }
+        ("GET", path) if path.starts_with(THUMBNAIL_CONTENT_PATH) => {
+            return Ok(Reply::Bytes {
+                content_type: "image/png",
+                body: THUMBNAIL_PNG.to_vec(),
+            })
+        }
+        _ => {}
+    }
+    if state.require_auth
+        && !request
+            .header("authorization")
+            .is_some_and(|value| value.starts_with("Bearer "))
+    {
+        return Err(ApiFailure::new(
+            StatusCode::UNAUTHORIZED,
+            "Request is missing required authentication credential.",
+        ));
+    }
+
+    let fields = url
+        .query_pairs()
+        .find(|(key, _)| key == "fields")
+        .map(|(_, value)| value.into_owned());
+    if let Some(rest) = url.path().strip_prefix(DRIVE_PATH) {
+        return drive_request(&mut state, request, &url, rest);
+    }
+    let Some(rest) = url.path().strip_prefix("/v1/presentations/") else {
+        return Err(ApiFailure::not_found());
+    };
+
+    let segments = decode_segments(rest);
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+    let body = match (request.method.as_str(), segments.as_slice()) {
+        ("POST", &[id_and_method]) if id_and_method.ends_with(":batchUpdate") => {
+            let presentation_id = id_and_method.trim_end_matches(":batchUpdate");
+            let body: BatchUpdatePresentationRequest = serde_json::from_slice(&request.body)
+                .map_err(|e| {
+                    ApiFailure::invalid_argument(format!("Invalid JSON payload: {}", e))
+                })?;
+            let response = batch_update(&mut state, presentation_id, body)?;
+            serde_json::to_value(response)
+                .map_err(|e| ApiFailure::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+        }
+        ("GET", &[presentation_id]) => {
+            let presentation = state
+                .presentations
+                .get(presentation_id)
+                .ok_or_else(ApiFailure::not_found)?;
+            select_fields(presentation, fields.as_deref())
+        }
+        ("GET", &[presentation_id, "pages", page_object_id]) => {
+            let presentation = state
+                .presentations
+                .get(presentation_id)
+                .ok_or_else(ApiFailure::not_found)?;
+            let page = pages(presentation)
+                .find(|page| page["objectId"] == page_object_id)
+                .ok_or_else(ApiFailure::not_found)?;
+            select_fields(page, fields.as_deref())
+        }
+        ("GET", &[presentation_id, "pages", page_object_id, "thumbnail"]) => {
+            thumbnail(&state, request, &url, presentation_id, page_object_id)
+        }
+        _ => Err(ApiFailure::not_found()),
+    };
+    body.map(Reply::Json)
+}
+
+/// Splits a URL path into its percent-decoded segments.
+fn decode_segments(path: &str) -> Vec<String> {
+    path.split('/')
+        .map(|segment| percent_decode_str(segment).decode_utf8_lossy().into_owned())
+        .collect()
+}
+
+/// Answers the Drive calls for stored presentations.
+fn drive_request(
+    state: &mut EmulatorState,
+    request: &HttpRequest,
+    url: &reqwest::Url,
+    path: &str,
+) -> std::result::Result<Reply, ApiFailure> {
+    let query = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+    let segments = decode_segments(path.trim_start_matches('/'));
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("POST", &["files", file_id, "copy"]) => {
+            let body: Value = if request.body.is_empty() {
+                json!({})
+            } else {
+                serde_json::from_slice(&request.body).map_err(|e| {
+                    ApiFailure::invalid_argument(format!("Invalid JSON payload: {}", e))
+                })?
+            };
+            let mut copy = state
+                .presentations
+                .get(file_id)
+                .cloned()
+                .ok_or_else(|| file_not_found(file_id))?;
+            state.object_counter += 1;
+            let copy_id = format!("emulator_file_{}", state.object_counter);
+            copy["presentationId"] = Value::String(copy_id.clone());
+            copy["revisionId"] = Value::String(state.next_revision_id());
+            copy["title"] = match body["name"].as_str() {
+                Some(name) => Value::String(name.to_string()),
+                None => Value::String(format!(
+                    "Copy of {}",
+                    copy["title"].as_str().unwrap_or_default()
+                )),
+            };
+            if let Some(parents) = body["parents"].as_array() {
+                let parents = parents
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect();
+                state.file_parents.insert(copy_id.clone(), parents);
+            }
+            state.store(copy_id.clone(), copy);
+            Ok(Reply::Json(drive_file(state, &copy_id)))
+        }
+        ("GET", &["files", file_id]) => {
+            if !state.presentations.contains_key(file_id) {
+                return Err(file_not_found(file_id));
+            }
+            Ok(Reply::Json(drive_file(state, file_id)))
+        }
+        ("GET", &["files", file_id, "export"]) => {
+            if !state.presentations.contains_key(file_id) {
+                return Err(file_not_found(file_id));
+            }
+            let mime_type = query("mimeType")
+                .ok_or_else(|| ApiFailure::invalid_argument("Required parameter: mimeType"))?;
+            let Some(content_type) = EXPORT_MIME_TYPES.iter().find(|m| **m == mime_type) else {
+                return Err(ApiFailure::invalid_argument(
+                    "The requested conversion is not supported.",
+                ));
+            };
+            Ok(Reply::Bytes {
+                content_type,
+                body: format!("Export of {} as {}\n", file_id, mime_type).into_bytes(),
+            })
+        }
+        ("GET", &["files", file_id, "revisions"]) => {
+            let history = state
+                .revision_history
+                .get(file_id)
+                .ok_or_else(|| file_not_found(file_id))?;
+            let revisions: Vec<Value> = history
+                .iter()
+                .map(|id| json!({"id": id, "mimeType": PRESENTATION_MIME_TYPE}))
+                .collect();
+            list_page(state, &query, "revisions", revisions)
+        }
+        ("GET", &["files"]) => {
+            let q = query("q").unwrap_or_default();
+            let clauses = parse_drive_query(&q)?;
+            let mut file_ids: Vec<&String> = state.presentations.keys().collect();
+            file_ids.sort();
+            let files: Vec<Value> = file_ids
+                .into_iter()
+                .map(|file_id| drive_file(state, file_id))
+                .filter(|file| clauses.iter().all(|clause| clause.matches(file)))
+                .collect();
+            list_page(state, &query, "files", files)
+        }
+        _ => Err(ApiFailure::not_found()),
+    }
+}
+
+/// Answers one page of a Drive list call. Page tokens are the offset of the page's first item.
+fn list_page(
+    state: &EmulatorState,
+    query: &dyn Fn(&str) -> Option<String>,
+    key: &str,
+    items: Vec<Value>,
+) -> std::result::Result<Reply, ApiFailure> {
+    let page_size = match query("pageSize") {
+        Some(size) => size
+            .parse::<usize>()
+            .map_err(|_| ApiFailure::invalid_argument(format!("Invalid pageSize: {}", size)))?
+            .clamp(1, state.drive_page_size),
+        None => state.drive_page_size,
+    };
+    let start = match query("pageToken") {
+        Some(token) => token
+            .parse::<usize>()
+            .ok()
+            .filter(|start| *start <= items.len())
+            .ok_or_else(|| ApiFailure::invalid_argument(format!("Invalid pageToken: {}", token)))?,
+        None => 0,
+    };
+    let end = (start + page_size).min(items.len());
+    let mut page = json!({ key: items[start..end] });
+    if end < items.len() {
+        page["nextPageToken"] = Value::String(end.to_string());
+    }
+    Ok(Reply::Json(page))
+}
+
+/// One `and`-joined condition of a Drive search query.
+#[derive(Debug, PartialEq)]
+enum DriveQueryClause {
+    InParents(String),
+    Equals(String, String),
+    NotEquals(String, String),
+    Contains(String, String),
+}
+
+impl DriveQueryClause {
+    fn matches(&self, file: &Value) -> bool {
+        let field = |name: &str| match &file[name] {
+            Value::String(value) => value.clone(),
+            other => other.to_string(),
+        };
+        match self {
+            DriveQueryClause::InParents(parent) => file["parents"]
+                .as_array()
+                .is_some_and(|parents| parents.iter().any(|p| p == parent.as_str())),
+            DriveQueryClause::Equals(name, value) => field(name) == *value,
+            DriveQueryClause::NotEquals(name, value) => field(name) != *value,
+            DriveQueryClause::Contains(name, value) => field(name).contains(value.as_str()),
+        }
+    }
+}
+
+/// Parses the subset of the Drive query language the emulator supports:
+/// `'<id>' in parents`, and `name`, `mimeType` or `trashed` compared with `=`, `!=` or
+/// `contains`, joined by `and`. String literals use `\'` and `\\` escapes.
+fn parse_drive_query(query: &str) -> std::result::Result<Vec<DriveQueryClause>, ApiFailure> {
+    let invalid = || ApiFailure::invalid_argument(format!("Invalid Value: {}", query));
+    let mut clauses = Vec::new();
+    let mut rest = query.trim();
+    while !rest.is_empty() {
+        let clause;
+        if rest.starts_with('\'') {
+            let (parent, after) = take_string_literal(rest).ok_or_else(invalid)?;
+            rest = after
+                .trim_start()
+                .strip_prefix("in parents")
+                .ok_or_else(invalid)?;
+            clause = DriveQueryClause::InParents(parent);
+        } else {
+            let name_end = rest
+                .find(|c: char| !c.is_ascii_alphanumeric())
+                .unwrap_or(rest.len());
+            let (name, after) = rest.split_at(name_end);
+            if !["name", "mimeType", "trashed"].contains(&name) {
+                return Err(invalid());
+            }
+            let after = after.trim_start();
+            let (operator, after) = ["!=", "=", "contains"]
+                .iter()
+                .find_map(|op| {
+                    after
+                        .strip_prefix(op)
+                        .map(|after| (*op, after.trim_start()))
+                })
+                .ok_or_else(invalid)?;
+            let (value, after) = if after.starts_with('\'') {
+                take_string_literal(after).ok_or_else(invalid)?
+            } else {
+                let end = after.find(' ').unwrap_or(after.len());
+                (after[..end].to_string(), &after[end..])
+            };
+            let name = name.to_string();
+            clause = match operator {
+                "=" => DriveQueryClause::Equals(name, value),
+                "!=" => DriveQueryClause::NotEquals(name, value),
+                _ => DriveQueryClause::Contains(name, value),
+            };
+            rest = after;
+        }
+        clauses.push(clause);
+        rest = rest.trim_start();
+        if !rest.is_empty() {
+            rest = rest.strip_prefix("and ").ok_or_else(invalid)?.trim_start();
+        }
+    }
+    Ok(clauses)
+}
+
+/// Reads a single-quoted Drive string literal, returning its value and the rest of the input.
+fn take_string_literal(input: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = input.char_indices().skip(1);
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => value.push(chars.next()?.1),
+            '\'' => return Some((value, &input[index + 1..])),
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+/// The Drive error for a file that does not exist.
+fn file_not_found(file_id: &str) -> ApiFailure {
+    ApiFailure::new(
+        StatusCode::NOT_FOUND,
+        format!("File not found: {}.", file_id),
+    )
+}
+
+/// The Drive file metadata of a stored presentation.
+fn drive_file(state: &EmulatorState, file_id: &str) -> Value {
+    let presentation = &state.presentations[file_id];
+    let mut file = json!({
+        "id": file_id,
+        "name": presentation["title"].as_str().unwrap_or_default(),
+        "mimeType": PRESENTATION_MIME_TYPE,
+        "trashed": false,
+    });
+    if let Some(parents) = state.file_parents.get(file_id) {
+        file["parents"] = json!(parents);
+    }
+    file
+}
+
+/// Answers `presentations.pages.getThumbnail` with a content URL served by the emulator.
+/// The thumbnail is as wide as the requested size and has the aspect ratio of the page.
+fn thumbnail(
+    state: &EmulatorState,
+    request: &HttpRequest,
+    url: &reqwest::Url,
+    presentation_id: &str,
+    page_object_id: &str,
+) -> std::result::Result<Value, ApiFailure> {
+    let presentation = state
+        .presentations
+        .get(presentation_id)
+        .ok_or_else(ApiFailure::not_found)?;
+    if !pages(presentation).any(|page| page["objectId"] == page_object_id) {
+        return Err(ApiFailure::not_found());
+    }
+    let query = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+    let width: u32 = match query("thumbnailProperties.thumbnailSize").as_deref() {
+        None | Some("THUMBNAIL_SIZE_UNSPECIFIED") | Some("LARGE") => 1600,
+        Some("MEDIUM") => 800,
+        Some("SMALL") => 200,
+        Some(other) => {
+            return Err(ApiFailure::invalid_argument(format!(
+                "Invalid value at 'thumbnail_properties.thumbnail_size' ({})",
+                other
+            )))
+        }
+    };
+    match query("thumbnailProperties.mimeType").as_deref() {
+        None | Some("PNG") => {}
+        Some(other) => {
+            return Err(ApiFailure::invalid_argument(format!(
+                "Invalid value at 'thumbnail_properties.mime_type' ({})",
+                other
+            )))
+        }
+    }
+    let magnitude = |dimension: &str| presentation["pageSize"][dimension]["magnitude"].as_f64();
+    let height = match (magnitude("width"), magnitude("height")) {
+        (Some(page_width), Some(page_height)) if page_width > 0.0 => {
+            (f64::from(width) * page_height / page_width).round() as u32
+        }
+        _ => width * 9 / 16,
+    };
+    let host = request.header("host").unwrap_or("localhost");
+    Ok(json!({
+        "width": width,
+        "height": height,
+        "contentUrl": format!(
+            "http://{}{}{}/{}.png",
+            host, THUMBNAIL_CONTENT_PATH, presentation_id, page_object_id
+        ),
+    }))
+}
+
+/// Answers the OAuth 2.0 token endpoint for JWT-bearer (service account) and refresh-token
+/// grants. Assertions and refresh tokens are accepted without verification.
+fn oauth_token(
+    state: &mut EmulatorState,
+    request: &HttpRequest,
+) -> std::result::Result<Value, ApiFailure> {
+    let form: HashMap<String, String> = reqwest::Url::parse(&format!(
+        "http://emulator/?{}",
+        String::from_utf8_lossy(&request.body)
+    ))
+    .map(|url| url.query_pairs().into_owned().collect())
+    .unwrap_or_default();
+    let credential = match form.get("grant_type").map(String::as_str) {
+        Some(JWT_BEARER_GRANT) => "assertion",
+        Some("refresh_token") => "refresh_token",
+        other => {
+            return Err(ApiFailure::invalid_argument(format!(
+                "Unsupported grant type: {}",
+                other.unwrap_or_default()
+            )))
+        }
+    };
+    if form.get(credential).is_none_or(|value| value.is_empty()) {
+        return Err(ApiFailure::invalid_argument(format!(
+            "Missing required parameter: {}",
+            credential
+        )));
+    }
+    Ok(state.issue_token())
+}
+
+/// Answers the metadata server token endpoint, which requires the `Metadata-Flavor` header.
+fn metadata_token(
+    state: &mut EmulatorState,
+    request: &HttpRequest,
+) -> std::result::Result<Value, ApiFailure> {
+    if request.header("metadata-flavor") != Some("Google") {
+        return Err(ApiFailure::new(
+            StatusCode::FORBIDDEN,
+            "Missing Metadata-Flavor:Google header.",
+        ));
+    }
+    Ok(state.issue_token())
+}
+
+/// Applies a batch atomically with `BatchUpdateEngine`: either every request succeeds or
+/// nothing changes.
+fn batch_update(
+    state: &mut EmulatorState,
+    presentation_id: &str,
+    body: BatchUpdatePresentationRequest,
+) -> std::result::Result<BatchUpdatePresentationResponse, ApiFailure> {
+    let current = state
+        .presentations
+        .get(presentation_id)
+        .ok_or_else(ApiFailure::not_found)?;
+    if let Some(WriteControl {
+        required_revision_id: Some(required),
+    }) = &body.write_control
+    {
+        if current["revisionId"].as_str() != Some(required.as_str()) {
+            return Err(ApiFailure::invalid_argument(format!(
+                "The required revision ID '{}' does not match the latest revision.",
+                required
+            )));
+        }
+    }
+
+    let mut presentation: Presentation = serde_json::from_value(current.clone()).map_err(|e| {
+        ApiFailure::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("The stored presentation could not be read: {}", e),
+        )
+    })?;
+    let replies = state
+        .engine
+        .apply(&mut presentation, &body.requests)
+        .map_err(|e| ApiFailure::invalid_argument(e.to_string()))?;
+
+    let revision_id = state.next_revision_id();
+    presentation.revision_id = Some(revision_id.clone());
+    let presentation = serde_json::to_value(&presentation)
+        .map_err(|e| ApiFailure::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    state.store(presentation_id.to_string(), presentation);
+    Ok(BatchUpdatePresentationResponse {
+        presentation_id: Some(presentation_id.to_string()),
+        replies,
+        write_control: Some(WriteControl {
+            required_revision_id: Some(revision_id),
+        }),
+    })
+}
+
+fn presentation_id_of(presentation: &Value) -> Result<String> {
+    presentation["presentationId"]
+        .as_str()
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| SlidesApiError::InvalidInput("Fixture has no presentationId".to_string()))
+}
+/// A parsed `fields` mask such as `title,slides(objectId,pageElements/size)`.
+///
+/// Each selected key maps to its nested selection, or to `None` when the whole value is
+/// selected. `*` selects every key of an object.
+#[derive(Debug, Default)]
+struct FieldSelection {
+    keys: HashMap<String, Option<FieldSelection>>,
+}
+
+impl FieldSelection {
+    /// Parses a mask, rejecting empty names and unbalanced parentheses like the API.
+    fn parse(fields: &str) -> std::result::Result<Self, ApiFailure> {
+        let invalid =
+            || ApiFailure::invalid_argument(format!("Invalid field selection {}", fields));
+        let (selection, rest) = Self::parse_list(fields).ok_or_else(invalid)?;
+        if !rest.trim().is_empty() {
+            return Err(invalid());
+        }
+        Ok(selection)
+    }
+
+    /// Parses comma-separated paths up to an unmatched `)` or the end of `input`.
+    fn parse_list(mut input: &str) -> Option<(Self, &str)> {
+        let mut selection = Self::default();
+        loop {
+            let end = input.find([',', '(', ')']).unwrap_or(input.len());
+            let path: Vec<&str> = input[..end].split('/').map(str::trim).collect();
+            if path.iter().any(|name| name.is_empty()) {
+                return None;
+            }
+            input = input[end..].trim_start();
+            let mut nested = None;
+            if let Some(inner) = input.strip_prefix('(') {
+                let (inner, rest) = Self::parse_list(inner)?;
+                input = rest.strip_prefix(')')?.trim_start();
+                nested = Some(inner);
+            }
+            // `a/b(c)` selects `c` inside `b` inside `a`.
+            let (first, rest) = path.split_first()?;
+            let nested = rest.iter().rev().fold(nested, |nested, name| {
+                let mut selection = Self::default();
+                selection.merge(name.to_string(), nested);
+                Some(selection)
+            });
+            selection.merge(first.to_string(), nested);
+            match input.strip_prefix(',') {
+                Some(rest) => input = rest,
+                None => return Some((selection, input)),
+            }
+        }
+    }
+
+    /// Adds a key to the selection; selecting a whole value wins over nested selections.
+    fn merge(&mut self, key: String, nested: Option<FieldSelection>) {
+        match (self.keys.get_mut(&key), nested) {
+            (Some(None), _) => {}
+            (Some(existing), None) => *existing = None,
+            (Some(Some(existing)), Some(nested)) => {
+                for (key, value) in nested.keys {
+                    existing.merge(key, value);
+                }
+            }
+            (None, nested) => {
+                self.keys.insert(key, nested);
+            }
+        }
+    }
+
+    /// Keeps the selected parts of `value`; arrays apply the selection to each item.
+    fn apply(&self, value: &Value) -> Value {
+        match value {
+            Value::Object(object) => Value::Object(
+                object
+                    .iter()
+                    .filter_map(|(key, child)| {
+                        let nested = self.keys.get(key).or_else(|| self.keys.get("*"))?;
+                        let child = match nested {
+                            Some(nested) => nested.apply(child),
+                            None => child.clone(),
+                        };
+                        Some((key.clone(), child))
+                    })
+                    .collect(),
+            ),
+            Value::Array(items) => {
+                Value::Array(items.iter().map(|item| self.apply(item)).collect())
+            }
+            other => other.clone(),
+        }
+    }
+}
+
+/// Applies a `fields` mask to a resource. No mask (or an empty one) returns everything.
+fn select_fields(resource: &Value, fields: Option<&str>) -> std::result::Result<Value, ApiFailure> {
+    match fields.filter(|f| !f.trim().is_empty()) {
+        Some(fields) => Ok(FieldSelection::parse(fields)?.apply(resource)),
+        None => Ok(resource.clone()),
+    }
+}
+
+/// All pages of a presentation: slides, layouts, masters, the notes master and notes pages.
+fn pages(presentation: &Value) -> impl Iterator<Item = &Value> {
+    let lists = ["slides", "layouts", "masters"]
+        .into_iter()
+        .filter_map(|key| presentation.get(key).and_then(Value::as_array))
+        .flatten();
+    let notes = presentation
+        .get("slides")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|slide| slide.pointer("/slideProperties/notesPage"));
+    lists.chain(presentation.get("notesMaster")).chain(notes)
+}
+#[cfg(test)]
+#[cfg(feature = "yup-oauth2")]
+mod tests {
+    use super::*;
+    use crate::auth::StaticTokenProvider;
+    use crate::client::SlidesClient;
+    use crate::models::requests::{CreateSlideRequest, DeleteObjectRequest, Request};
+    use crate::models::responses::{CreateSlideResponse, Response};
+    use crate::retry::RetryPolicy;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_client_against_emulator() {
+        let emulator = SlidesEmulator::builder()
+            .add_presentation_json(r#"{"presentationId": "deck", "slides": [{"objectId": "s1"}]}"#)
+            .unwrap()
+            .start()
+            .await
+            .unwrap();
+        let client = SlidesClient::builder()
+            .set_token_provider(StaticTokenProvider::new("test-token"))
+            .set_base_url(emulator.base_url())
+            .set_retry_policy(RetryPolicy {
+                initial_backoff: Duration::from_millis(1),
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+
+        // Injected transient failures are retried by the client.
+        emulator.fail_next(2, StatusCode::SERVICE_UNAVAILABLE);
+        let presentation = client.get_presentation("deck").await.unwrap();
+        assert_eq!(presentation.slides.unwrap().len(), 1);
+        assert_eq!(emulator.request_count(), 3);
+
+        let response = client
+            .batch_update(
+                "deck",
+                vec![
+                    Request::CreateSlide(CreateSlideRequest {
+                        object_id: Some("slide2".to_string()),
+                        ..Default::default()
+                    }),
+                    Request::DeleteObject(DeleteObjectRequest {
+                        object_id: "s1".to_string(),
+                    }),
+                ],
+                presentation.revision_id.map(|revision_id| WriteControl {
+                    required_revision_id: Some(revision_id),
+                }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response.replies[0],
+            Response::CreateSlide(CreateSlideResponse {
+                object_id: Some("slide2".to_string())
+            })
+        );
+        let slides = emulator.presentation_json("deck").unwrap()["slides"].clone();
+        assert_eq!(
+            slides,
+            json!([{
+                "objectId": "slide2",
+                "pageType": "SLIDE",
+                "pageElements": [],
+                "slideProperties": {}
+            }])
+        );
+
+        // Errors carry the message of the Google-style error body.
+        let error = client.get_page("deck", "missing").await.unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+        assert!(error
+            .to_string()
+            .contains("Requested entity was not found."));
+    }
+
+    fn client_for(emulator: &SlidesEmulator) -> SlidesClient {
+        SlidesClient::builder()
+            .set_token_provider(StaticTokenProvider::new("test-token"))
+            .set_base_url(emulator.base_url())
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_batch_update_applies_requests_with_the_engine() {
+        let emulator = SlidesEmulator::builder()
+            .add_presentation_json(
+                r#"{"presentationId": "deck", "revisionId": "rev1", "slides": [{
+                    "objectId": "slide1",
+                    "pageElements": [{"objectId": "box1", "shape": {"shapeType": "TEXT_BOX"}}],
+                    "futureField": {"kept": true}
+                }]}"#,
+            )
+            .unwrap()
+            .start()
+            .await
+            .unwrap();
+        let client = client_for(&emulator);
+
+        let requests: Vec<Request> = serde_json::from_value(json!([
+            {"insertText": {"objectId": "box1", "text": "Hello {{name}}"}},
+            {"replaceAllText": {"containsText": {"text": "{{name}}"}, "replaceText": "Ada"}},
+            {"createShape": {"shapeType": "RECTANGLE",
+                "elementProperties": {"pageObjectId": "slide1"}}}
+        ]))
+        .unwrap();
+        let response = client
+            .batch_update(
+                "deck",
+                requests,
+                Some(WriteControl {
+                    required_revision_id: Some("rev1".to_string()),
+                }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&response.replies).unwrap(),
+            json!([
+                {},
+                {"replaceAllText": {"occurrencesChanged": 1}},
+                {"createShape": {"objectId": "emulator_object_1"}}
+            ])
+        );
+
+        let stored = emulator.presentation_json("deck").unwrap();
+        assert_eq!(stored["revisionId"], "emulator_rev_1");
+        assert_eq!(stored["slides"][0]["futureField"], json!({"kept": true}));
+        assert_eq!(
+            stored["slides"][0]["pageElements"][0]["shape"]["text"]["textElements"][1]["textRun"]
+                ["content"],
+            "Hello Ada\n"
+        );
+        assert_eq!(
+            stored["slides"][0]["pageElements"][1]["objectId"],
+            "emulator_object_1"
+        );
+
+        // A failing request rejects the whole batch.
+        let requests: Vec<Request> = serde_json::from_value(json!([
+            {"deleteObject": {"objectId": "box1"}},
+            {"groupObjects": {"childrenObjectIds": ["box1", "emulator_object_1"]}}
+        ]))
+        .unwrap();
+        let error = client
+            .batch_update("deck", requests, None)
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+        assert!(error.to_string().contains(
+            "Invalid requests[1]: The request 'groupObjects' is not supported by the local engine."
+        ));
+        assert_eq!(emulator.presentation_json("deck").unwrap(), stored);
+    }
+
+    #[tokio::test]
+    async fn test_nested_field_masks_are_applied() {
+        let emulator = SlidesEmulator::builder()
+            .add_presentation_json(
+                r#"{"presentationId": "deck", "title": "Deck", "slides": [{
+                    "objectId": "slide1",
+                    "pageElements": [{"objectId": "box1", "title": "Box",
+                        "shape": {"shapeType": "TEXT_BOX"}}]
+                }]}"#,
+            )
+            .unwrap()
+            .start()
+            .await
+            .unwrap();
+        let client = client_for(&emulator);
+        let get = |fields: &'static str| {
+            let url = format!("{}/v1/presentations/deck", emulator.base_url());
+            async move {
+                reqwest::Client::new()
+                    .get(url)
+                    .query(&[("fields", fields)])
+                    .bearer_auth("test-token")
+                    .send()
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let body: Value = get("title,slides(objectId,pageElements/title)")
+            .await
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "title": "Deck",
+                "slides": [{"objectId": "slide1", "pageElements": [{"title": "Box"}]}]
+            })
+        );
+        let body: Value = get("slides(pageElements),slides/objectId")
+            .await
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(body["slides"][0]["objectId"], "slide1");
+        assert_eq!(
+            body["slides"][0]["pageElements"][0]["shape"]["shapeType"],
+            "TEXT_BOX"
+        );
+
+        for malformed in ["slides(objectId", "slides)", "title,,slides", "slides()"] {
+            let response = get(malformed).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", malformed);
+        }
+
+        let mask = crate::field_mask::FieldMask::new().field(
+            crate::field_mask::PresentationField::Slides(vec![
+                crate::field_mask::PageField::ObjectId,
+            ]),
+        );
+        let presentation = client
+            .get_presentation_with_fields("deck", &mask)
+            .await
+            .unwrap();
+        assert_eq!(presentation.title, None);
+        let slides = presentation.slides.unwrap();
+        assert_eq!(slides[0].object_id, "slide1");
+        assert!(slides[0].page_elements.is_none());
+    }
+}
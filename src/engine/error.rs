@@ -0,0 +1,42 @@
+use thiserror::Error;
+
+/// Errors raised while applying `batchUpdate` requests locally.
+#[derive(Error, Debug)]
+pub enum EngineError {
+    /// No page or page element has the given object ID.
+    #[error("The object ({0}) could not be found.")]
+    ObjectNotFound(String),
+
+    /// An object ID supplied for a new object is already in use.
+    #[error("The object ID ({0}) should be unique among all pages and page elements.")]
+    DuplicateObjectId(String),
+
+    /// The object exists but cannot be targeted by the request (e.g. text on an image).
+    #[error("The object ({object_id}) does not support this request: {reason}")]
+    InvalidTarget { object_id: String, reason: String },
+
+    /// A text range or insertion index lies outside the text.
+    #[error("Invalid range: {0}")]
+    InvalidRange(String),
+
+    /// A request field has an invalid value.
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    /// The request kind is not implemented by the local engine.
+    #[error("The request '{0}' is not supported by the local engine.")]
+    Unsupported(String),
+
+    /// A field mask could not be applied to the model.
+    #[error("Failed to apply field mask: {0}")]
+    FieldMask(#[from] serde_json::Error),
+
+    /// An error of a specific request in a batch.
+    #[error("Invalid requests[{index}]: {source}")]
+    InRequest {
+        /// Position of the failing request in the batch.
+        index: usize,
+        /// The error of that request.
+        source: Box<EngineError>,
+    },
+}
@@ -0,0 +1,154 @@
+//! Applies `fields` masks of update requests (e.g. `bold,foregroundColor`) to model values.
+
+use super::error::EngineError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Returns a copy of `target` in which every path listed in `fields` is taken from `source`.
+///
+/// A path that is unset in `source` is reset (removed) in `target`, as the API does.
+/// `*` selects every field.
+pub(crate) fn merge_fields<T>(target: &T, source: &T, fields: &str) -> Result<T, EngineError>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let mut target_value = to_object(target)?;
+    let source_value = to_object(source)?;
+
+    let paths: Vec<&str> = fields
+        .split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect();
+    if paths.is_empty() {
+        return Err(EngineError::InvalidRequest(
+            "At least one field must be listed in 'fields'.".to_string(),
+        ));
+    }
+
+    for path in paths {
+        if path == "*" {
+            target_value = source_value.clone();
+            continue;
+        }
+        let segments: Vec<&str> = path.split('.').collect();
+        match lookup(&source_value, &segments) {
+            Some(value) => insert(&mut target_value, &segments, value.clone()),
+            None => remove(&mut target_value, &segments),
+        }
+    }
+
+    Ok(serde_json::from_value(target_value)?)
+}
+
+/// Serializes a model; a model without any set field becomes an empty object.
+fn to_object<T: Serialize>(value: &T) -> Result<Value, EngineError> {
+    match serde_json::to_value(value)? {
+        Value::Null => Ok(Value::Object(Map::new())),
+        other => Ok(other),
+    }
+}
+
+fn lookup<'a>(value: &'a Value, segments: &[&str]) -> Option<&'a Value> {
+    segments
+        .iter()
+        .try_fold(value, |current, segment| current.get(*segment))
+}
+
+fn insert(value: &mut Value, segments: &[&str], new_value: Value) {
+    let Some((last, parents)) = segments.split_last() else {
+        return;
+    };
+    let mut current = value;
+    for segment in parents {
+        if !current.is_object() {
+            *current = Value::Object(Map::new());
+        }
+        current = current
+            .as_object_mut()
+            .map(|object| {
+                object
+                    .entry(segment.to_string())
+                    .or_insert_with(|| Value::Object(Map::new()))
+            })
+            .expect("just ensured an object");
+    }
+    if !current.is_object() {
+        *current = Value::Object(Map::new());
+    }
+    if let Some(object) = current.as_object_mut() {
+        object.insert(last.to_string(), new_value);
+    }
+}
+
+fn remove(value: &mut Value, segments: &[&str]) {
+    let Some((last, parents)) = segments.split_last() else {
+        return;
+    };
+    let mut current = value;
+    for segment in parents {
+        match current.get_mut(*segment) {
+            Some(next) => current = next,
+            None => return,
+        }
+    }
+    if let Some(object) = current.as_object_mut() {
+        object.remove(*last);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::properties::TextStyle;
+    use serde_json::json;
+
+    fn style(value: Value) -> TextStyle {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_merge_fields_copies_and_resets_listed_paths() {
+        let target = style(json!({
+            "bold": true,
+            "italic": true,
+            "fontSize": {"magnitude": 12, "unit": "PT"}
+        }));
+        let source = style(json!({"bold": false, "fontFamily": "Arial"}));
+
+        // `italic` is listed but unset in the source, so it is reset.
+        let merged = merge_fields(&target, &source, "bold, italic,fontFamily").unwrap();
+        assert_eq!(
+            merged,
+            style(json!({
+                "bold": false,
+                "fontFamily": "Arial",
+                "fontSize": {"magnitude": 12, "unit": "PT"}
+            }))
+        );
+
+        // Nested paths only touch the named field.
+        let source = style(json!({"fontSize": {"magnitude": 20}}));
+        let merged = merge_fields(&target, &source, "fontSize.magnitude").unwrap();
+        assert_eq!(
+            merged,
+            style(json!({
+                "bold": true,
+                "italic": true,
+                "fontSize": {"magnitude": 20, "unit": "PT"}
+            }))
+        );
+    }
+
+    #[test]
+    fn test_merge_fields_star_replaces_everything() {
+        let target = style(json!({"bold": true, "italic": true}));
+        let source = style(json!({"underline": true}));
+        assert_eq!(merge_fields(&target, &source, "*").unwrap(), source);
+        assert!(matches!(
+            merge_fields(&target, &source, " , "),
+            Err(EngineError::InvalidRequest(_))
+        ));
+    }
+}
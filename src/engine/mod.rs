@@ -0,0 +1,1332 @@
+//! Applies `batchUpdate` requests to an in-memory `Presentation`, without calling Google.
+//!
+//! This lets a planned edit be previewed (rendered to SVG, or diffed with `ComparerBuilder`)
+//! before it is sent. Text edits keep `TextElement::start_index`/`end_index` consistent, and
+//! new objects get unique IDs.
+//!
+//! ```
+//! use gslides_tools::engine::BatchUpdateEngine;
+//! use gslides_tools::models::requests::{CreateSlideRequest, Request};
+//! use gslides_tools::Presentation;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut presentation: Presentation =
+//!     serde_json::from_str(r#"{"presentationId": "deck", "slides": []}"#)?;
+//! let replies = BatchUpdateEngine::new().apply(
+//!     &mut presentation,
+//!     &[Request::CreateSlide(CreateSlideRequest::default())],
+//! )?;
+//! assert_eq!(presentation.slides.as_ref().map(Vec::len), Some(1));
+//! assert_eq!(replies.len(), 1);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Supported requests: `createSlide`, `createShape`, `createTable`, `createImage`,
+//! `createLine`, `insertText`, `deleteText`, `replaceAllText` (without regex),
+//! `deleteObject`, `duplicateObject`, `updateTextStyle`, `updateParagraphStyle`,
+//! `updateShapeProperties`, `updatePageProperties`, `updatePageElementTransform`,
+//! `updatePageElementAltText`, `updateSlidesPosition` and `replaceImage`. Others fail with
+//! `EngineError::Unsupported`.
+
+pub mod error;
+mod fields;
+mod objects;
+mod text;
+
+pub use error::EngineError;
+pub(crate) use objects::{collect_page_ids, object_ids};
+
+use crate::geometry::Matrix;
+use crate::models::common::{AffineTransform, Unit};
+use crate::models::elements::{PageElement, PageElementKind};
+use crate::models::image::Image;
+use crate::models::line::{Line, LineCategory, LineType};
+use crate::models::page::{Page, PageType};
+use crate::models::page_properties::PageProperties;
+use crate::models::placeholder::Placeholder;
+use crate::models::presentation::Presentation;
+use crate::models::properties::SlideProperties;
+use crate::models::requests::*;
+use crate::models::responses::*;
+use crate::models::shape::Shape;
+use crate::models::table::{Table, TableCell, TableCellLocation, TableRow};
+use crate::models::table_properties::TableColumnProperties;
+use crate::models::text::TextContent;
+use fields::merge_fields;
+use objects::{
+    containing_list_mut, find_element_mut, find_page_mut, for_each_text_mut, reassign_element_ids,
+    remove_element, text_mut,
+};
+use std::collections::{HashMap, HashSet};
+pub(crate) use text::EditableText;
+
+/// Applies `batchUpdate` requests to presentations in memory.
+///
+/// Object IDs generated for new objects are `<prefix><n>` (default prefix `local_`), skipping
+/// IDs already in use.
+#[derive(Debug, Clone)]
+pub struct BatchUpdateEngine {
+    id_prefix: String,
+    next_id: u64,
+}
+
+impl Default for BatchUpdateEngine {
+    fn default() -> Self {
+        Self {
+            id_prefix: "local_".to_string(),
+            next_id: 0,
+        }
+    }
+}
+
+impl BatchUpdateEngine {
+    /// Creates an engine generating IDs like `local_1`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the prefix of generated object IDs.
+    pub fn set_id_prefix(mut self, id_prefix: impl Into<String>) -> Self {
+        self.id_prefix = id_prefix.into();
+        self
+    }
+
+    /// Applies `requests` in order, like `presentations.batchUpdate`.
+    ///
+    /// The batch is atomic: if any request fails, `presentation` is left unchanged. When at
+    /// least one request is applied, `revision_id` is cleared, since the result no longer
+    /// matches any revision stored by Google.
+    ///
+    /// # Arguments
+    ///
+    /// * `presentation` - The presentation to update in place.
+    /// * `requests` - The requests to apply.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing one reply per request, or the first failure wrapped in
+    /// `EngineError::InRequest`.
+    pub fn apply(
+        &mut self,
+        presentation: &mut Presentation,
+        requests: &[Request],
+    ) -> Result<Vec<Response>, EngineError> {
+        let mut batch = Batch {
+            used_ids: object_ids(presentation),
+            presentation: presentation.clone(),
+            id_prefix: &self.id_prefix,
+            next_id: self.next_id,
+        };
+        let mut replies = Vec::with_capacity(requests.len());
+        for (index, request) in requests.iter().enumerate() {
+            let reply = batch
+                .apply(request)
+                .map_err(|source| EngineError::InRequest {
+                    index,
+                    source: Box::new(source),
+                })?;
+            replies.push(reply);
+        }
+        if !requests.is_empty() {
+            batch.presentation.revision_id = None;
+        }
+        self.next_id = batch.next_id;
+        *presentation = batch.presentation;
+        Ok(replies)
+    }
+
+    /// Applies a full `batchUpdate` body, honouring its `WriteControl`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the response the API would send.
+    pub fn apply_batch(
+        &mut self,
+        presentation: &mut Presentation,
+        request: &BatchUpdatePresentationRequest,
+    ) -> Result<BatchUpdatePresentationResponse, EngineError> {
+        if let Some(required) = request
+            .write_control
+            .as_ref()
+            .and_then(|w| w.required_revision_id.as_deref())
+        {
+            if presentation.revision_id.as_deref() != Some(required) {
+                return Err(EngineError::InvalidRequest(format!(
+                    "The required revision ID ({}) does not match the presentation's revision.",
+                    required
+                )));
+            }
+        }
+        let replies = self.apply(presentation, &request.requests)?;
+        Ok(BatchUpdatePresentationResponse {
+            presentation_id: Some(presentation.presentation_id.clone()),
+            replies,
+            write_control: None,
+        })
+    }
+}
+
+/// The state of one `apply` call, working on a copy of the presentation.
+struct Batch<'a> {
+    presentation: Presentation,
+    used_ids: HashSet<String>,
+    id_prefix: &'a str,
+    next_id: u64,
+}
+
+impl Batch<'_> {
+    fn apply(&mut self, request: &Request) -> Result<Response, EngineError> {
+        let empty = Ok(Response::Empty(EmptyResponse {}));
+        match request {
+            Request::CreateSlide(r) => {
+                let object_id = self.create_slide(r)?;
+                Ok(Response::CreateSlide(CreateSlideResponse {
+                    object_id: Some(object_id),
+                }))
+            }
+            Request::CreateShape(r) => {
+                let kind = PageElementKind::Shape(Shape {
+                    shape_type: Some(r.shape_type.clone()),
+                    text: None,
+                    shape_properties: None,
+                    placeholder: None,
+                    extra: Default::default(),
+                });
+                let object_id =
+                    self.create_element(r.object_id.as_deref(), &r.element_properties, kind)?;
+                Ok(Response::CreateShape(CreateShapeResponse {
+                    object_id: Some(object_id),
+                }))
+            }
+            Request::CreateTable(r) => {
+                let kind = PageElementKind::Table(new_table(r.rows, r.columns)?);
+                let object_id =
+                    self.create_element(r.object_id.as_deref(), &r.element_properties, kind)?;
+                Ok(Response::CreateTable(CreateTableResponse {
+                    object_id: Some(object_id),
+                }))
+            }
+            Request::CreateImage(r) => {
+                let kind = PageElementKind::Image(Image {
+                    content_url: Some(r.url.clone()),
+                    source_url: Some(r.url.clone()),
+                    image_properties: None,
+                    placeholder: None,
+                    extra: Default::default(),
+                });
+                let object_id =
+                    self.create_element(r.object_id.as_deref(), &r.element_properties, kind)?;
+                Ok(Response::CreateImage(CreateImageResponse {
+                    object_id: Some(object_id),
+                }))
+            }
+            Request::CreateLine(r) => {
+                let line_type = match r.category {
+                    Some(LineCategory::Bent) => LineType::BentConnector_3,
+                    Some(LineCategory::Curved) => LineType::CurvedConnector_3,
+                    _ => LineType::StraightConnector_1,
+                };
+                let kind = PageElementKind::Line(Line {
+                    line_properties: None,
+                    line_type: Some(line_type),
+                    line_category: Some(r.category.clone().unwrap_or(LineCategory::Straight)),
+                    extra: Default::default(),
+                });
+                let object_id =
+                    self.create_element(r.object_id.as_deref(), &r.element_properties, kind)?;
+                Ok(Response::CreateLine(CreateLineResponse {
+                    object_id: Some(object_id),
+                }))
+            }
+            Request::InsertText(r) => {
+                self.edit_text(&r.object_id, r.cell_location.as_ref(), |text| {
+                    text.insert(r.insertion_index.unwrap_or(0), &r.text)
+                })?;
+                empty
+            }
+            Request::DeleteText(r) => {
+                self.edit_text(&r.object_id, r.cell_location.as_ref(), |text| {
+                    let (start, end) = text.resolve_range(Some(&r.text_range))?;
+                    text.delete(start, end)
+                })?;
+                empty
+            }
+            Request::ReplaceAllText(r) => {
+                let occurrences = self.replace_all_text(r)?;
+                Ok(Response::ReplaceAllText(ReplaceAllTextResponse {
+                    occurrences_changed: Some(occurrences as i32),
+                }))
+            }
+            Request::UpdateTextStyle(r) => {
+                self.edit_text(&r.object_id, r.cell_location.as_ref(), |text| {
+                    let (start, end) = text.resolve_range(r.text_range.as_ref())?;
+                    text.update_text_style(start, end, &r.style, &r.fields)
+                })?;
+                empty
+            }
+            Request::UpdateParagraphStyle(r) => {
+                self.edit_text(&r.object_id, r.cell_location.as_ref(), |text| {
+                    let (start, end) = text.resolve_range(r.text_range.as_ref())?;
+                    text.update_paragraph_style(start, end, &r.style, &r.fields)
+                })?;
+                empty
+            }
+            Request::DeleteObject(r) => {
+                self.delete_object(&r.object_id)?;
+                empty
+            }
+            Request::DuplicateObject(r) => {
+                let object_id = self.duplicate_object(r)?;
+                Ok(Response::DuplicateObject(DuplicateObjectResponse {
+                    object_id: Some(object_id),
+                }))
+            }
+            Request::UpdateShapeProperties(r) => {
+                let element = self.element_mut(&r.object_id)?;
+                let PageElementKind::Shape(shape) = &mut element.element_kind else {
+                    return Err(EngineError::InvalidTarget {
+                        object_id: r.object_id.clone(),
+                        reason: "the element is not a shape".to_string(),
+                    });
+                };
+                let current = shape.shape_properties.clone().unwrap_or_default();
+                shape.shape_properties =
+                    Some(merge_fields(&current, &r.shape_properties, &r.fields)?);
+                empty
+            }
+            Request::UpdatePageProperties(r) => {
+                let page = find_page_mut(&mut self.presentation, &r.object_id)
+                    .ok_or_else(|| EngineError::ObjectNotFound(r.object_id.clone()))?;
+                let current = page.page_properties.clone().unwrap_or(PageProperties {
+                    page_background_fill: None,
+                    color_scheme: None,
+                    extra: Default::default(),
+                });
+                page.page_properties = Some(merge_fields(&current, &r.page_properties, &r.fields)?);
+                empty
+            }
+            Request::UpdatePageElementTransform(r) => {
+                let element = self.element_mut(&r.object_id)?;
+                element.transform = Some(match r.apply_mode {
+                    ApplyMode::Absolute => r.transform.clone(),
+                    ApplyMode::Relative => Matrix::from_transform(&r.transform)
+                        .multiply(&Matrix::of_element(element))
+                        .to_transform(),
+                    ApplyMode::ApplyModeUnspecified => {
+                        return Err(EngineError::InvalidRequest(
+                            "The apply mode must be specified.".to_string(),
+                        ))
+                    }
+                });
+                empty
+            }
+            Request::UpdatePageElementAltText(r) => {
+                let element = self.element_mut(&r.object_id)?;
+                if let Some(title) = &r.title {
+                    element.title = Some(title.clone());
+                }
+                if let Some(description) = &r.description {
+                    element.description = Some(description.clone());
+                }
+                empty
+            }
+            Request::ReplaceImage(r) => {
+                let element = self.element_mut(&r.image_object_id)?;
+                let PageElementKind::Image(image) = &mut element.element_kind else {
+                    return Err(EngineError::InvalidTarget {
+                        object_id: r.image_object_id.clone(),
+                        reason: "the element is not an image".to_string(),
+                    });
+                };
+                image.content_url = Some(r.url.clone());
+                image.source_url = Some(r.url.clone());
+                empty
+            }
+            Request::UpdateSlidesPosition(r) => {
+                self.update_slides_position(r)?;
+                empty
+            }
+            other => Err(EngineError::Unsupported(request_name(other))),
+        }
+    }
+
+    fn element_mut(&mut self, object_id: &str) -> Result<&mut PageElement, EngineError> {
+        find_element_mut(&mut self.presentation, object_id)
+            .ok_or_else(|| EngineError::ObjectNotFound(object_id.to_string()))
+    }
+
+    /// Returns the requested object ID after checking it, or generates a fresh one.
+    fn claim_id(&mut self, requested: Option<&str>) -> Result<String, EngineError> {
+        let Some(object_id) = requested else {
+            return Ok(self.generate_id());
+        };
+        validate_object_id(object_id)?;
+        if !self.used_ids.insert(object_id.to_string()) {
+            return Err(EngineError::DuplicateObjectId(object_id.to_string()));
+        }
+        Ok(object_id.to_string())
+    }
+
+    fn generate_id(&mut self) -> String {
+        loop {
+            self.next_id += 1;
+            let object_id = format!("{}{}", self.id_prefix, self.next_id);
+            if self.used_ids.insert(object_id.clone()) {
+                return object_id;
+            }
+        }
+    }
+
+    fn create_slide(&mut self, request: &CreateSlideRequest) -> Result<String, EngineError> {
+        let slide_count = self.presentation.slides.as_ref().map_or(0, Vec::len);
+        let index = match request.insertion_index {
+            None => slide_count,
+            Some(index) => usize::try_from(index)
+                .ok()
+                .filter(|index| *index <= slide_count)
+                .ok_or_else(|| {
+                    EngineError::InvalidRequest(format!(
+                        "The insertion index ({}) is out of range.",
+                        index
+                    ))
+                })?,
+        };
+        let layout = self.find_layout(request.slide_layout_reference.as_ref(), index)?;
+        let object_id = self.claim_id(request.object_id.as_deref())?;
+
+        let mappings = request
+            .placeholder_id_mappings
+            .as_deref()
+            .unwrap_or_default();
+        let mut elements = Vec::new();
+        let mut used_mappings = 0;
+        for layout_element in layout.iter().flat_map(|l| l.page_elements.iter().flatten()) {
+            let PageElementKind::Shape(layout_shape) = &layout_element.element_kind else {
+                continue;
+            };
+            let Some(layout_placeholder) = &layout_shape.placeholder else {
+                continue;
+            };
+            let mapping = mappings.iter().find(|m| {
+                m.layout_placeholder_object_id.as_deref() == Some(&layout_element.object_id)
+                    || m.layout_placeholder.as_ref().is_some_and(|p| {
+                        p.placeholder_type == layout_placeholder.placeholder_type
+                            && p.index.unwrap_or(0) == layout_placeholder.index.unwrap_or(0)
+                    })
+            });
+            used_mappings += usize::from(mapping.is_some());
+            let element_id = self.claim_id(mapping.map(|m| m.object_id.as_str()))?;
+            elements.push(PageElement {
+                object_id: element_id,
+                size: layout_element.size.clone(),
+                transform: layout_element.transform.clone(),
+                title: None,
+                description: None,
+                element_kind: PageElementKind::Shape(Shape {
+                    shape_type: layout_shape.shape_type.clone(),
+                    text: None,
+                    shape_properties: None,
+                    placeholder: Some(Placeholder {
+                        placeholder_type: layout_placeholder.placeholder_type.clone(),
+                        index: layout_placeholder.index,
+                        parent_object_id: Some(layout_element.object_id.clone()),
+                        extra: Default::default(),
+                    }),
+                    extra: Default::default(),
+                }),
+                extra: Default::default(),
+            });
+        }
+        if used_mappings < mappings.len() {
+            return Err(EngineError::InvalidRequest(
+                "A placeholder ID mapping does not match any placeholder of the layout."
+                    .to_string(),
+            ));
+        }
+
+        let slide = Page {
+            object_id: object_id.clone(),
+            page_type: Some(PageType::Slide),
+            page_elements: Some(elements),
+            revision_id: None,
+            page_properties: None,
+            slide_properties: Some(SlideProperties {
+                layout_object_id: layout.as_ref().map(|l| l.object_id.clone()),
+                master_object_id: layout
+                    .as_ref()
+                    .and_then(|l| l.layout_properties.as_ref())
+                    .and_then(|p| p.master_object_id.clone()),
+                notes_page: None,
+                is_skipped: None,
+                extra: Default::default(),
+            }),
+            layout_properties: None,
+            notes_properties: None,
+            master_properties: None,
+            extra: Default::default(),
+        };
+        self.presentation
+            .slides
+            .get_or_insert_with(Vec::new)
+            .insert(index, slide);
+        Ok(object_id)
+    }
+
+    /// Resolves a layout reference against the master of the slide before `index` (or the
+    /// first master). Without a reference the `BLANK` layout is used if the deck has one.
+    fn find_layout(
+        &self,
+        reference: Option<&LayoutReference>,
+        index: usize,
+    ) -> Result<Option<Page>, EngineError> {
+        let layouts = self.presentation.layouts.as_deref().unwrap_or_default();
+        let slides = self.presentation.slides.as_deref().unwrap_or_default();
+        let master_id = slides
+            .get(index.saturating_sub(1))
+            .and_then(|s| s.slide_properties.as_ref())
+            .and_then(|p| p.master_object_id.clone())
+            .or_else(|| {
+                self.presentation
+                    .masters
+                    .as_ref()
+                    .and_then(|m| m.first())
+                    .map(|m| m.object_id.clone())
+            });
+        let in_master = |layout: &&Page| {
+            master_id.is_none()
+                || layout
+                    .layout_properties
+                    .as_ref()
+                    .and_then(|p| p.master_object_id.as_ref())
+                    == master_id.as_ref()
+        };
+        let layout_named = |name: &PredefinedLayout| {
+            let name = serde_json::to_value(name).ok()?;
+            layouts
+                .iter()
+                .filter(in_master)
+                .find(|l| {
+                    l.layout_properties.as_ref().and_then(|p| p.name.as_deref()) == name.as_str()
+                })
+                .cloned()
+        };
+        match reference {
+            None => Ok(layout_named(&PredefinedLayout::Blank)),
+            Some(LayoutReference::LayoutId(layout_id)) => layouts
+                .iter()
+                .find(|l| &l.object_id == layout_id)
+                .cloned()
+                .map(Some)
+                .ok_or_else(|| EngineError::ObjectNotFound(layout_id.clone())),
+            Some(LayoutReference::PredefinedLayout(name)) => {
+                layout_named(name).map(Some).ok_or_else(|| {
+                    EngineError::InvalidRequest(format!(
+                        "The layout {:?} was not found in the current master.",
+                        name
+                    ))
+                })
+            }
+        }
+    }
+
+    fn create_element(
+        &mut self,
+        object_id: Option<&str>,
+        properties: &PageElementProperties,
+        element_kind: PageElementKind,
+    ) -> Result<String, EngineError> {
+        let page_id = properties.page_object_id.as_deref().ok_or_else(|| {
+            EngineError::InvalidRequest("'elementProperties.pageObjectId' is required.".to_string())
+        })?;
+        if find_page_mut(&mut self.presentation, page_id).is_none() {
+            return Err(EngineError::ObjectNotFound(page_id.to_string()));
+        }
+        let object_id = self.claim_id(object_id)?;
+        let element = PageElement {
+            object_id: object_id.clone(),
+            size: properties.size.clone(),
+            transform: Some(properties.transform.clone().unwrap_or_else(identity)),
+            title: None,
+            description: None,
+            element_kind,
+            extra: Default::default(),
+        };
+        if let Some(page) = find_page_mut(&mut self.presentation, page_id) {
+            page.page_elements
+                .get_or_insert_with(Vec::new)
+                .push(element);
+        }
+        Ok(object_id)
+    }
+
+    fn edit_text(
+        &mut self,
+        object_id: &str,
+        cell_location: Option<&TableCellLocation>,
+        edit: impl FnOnce(&mut EditableText) -> Result<(), EngineError>,
+    ) -> Result<(), EngineError> {
+        let content = text_mut(&mut self.presentation, object_id, cell_location)?;
+        let mut text = EditableText::from_content(content.as_ref());
+        edit(&mut text)?;
+        *content = text.into_content();
+        Ok(())
+    }
+
+    fn replace_all_text(&mut self, request: &ReplaceAllTextRequest) -> Result<usize, EngineError> {
+        let criteria = &request.contains_text;
+        if criteria.search_by_regex == Some(true) {
+            return Err(EngineError::Unsupported(
+                "replaceAllText with searchByRegex".to_string(),
+            ));
+        }
+        if criteria.text.is_empty() {
+            return Err(EngineError::InvalidRequest(
+                "'containsText.text' must not be empty.".to_string(),
+            ));
+        }
+        let match_case = criteria.match_case.unwrap_or(false);
+        let mut occurrences = 0;
+        let mut replace = |content: &mut Option<TextContent>| {
+            let mut text = EditableText::from_content(content.as_ref());
+            let count = text.replace_all(&criteria.text, &request.replace_text, match_case);
+            if count > 0 {
+                *content = text.into_content();
+                occurrences += count;
+            }
+        };
+
+        match request
+            .page_object_ids
+            .as_deref()
+            .filter(|ids| !ids.is_empty())
+        {
+            None => {
+                for slide in self.presentation.slides.iter_mut().flatten() {
+                    for_each_text_mut(
+                        slide.page_elements.as_deref_mut().unwrap_or_default(),
+                        &mut replace,
+                    );
+                }
+            }
+            Some(page_ids) => {
+                for page_id in page_ids {
+                    let page = find_page_mut(&mut self.presentation, page_id)
+                        .ok_or_else(|| EngineError::ObjectNotFound(page_id.clone()))?;
+                    for_each_text_mut(
+                        page.page_elements.as_deref_mut().unwrap_or_default(),
+                        &mut replace,
+                    );
+                }
+            }
+        }
+        Ok(occurrences)
+    }
+
+    fn delete_object(&mut self, object_id: &str) -> Result<(), EngineError> {
+        if let Some(slides) = self.presentation.slides.as_mut() {
+            if let Some(position) = slides.iter().position(|s| s.object_id == object_id) {
+                slides.remove(position);
+                return Ok(());
+            }
+        }
+        remove_element(&mut self.presentation, object_id)
+            .map(|_| ())
+            .ok_or_else(|| EngineError::ObjectNotFound(object_id.to_string()))
+    }
+
+    fn duplicate_object(
+        &mut self,
+        request: &DuplicateObjectRequest,
+    ) -> Result<String, EngineError> {
+        let slides = self.presentation.slides.as_deref().unwrap_or_default();
+        let original = match slides.iter().position(|s| s.object_id == request.object_id) {
+            Some(position) => Duplicate::Slide(position, Box::new(slides[position].clone())),
+            None => Duplicate::Element(Box::new(self.element_mut(&request.object_id)?.clone())),
+        };
+
+        let mut requested_ids = request.object_ids.clone().unwrap_or_default();
+        for new_id in requested_ids.values() {
+            self.claim_id(Some(new_id))?;
+        }
+        let mut assigned = HashMap::new();
+        let mut new_id = |old_id: &str| {
+            let new_id = requested_ids
+                .remove(old_id)
+                .unwrap_or_else(|| self.generate_id());
+            assigned.insert(old_id.to_string(), new_id.clone());
+            new_id
+        };
+
+        match original {
+            Duplicate::Slide(position, mut slide) => {
+                slide.object_id = new_id(&slide.object_id);
+                slide.revision_id = None;
+                reassign_element_ids(
+                    slide.page_elements.as_deref_mut().unwrap_or_default(),
+                    &mut new_id,
+                );
+                if let Some(notes_page) = slide
+                    .slide_properties
+                    .as_mut()
+                    .and_then(|p| p.notes_page.as_deref_mut())
+                {
+                    notes_page.object_id = new_id(&notes_page.object_id);
+                    reassign_element_ids(
+                        notes_page.page_elements.as_deref_mut().unwrap_or_default(),
+                        &mut new_id,
+                    );
+                    if let Some(speaker_notes_id) = notes_page
+                        .notes_properties
+                        .as_mut()
+                        .and_then(|p| p.speaker_notes_object_id.as_mut())
+                    {
+                        if let Some(assigned_id) = assigned.get(speaker_notes_id) {
+                            *speaker_notes_id = assigned_id.clone();
+                        }
+                    }
+                }
+                let object_id = slide.object_id.clone();
+                self.presentation
+                    .slides
+                    .get_or_insert_with(Vec::new)
+                    .insert(position + 1, *slide);
+                Ok(object_id)
+            }
+            Duplicate::Element(mut element) => {
+                reassign_element_ids(std::slice::from_mut(&mut *element), &mut new_id);
+                let object_id = element.object_id.clone();
+                if let Some((elements, position)) =
+                    containing_list_mut(&mut self.presentation, &request.object_id)
+                {
+                    elements.insert(position + 1, *element);
+                }
+                Ok(object_id)
+            }
+        }
+    }
+
+    fn update_slides_position(
+        &mut self,
+        request: &UpdateSlidesPositionRequest,
+    ) -> Result<(), EngineError> {
+        let slides = self.presentation.slides.get_or_insert_with(Vec::new);
+        let mut positions = Vec::with_capacity(request.slide_object_ids.len());
+        for slide_id in &request.slide_object_ids {
+            let position = slides
+                .iter()
+                .position(|s| &s.object_id == slide_id)
+                .ok_or_else(|| EngineError::ObjectNotFound(slide_id.clone()))?;
+            if positions.last().is_some_and(|last| *last >= position) {
+                return Err(EngineError::InvalidRequest(
+                    "'slideObjectIds' must be unique and in presentation order.".to_string(),
+                ));
+            }
+            positions.push(position);
+        }
+        let insertion_index = usize::try_from(request.insertion_index)
+            .ok()
+            .filter(|index| *index <= slides.len())
+            .ok_or_else(|| {
+                EngineError::InvalidRequest(format!(
+                    "The insertion index ({}) is out of range.",
+                    request.insertion_index
+                ))
+            })?;
+
+        let before_insertion = positions.iter().filter(|p| **p < insertion_index).count();
+        let mut moved = Vec::with_capacity(positions.len());
+        for position in positions.iter().rev() {
+            moved.push(slides.remove(*position));
+        }
+        moved.reverse();
+        let index = insertion_index - before_insertion;
+        slides.splice(index..index, moved);
+        Ok(())
+    }
+}
+
+/// The object copied by `duplicateObject`: a slide (with its position) or a page element.
+enum Duplicate {
+    Slide(usize, Box<Page>),
+    Element(Box<PageElement>),
+}
+
+/// Checks an object ID supplied by a request against the API's rules: 5 to 50 characters
+/// from `[a-zA-Z0-9_-:]`, not starting with `-` or `:`.
+fn validate_object_id(object_id: &str) -> Result<(), EngineError> {
+    let is_valid_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | ':');
+    let is_valid = (5..=50).contains(&object_id.len())
+        && object_id.chars().all(is_valid_char)
+        && !object_id.starts_with(['-', ':']);
+    if is_valid {
+        Ok(())
+    } else {
+        Err(EngineError::InvalidRequest(format!(
+            "The object ID ({}) must be 5 to 50 characters from [a-zA-Z0-9_-:], starting with [a-zA-Z0-9_].",
+            object_id
+        )))
+    }
+}
+
+/// The JSON name of a request, e.g. `groupObjects`.
+fn request_name(request: &Request) -> String {
+    serde_json::to_value(request)
+        .ok()
+        .and_then(|v| v.as_object().and_then(|o| o.keys().next().cloned()))
+        .unwrap_or_default()
+}
+
+pub(crate) fn new_table(rows: i32, columns: i32) -> Result<Table, EngineError> {
+    if rows < 1 || columns < 1 {
+        return Err(EngineError::InvalidRequest(format!(
+            "A table needs at least one row and one column, got {}x{}.",
+            rows, columns
+        )));
+    }
+    let table_rows = (0..rows)
+        .map(|row| TableRow {
+            row_height: None,
+            table_cells: Some(
+                (0..columns)
+                    .map(|column| TableCell {
+                        location: Some(TableCellLocation {
+                            row_index: Some(row),
+                            column_index: Some(column),
+                            extra: Default::default(),
+                        }),
+                        row_span: Some(1),
+                        column_span: Some(1),
+                        text: None,
+                        table_cell_properties: None,
+                        extra: Default::default(),
+                    })
+                    .collect(),
+            ),
+            table_row_properties: None,
+            extra: Default::default(),
+        })
+        .collect();
+    Ok(Table {
+        rows,
+        columns,
+        table_columns: Some(
+            (0..columns)
+                .map(|_| TableColumnProperties {
+                    column_width: None,
+                    extra: Default::default(),
+                })
+                .collect(),
+        ),
+        table_rows: Some(table_rows),
+        horizontal_border_rows: None,
+        vertical_border_rows: None,
+        extra: Default::default(),
+    })
+}
+
+fn identity() -> AffineTransform {
+    AffineTransform {
+        scale_x: Some(1.0),
+        scale_y: Some(1.0),
+        shear_x: None,
+        shear_y: None,
+        translate_x: None,
+        translate_y: None,
+        unit: Some(Unit::Emu),
+        extra: Default::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::text_element::TextElementKind;
+
+    fn shape_text(presentation: &Presentation, slide: usize) -> Vec<(Option<u32>, u32, String)> {
+        let slides = presentation.slides.as_ref().unwrap();
+        let elements = slides[slide].page_elements.as_ref().unwrap();
+        let PageElementKind::Shape(shape) = &elements[0].element_kind else {
+            panic!("expected a shape");
+        };
+        let text = shape.text.as_ref().unwrap().text_elements.as_ref().unwrap();
+        text.iter()
+            .map(|e| {
+                let content = match e.kind.as_ref().unwrap() {
+                    TextElementKind::TextRun(run) => run.content.clone().unwrap(),
+                    TextElementKind::ParagraphMarker(_) => "¶".to_string(),
+                    TextElementKind::AutoText(_) => "#".to_string(),
+                };
+                (e.start_index, e.end_index.unwrap(), content)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_apply_text_edits_and_duplicate_slide() {
+        let mut presentation: Presentation = serde_json::from_str(
+            r#"{"presentationId": "deck", "revisionId": "rev1", "slides": [{
+                "objectId": "slide1",
+                "pageElements": [{"objectId": "box1", "shape": {"shapeType": "TEXT_BOX", "text": {
+                    "textElements": [
+                        {"endIndex": 6, "paragraphMarker": {}},
+                        {"endIndex": 6, "textRun": {"content": "Héllo\n"}}
+                    ]
+                }}}]
+            }]}"#,
+        )
+        .unwrap();
+        let requests: Vec<Request> = serde_json::from_str(
+            r#"[
+                {"insertText": {"objectId": "box1", "insertionIndex": 5, "text": " 🌍\nBye"}},
+                {"updateTextStyle": {"objectId": "box1", "style": {"bold": true}, "fields": "bold",
+                    "textRange": {"type": "FIXED_RANGE", "startIndex": 0, "endIndex": 5}}},
+                {"duplicateObject": {"objectId": "slide1", "objectIds": {"box1": "box1_copy"}}},
+                {"deleteText": {"objectId": "box1_copy",
+                    "textRange": {"type": "FROM_START_INDEX", "startIndex": 5}}}
+            ]"#,
+        )
+        .unwrap();
+
+        let mut engine = BatchUpdateEngine::new();
+        let replies = engine.apply(&mut presentation, &requests).unwrap();
+        assert_eq!(
+            replies[2],
+            Response::DuplicateObject(DuplicateObjectResponse {
+                object_id: Some("local_1".to_string())
+            })
+        );
+        assert_eq!(presentation.revision_id, None);
+        assert_eq!(
+            shape_text(&presentation, 0),
+            vec![
+                (None, 9, "¶".to_string()),
+                (None, 5, "Héllo".to_string()),
+                (Some(5), 9, " 🌍\n".to_string()),
+                (Some(9), 13, "¶".to_string()),
+                (Some(9), 13, "Bye\n".to_string()),
+            ]
+        );
+        assert_eq!(
+            shape_text(&presentation, 1),
+            vec![
+                (None, 6, "¶".to_string()),
+                (None, 5, "Héllo".to_string()),
+                (Some(5), 6, "\n".to_string())
+            ]
+        );
+
+        // A failing request leaves the presentation untouched.
+        let before = serde_json::to_value(&presentation).unwrap();
+        let error = engine
+            .apply(
+                &mut presentation,
+                &[
+                    Request::DeleteObject(DeleteObjectRequest {
+                        object_id: "slide1".to_string(),
+                    }),
+                    Request::DeleteObject(DeleteObjectRequest {
+                        object_id: "missing".to_string(),
+                    }),
+                ],
+            )
+            .unwrap_err();
+        assert!(matches!(error, EngineError::InRequest { index: 1, .. }));
+        assert_eq!(serde_json::to_value(&presentation).unwrap(), before);
+    }
+
+    /// A deck with a `BLANK` and a `TITLE_AND_BODY` layout and three slides.
+    fn deck() -> Presentation {
+        serde_json::from_value(serde_json::json!({
+            "presentationId": "deck",
+            "revisionId": "rev1",
+            "masters": [{"objectId": "master1", "pageType": "MASTER"}],
+            "layouts": [
+                {"objectId": "layout_blank", "pageType": "LAYOUT",
+                    "layoutProperties": {"name": "BLANK", "masterObjectId": "master1"}},
+                {"objectId": "layout_title", "pageType": "LAYOUT",
+                    "layoutProperties": {"name": "TITLE_AND_BODY", "masterObjectId": "master1"},
+                    "pageElements": [
+                        {"objectId": "layout_title_t", "shape": {"shapeType": "TEXT_BOX",
+                            "placeholder": {"type": "TITLE"}}},
+                        {"objectId": "layout_title_b", "shape": {"shapeType": "TEXT_BOX",
+                            "placeholder": {"type": "BODY"}}}
+                    ]}
+            ],
+            "slides": [
+                {"objectId": "slide1", "slideProperties": {"masterObjectId": "master1"},
+                    "pageElements": [{"objectId": "box1",
+                        "transform": {"scaleX": 2, "scaleY": 2, "translateX": 100,
+                            "translateY": 200, "unit": "EMU"},
+                        "shape": {"shapeType": "TEXT_BOX",
+                            "shapeProperties": {"contentAlignment": "MIDDLE",
+                                "outline": {"weight": {"magnitude": 2, "unit": "PT"},
+                                    "dashStyle": "DASH"}},
+                            "text": {"textElements": [
+                                {"endIndex": 18, "paragraphMarker": {}},
+                                {"endIndex": 17, "textRun": {"content": "Hello hello HELLO",
+                                    "style": {"bold": true, "italic": true}}},
+                                {"startIndex": 17, "endIndex": 18, "textRun": {"content": "\n"}}
+                            ]}}}]},
+                {"objectId": "slide2", "pageElements": [{"objectId": "box2",
+                    "shape": {"shapeType": "TEXT_BOX", "text": {"textElements": [
+                        {"endIndex": 6, "paragraphMarker": {}},
+                        {"endIndex": 6, "textRun": {"content": "hello\n"}}
+                    ]}}}]},
+                {"objectId": "slide3"}
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn requests(value: serde_json::Value) -> Vec<Request> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn plain_text(
+        presentation: &mut Presentation,
+        object_id: &str,
+        cell_location: Option<&TableCellLocation>,
+    ) -> String {
+        let content = text_mut(presentation, object_id, cell_location).unwrap();
+        content
+            .iter()
+            .flat_map(|c| c.text_elements.iter().flatten())
+            .filter_map(|e| match e.kind.as_ref()? {
+                TextElementKind::TextRun(run) => run.content.clone(),
+                TextElementKind::AutoText(auto_text) => auto_text.content.clone(),
+                TextElementKind::ParagraphMarker(_) => None,
+            })
+            .collect()
+    }
+
+    fn slide_ids(presentation: &Presentation) -> Vec<&str> {
+        presentation
+            .slides
+            .iter()
+            .flatten()
+            .map(|s| s.object_id.as_str())
+            .collect()
+    }
+
+    fn failing_source(error: EngineError) -> EngineError {
+        match error {
+            EngineError::InRequest { source, .. } => *source,
+            other => panic!("expected an error in a request, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_replace_all_text_honours_match_case_and_page_ids() {
+        let mut presentation = deck();
+        let mut engine = BatchUpdateEngine::new();
+        let replies = engine
+            .apply(
+                &mut presentation,
+                &requests(serde_json::json!([
+                    {"replaceAllText": {"containsText": {"text": "hello", "matchCase": true},
+                        "replaceText": "bye", "pageObjectIds": ["slide1"]}},
+                    {"replaceAllText": {"containsText": {"text": "HELLO"},
+                        "replaceText": "hi"}}
+                ])),
+            )
+            .unwrap();
+        let occurrences = |reply: &Response| match reply {
+            Response::ReplaceAllText(r) => r.occurrences_changed,
+            other => panic!("unexpected reply {:?}", other),
+        };
+        assert_eq!(occurrences(&replies[0]), Some(1));
+        assert_eq!(occurrences(&replies[1]), Some(3));
+        assert_eq!(plain_text(&mut presentation, "box1", None), "hi bye hi\n");
+        assert_eq!(plain_text(&mut presentation, "box2", None), "hi\n");
+
+        let error = engine
+            .apply(
+                &mut presentation,
+                &requests(serde_json::json!([
+                    {"replaceAllText": {"containsText": {"text": "hi"},
+                        "replaceText": "", "pageObjectIds": ["missing"]}}
+                ])),
+            )
+            .unwrap_err();
+        assert!(matches!(
+            failing_source(error),
+            EngineError::ObjectNotFound(id) if id == "missing"
+        ));
+    }
+
+    #[test]
+    fn test_create_slide_from_a_layout_with_placeholder_mappings() {
+        let mut presentation = deck();
+        let mut engine = BatchUpdateEngine::new();
+        let replies = engine
+            .apply(
+                &mut presentation,
+                &requests(serde_json::json!([
+                    {"createSlide": {"objectId": "slide_new", "insertionIndex": 1,
+                        "slideLayoutReference": {"predefinedLayout": "TITLE_AND_BODY"},
+                        "placeholderIdMappings": [
+                            {"layoutPlaceholder": {"type": "TITLE"}, "objectId": "new_title"}
+                        ]}},
+                    {"insertText": {"objectId": "new_title", "text": "Agenda"}},
+                    {"createSlide": {}}
+                ])),
+            )
+            .unwrap();
+        assert_eq!(
+            replies[0],
+            Response::CreateSlide(CreateSlideResponse {
+                object_id: Some("slide_new".to_string())
+            })
+        );
+        assert_eq!(
+            slide_ids(&presentation),
+            ["slide1", "slide_new", "slide2", "slide3", "local_2"]
+        );
+
+        let slides = presentation.slides.as_ref().unwrap();
+        let properties = slides[1].slide_properties.as_ref().unwrap();
+        assert_eq!(properties.layout_object_id.as_deref(), Some("layout_title"));
+        assert_eq!(properties.master_object_id.as_deref(), Some("master1"));
+        let placeholders: Vec<(&str, Option<&str>)> = slides[1]
+            .page_elements
+            .iter()
+            .flatten()
+            .map(|e| {
+                let PageElementKind::Shape(shape) = &e.element_kind else {
+                    panic!("expected a shape");
+                };
+                let placeholder = shape.placeholder.as_ref().unwrap();
+                (
+                    e.object_id.as_str(),
+                    placeholder.parent_object_id.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            placeholders,
+            [
+                ("new_title", Some("layout_title_t")),
+                ("local_1", Some("layout_title_b"))
+            ]
+        );
+        assert_eq!(plain_text(&mut presentation, "new_title", None), "Agenda\n");
+
+        // Without a reference the BLANK layout is used.
+        let slides = presentation.slides.as_ref().unwrap();
+        let properties = slides[4].slide_properties.as_ref().unwrap();
+        assert_eq!(properties.layout_object_id.as_deref(), Some("layout_blank"));
+        assert_eq!(slides[4].page_elements.as_ref().map(Vec::len), Some(0));
+
+        let error = engine
+            .apply(
+                &mut presentation,
+                &requests(serde_json::json!([
+                    {"createSlide": {
+                        "slideLayoutReference": {"layoutId": "layout_title"},
+                        "placeholderIdMappings": [
+                            {"layoutPlaceholder": {"type": "SUBTITLE"}, "objectId": "subtitle1"}
+                        ]}}
+                ])),
+            )
+            .unwrap_err();
+        assert!(matches!(
+            failing_source(error),
+            EngineError::InvalidRequest(_)
+        ));
+    }
+
+    #[test]
+    fn test_update_slides_position_moves_slides_in_order() {
+        let mut presentation = deck();
+        let mut engine = BatchUpdateEngine::new();
+        engine
+            .apply(
+                &mut presentation,
+                &requests(serde_json::json!([
+                    {"updateSlidesPosition": {"slideObjectIds": ["slide1", "slide2"],
+                        "insertionIndex": 3}}
+                ])),
+            )
+            .unwrap();
+        assert_eq!(slide_ids(&presentation), ["slide3", "slide1", "slide2"]);
+
+        engine
+            .apply(
+                &mut presentation,
+                &requests(serde_json::json!([
+                    {"updateSlidesPosition": {"slideObjectIds": ["slide2"], "insertionIndex": 0}}
+                ])),
+            )
+            .unwrap();
+        assert_eq!(slide_ids(&presentation), ["slide2", "slide3", "slide1"]);
+
+        for request in [
+            serde_json::json!({"slideObjectIds": ["slide1", "slide2"], "insertionIndex": 0}),
+            serde_json::json!({"slideObjectIds": ["slide2"], "insertionIndex": 4}),
+        ] {
+            let error = engine
+                .apply(
+                    &mut presentation,
+                    &requests(serde_json::json!([{ "updateSlidesPosition": request }])),
+                )
+                .unwrap_err();
+            assert!(matches!(
+                failing_source(error),
+                EngineError::InvalidRequest(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_create_table_and_insert_text_into_a_cell() {
+        let mut presentation = deck();
+        let mut engine = BatchUpdateEngine::new();
+        engine
+            .apply(
+                &mut presentation,
+                &requests(serde_json::json!([
+                    {"createTable": {"objectId": "table1",
+                        "elementProperties": {"pageObjectId": "slide3"}, "rows": 2, "columns": 3}},
+                    {"insertText": {"objectId": "table1",
+                        "cellLocation": {"rowIndex": 1, "columnIndex": 2}, "text": "Cell"}}
+                ])),
+            )
+            .unwrap();
+        let cell = |row, column| TableCellLocation {
+            row_index: Some(row),
+            column_index: Some(column),
+            extra: Default::default(),
+        };
+        assert_eq!(
+            plain_text(&mut presentation, "table1", Some(&cell(1, 2))),
+            "Cell\n"
+        );
+        assert_eq!(
+            plain_text(&mut presentation, "table1", Some(&cell(0, 0))),
+            ""
+        );
+
+        for request in [
+            serde_json::json!({"objectId": "table1", "text": "x"}),
+            serde_json::json!({"objectId": "table1", "text": "x",
+                "cellLocation": {"rowIndex": 2, "columnIndex": 0}}),
+            serde_json::json!({"objectId": "box1", "text": "x",
+                "cellLocation": {"rowIndex": 0, "columnIndex": 0}}),
+        ] {
+            let error = engine
+                .apply(
+                    &mut presentation,
+                    &requests(serde_json::json!([{ "insertText": request }])),
+                )
+                .unwrap_err();
+            assert!(matches!(
+                failing_source(error),
+                EngineError::InvalidTarget { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn test_field_masks_reset_fields_unset_in_the_request() {
+        let mut presentation = deck();
+        BatchUpdateEngine::new()
+            .apply(
+                &mut presentation,
+                &requests(serde_json::json!([
+                    {"updateShapeProperties": {"objectId": "box1",
+                        "shapeProperties": {"contentAlignment": "BOTTOM"},
+                        "fields": "contentAlignment,outline.weight"}},
+                    {"updateTextStyle": {"objectId": "box1", "style": {"underline": true},
+                        "fields": "underline,italic",
+                        "textRange": {"type": "FIXED_RANGE", "startIndex": 0, "endIndex": 5}}}
+                ])),
+            )
+            .unwrap();
+
+        let element = find_element_mut(&mut presentation, "box1").unwrap();
+        let PageElementKind::Shape(shape) = &element.element_kind else {
+            panic!("expected a shape");
+        };
+        assert_eq!(
+            serde_json::to_value(&shape.shape_properties).unwrap(),
+            serde_json::json!({"contentAlignment": "BOTTOM", "outline": {"dashStyle": "DASH"}})
+        );
+        let styles: Vec<(String, serde_json::Value)> = shape
+            .text
+            .as_ref()
+            .unwrap()
+            .text_elements
+            .iter()
+            .flatten()
+            .filter_map(|e| match e.kind.as_ref()? {
+                TextElementKind::TextRun(run) => Some((
+                    run.content.clone().unwrap(),
+                    serde_json::to_value(&run.style).unwrap(),
+                )),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            styles,
+            [
+                (
+                    "Hello".to_string(),
+                    serde_json::json!({"bold": true, "underline": true})
+                ),
+                (
+                    " hello HELLO".to_string(),
+                    serde_json::json!({"bold": true, "italic": true})
+                ),
+                ("\n".to_string(), serde_json::Value::Null),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_relative_and_absolute_transforms() {
+        let mut presentation = deck();
+        let mut engine = BatchUpdateEngine::new();
+        let mut transform_after = |request: serde_json::Value| {
+            engine
+                .apply(
+                    &mut presentation,
+                    &requests(serde_json::json!([{ "updatePageElementTransform": request }])),
+                )
+                .unwrap();
+            let element = find_element_mut(&mut presentation, "box1").unwrap();
+            let t = element.transform.clone().unwrap();
+            (
+                t.scale_x.unwrap(),
+                t.scale_y.unwrap(),
+                t.translate_x.unwrap_or(0.0),
+                t.translate_y.unwrap_or(0.0),
+            )
+        };
+
+        // Relative transforms are applied after the current one.
+        assert_eq!(
+            transform_after(
+                serde_json::json!({"objectId": "box1", "applyMode": "RELATIVE",
+                "transform": {"scaleX": 3, "scaleY": 1, "translateX": 10,
+                    "translateY": -20, "unit": "EMU"}})
+            ),
+            (6.0, 2.0, 310.0, 180.0)
+        );
+        assert_eq!(
+            transform_after(
+                serde_json::json!({"objectId": "box1", "applyMode": "RELATIVE",
+                "transform": {"scaleX": 1, "scaleY": 1, "translateX": 1, "unit": "PT"}})
+            ),
+            (6.0, 2.0, 13010.0, 180.0)
+        );
+        // Absolute transforms replace it.
+        assert_eq!(
+            transform_after(
+                serde_json::json!({"objectId": "box1", "applyMode": "ABSOLUTE",
+                "transform": {"scaleX": 1, "scaleY": 1, "translateX": 5, "unit": "EMU"}})
+            ),
+            (1.0, 1.0, 5.0, 0.0)
+        );
+    }
+}
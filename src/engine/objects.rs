@@ -0,0 +1,348 @@
+//! Lookup, insertion and removal of pages and page elements by object ID.
+
+use super::error::EngineError;
+use crate::models::elements::{PageElement, PageElementKind};
+use crate::models::page::Page;
+use crate::models::presentation::Presentation;
+use crate::models::table::TableCellLocation;
+use crate::models::text::TextContent;
+use std::collections::HashSet;
+
+/// The slides, layouts, masters and notes master of a presentation (not the notes pages).
+pub(crate) fn pages_mut(presentation: &mut Presentation) -> impl Iterator<Item = &mut Page> {
+    presentation
+        .slides
+        .iter_mut()
+        .flatten()
+        .chain(presentation.layouts.iter_mut().flatten())
+        .chain(presentation.masters.iter_mut().flatten())
+        .chain(presentation.notes_master.iter_mut())
+}
+
+fn pages(presentation: &Presentation) -> impl Iterator<Item = &Page> {
+    presentation
+        .slides
+        .iter()
+        .flatten()
+        .chain(presentation.layouts.iter().flatten())
+        .chain(presentation.masters.iter().flatten())
+        .chain(presentation.notes_master.iter())
+}
+
+/// Finds a page, including the notes pages of slides.
+pub(crate) fn find_page_mut<'a>(
+    presentation: &'a mut Presentation,
+    object_id: &str,
+) -> Option<&'a mut Page> {
+    for page in pages_mut(presentation) {
+        if page.object_id == object_id {
+            return Some(page);
+        }
+        if let Some(notes_page) = page
+            .slide_properties
+            .as_mut()
+            .and_then(|p| p.notes_page.as_deref_mut())
+        {
+            if notes_page.object_id == object_id {
+                return Some(notes_page);
+            }
+        }
+    }
+    None
+}
+
+/// Finds a page element on any page, including elements nested in groups.
+pub(crate) fn find_element_mut<'a>(
+    presentation: &'a mut Presentation,
+    object_id: &str,
+) -> Option<&'a mut PageElement> {
+    for page in pages_mut(presentation) {
+        if let Some(element) = find_in_page_mut(page, object_id) {
+            return Some(element);
+        }
+    }
+    None
+}
+
+fn find_in_page_mut<'a>(page: &'a mut Page, object_id: &str) -> Option<&'a mut PageElement> {
+    if let Some(element) = page
+        .page_elements
+        .as_mut()
+        .and_then(|elements| find_in_elements_mut(elements, object_id))
+    {
+        return Some(element);
+    }
+    let notes_page = page
+        .slide_properties
+        .as_mut()
+        .and_then(|p| p.notes_page.as_deref_mut())?;
+    find_in_page_mut(notes_page, object_id)
+}
+
+fn find_in_elements_mut<'a>(
+    elements: &'a mut [PageElement],
+    object_id: &str,
+) -> Option<&'a mut PageElement> {
+    for element in elements {
+        if element.object_id == object_id {
+            return Some(element);
+        }
+        if let PageElementKind::ElementGroup(group) = &mut element.element_kind {
+            if let Some(found) = find_in_elements_mut(&mut group.children, object_id) {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
+/// Finds the list holding a page element (a page's elements or a group's children) and
+/// the element's position in it.
+pub(crate) fn containing_list_mut<'a>(
+    presentation: &'a mut Presentation,
+    object_id: &str,
+) -> Option<(&'a mut Vec<PageElement>, usize)> {
+    for page in pages_mut(presentation) {
+        if let Some(found) = list_in_page_mut(page, object_id) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+fn list_in_page_mut<'a>(
+    page: &'a mut Page,
+    object_id: &str,
+) -> Option<(&'a mut Vec<PageElement>, usize)> {
+    if let Some(found) = page
+        .page_elements
+        .as_mut()
+        .and_then(|elements| list_in_elements_mut(elements, object_id))
+    {
+        return Some(found);
+    }
+    let notes_page = page
+        .slide_properties
+        .as_mut()
+        .and_then(|p| p.notes_page.as_deref_mut())?;
+    list_in_page_mut(notes_page, object_id)
+}
+
+fn list_in_elements_mut<'a>(
+    elements: &'a mut Vec<PageElement>,
+    object_id: &str,
+) -> Option<(&'a mut Vec<PageElement>, usize)> {
+    if let Some(position) = elements.iter().position(|e| e.object_id == object_id) {
+        return Some((elements, position));
+    }
+    for element in elements.iter_mut() {
+        if let PageElementKind::ElementGroup(group) = &mut element.element_kind {
+            if let Some(found) = list_in_elements_mut(&mut group.children, object_id) {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
+/// Removes a page element. A group left without children is removed as well.
+pub(crate) fn remove_element(
+    presentation: &mut Presentation,
+    object_id: &str,
+) -> Option<PageElement> {
+    for page in pages_mut(presentation) {
+        if let Some(removed) = remove_from_page(page, object_id) {
+            return Some(removed);
+        }
+    }
+    None
+}
+
+fn remove_from_page(page: &mut Page, object_id: &str) -> Option<PageElement> {
+    if let Some(removed) = page
+        .page_elements
+        .as_mut()
+        .and_then(|elements| remove_from_elements(elements, object_id))
+    {
+        return Some(removed);
+    }
+    let notes_page = page
+        .slide_properties
+        .as_mut()
+        .and_then(|p| p.notes_page.as_deref_mut())?;
+    remove_from_page(notes_page, object_id)
+}
+
+fn remove_from_elements(elements: &mut Vec<PageElement>, object_id: &str) -> Option<PageElement> {
+    if let Some(position) = elements.iter().position(|e| e.object_id == object_id) {
+        return Some(elements.remove(position));
+    }
+    for position in 0..elements.len() {
+        if let PageElementKind::ElementGroup(group) = &mut elements[position].element_kind {
+            if let Some(removed) = remove_from_elements(&mut group.children, object_id) {
+                if group.children.is_empty() {
+                    elements.remove(position);
+                }
+                return Some(removed);
+            }
+        }
+    }
+    None
+}
+
+/// Every object ID in use: pages, notes pages and page elements.
+pub(crate) fn object_ids(presentation: &Presentation) -> HashSet<String> {
+    let mut ids = HashSet::new();
+    for page in pages(presentation) {
+        collect_page_ids(page, &mut ids);
+    }
+    ids
+}
+
+/// Adds the ID of `page`, its page elements and its notes page to `ids`.
+pub(crate) fn collect_page_ids(page: &Page, ids: &mut HashSet<String>) {
+    ids.insert(page.object_id.clone());
+    collect_element_ids(page.page_elements.as_deref().unwrap_or_default(), ids);
+    if let Some(notes_page) = page
+        .slide_properties
+        .as_ref()
+        .and_then(|p| p.notes_page.as_deref())
+    {
+        collect_page_ids(notes_page, ids);
+    }
+}
+
+fn collect_element_ids(elements: &[PageElement], ids: &mut HashSet<String>) {
+    for element in elements {
+        ids.insert(element.object_id.clone());
+        if let PageElementKind::ElementGroup(group) = &element.element_kind {
+            collect_element_ids(&group.children, ids);
+        }
+    }
+}
+
+/// The text of a shape, or of a table cell when `cell_location` is given.
+pub(crate) fn text_mut<'a>(
+    presentation: &'a mut Presentation,
+    object_id: &str,
+    cell_location: Option<&TableCellLocation>,
+) -> Result<&'a mut Option<TextContent>, EngineError> {
+    let element = find_element_mut(presentation, object_id)
+        .ok_or_else(|| EngineError::ObjectNotFound(object_id.to_string()))?;
+    let invalid_target = |reason: &str| EngineError::InvalidTarget {
+        object_id: object_id.to_string(),
+        reason: reason.to_string(),
+    };
+    match (&mut element.element_kind, cell_location) {
+        (PageElementKind::Shape(shape), None) => Ok(&mut shape.text),
+        (PageElementKind::Shape(_), Some(_)) => Err(invalid_target(
+            "a cell location can only be used with a table",
+        )),
+        (PageElementKind::Table(_), None) => Err(invalid_target("a cell location is required")),
+        (PageElementKind::Table(table), Some(location)) => {
+            let row = location.row_index.unwrap_or(0);
+            let column = location.column_index.unwrap_or(0);
+            usize::try_from(row)
+                .ok()
+                .zip(usize::try_from(column).ok())
+                .and_then(|(row, column)| {
+                    table
+                        .table_rows
+                        .as_mut()?
+                        .get_mut(row)?
+                        .table_cells
+                        .as_mut()?
+                        .get_mut(column)
+                })
+                .map(|cell| &mut cell.text)
+                .ok_or_else(|| {
+                    invalid_target(&format!("the table has no cell at ({}, {})", row, column))
+                })
+        }
+        _ => Err(invalid_target("the element does not contain text")),
+    }
+}
+
+/// Calls `f` with the text of every shape and table cell in `elements`, including groups.
+pub(crate) fn for_each_text_mut(
+    elements: &mut [PageElement],
+    f: &mut impl FnMut(&mut Option<TextContent>),
+) {
+    for element in elements {
+        match &mut element.element_kind {
+            PageElementKind::Shape(shape) => f(&mut shape.text),
+            PageElementKind::Table(table) => table
+                .table_rows
+                .iter_mut()
+                .flatten()
+                .flat_map(|row| row.table_cells.iter_mut().flatten())
+                .for_each(|cell| f(&mut cell.text)),
+            PageElementKind::ElementGroup(group) => for_each_text_mut(&mut group.children, f),
+            _ => {}
+        }
+    }
+}
+
+/// Rewrites object IDs of a page element tree according to `new_id`.
+pub(crate) fn reassign_element_ids(
+    elements: &mut [PageElement],
+    new_id: &mut impl FnMut(&str) -> String,
+) {
+    for element in elements {
+        element.object_id = new_id(&element.object_id);
+        if let PageElementKind::ElementGroup(group) = &mut element.element_kind {
+            reassign_element_ids(&mut group.children, new_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn presentation() -> Presentation {
+        serde_json::from_value(serde_json::json!({
+            "presentationId": "deck",
+            "slides": [{
+                "objectId": "slide1",
+                "pageElements": [
+                    {"objectId": "group1", "elementGroup": {"children": [
+                        {"objectId": "child1", "shape": {"shapeType": "RECTANGLE"}}
+                    ]}},
+                    {"objectId": "box1", "shape": {"shapeType": "TEXT_BOX"}}
+                ],
+                "slideProperties": {"notesPage": {
+                    "objectId": "notes1",
+                    "pageElements": [{"objectId": "speaker1", "shape": {"shapeType": "TEXT_BOX"}}]
+                }}
+            }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_lookup_finds_grouped_elements_and_notes_pages() {
+        let mut presentation = presentation();
+        let mut ids: Vec<String> = object_ids(&presentation).into_iter().collect();
+        ids.sort();
+        assert_eq!(
+            ids,
+            ["box1", "child1", "group1", "notes1", "slide1", "speaker1"]
+        );
+        assert!(find_element_mut(&mut presentation, "child1").is_some());
+        assert!(find_element_mut(&mut presentation, "speaker1").is_some());
+        assert!(find_page_mut(&mut presentation, "notes1").is_some());
+        assert!(find_element_mut(&mut presentation, "notes1").is_none());
+    }
+
+    #[test]
+    fn test_removing_the_last_child_removes_the_group() {
+        let mut presentation = presentation();
+        let removed = remove_element(&mut presentation, "child1").unwrap();
+        assert_eq!(removed.object_id, "child1");
+        let ids = object_ids(&presentation);
+        assert!(!ids.contains("group1"));
+        assert!(ids.contains("box1"));
+        assert!(remove_element(&mut presentation, "child1").is_none());
+    }
+}
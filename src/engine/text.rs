@@ -0,0 +1,516 @@
+//! Character-level editing of `TextContent`.
+//!
+//! Text is exploded into styled characters, edited, and rebuilt into `TextElement`s, so
+//! `start_index`/`end_index` always match the content. Indices are UTF-16 code units,
+//! like the API's. Each paragraph's marker travels with the `\n` that ends it.
+
+use super::error::EngineError;
+use super::fields::merge_fields;
+use crate::models::list::List;
+use crate::models::properties::{ParagraphStyle, TextStyle};
+use crate::models::requests::{Range, RangeType};
+use crate::models::text::TextContent;
+use crate::models::text_element::{
+    AutoText, ParagraphMarker, TextElement, TextElementKind, TextRun,
+};
+use indexmap::IndexMap;
+
+#[derive(Debug, Clone)]
+struct StyledChar {
+    ch: char,
+    style: Option<TextStyle>,
+    /// Index into `EditableText::auto_texts` if the character belongs to an auto text.
+    auto_text: Option<usize>,
+    /// The marker of the paragraph this character ends (`\n` only).
+    marker: Option<ParagraphMarker>,
+}
+
+/// An editable view of a shape's or table cell's text.
+#[derive(Debug, Clone)]
+pub(crate) struct EditableText {
+    chars: Vec<StyledChar>,
+    auto_texts: Vec<AutoText>,
+    lists: Option<IndexMap<String, List>>,
+}
+
+impl EditableText {
+    pub(crate) fn from_content(content: Option<&TextContent>) -> Self {
+        let mut text = Self {
+            chars: Vec::new(),
+            auto_texts: Vec::new(),
+            lists: content.and_then(|c| c.lists.clone()),
+        };
+        let mut marker = default_marker();
+        for element in content
+            .and_then(|c| c.text_elements.as_ref())
+            .into_iter()
+            .flatten()
+        {
+            match &element.kind {
+                Some(TextElementKind::ParagraphMarker(m)) => marker = m.clone(),
+                Some(TextElementKind::TextRun(run)) => {
+                    for ch in run.content.as_deref().unwrap_or_default().chars() {
+                        text.chars.push(StyledChar {
+                            ch,
+                            style: run.style.clone(),
+                            auto_text: None,
+                            marker: (ch == '\n').then(|| marker.clone()),
+                        });
+                    }
+                }
+                Some(TextElementKind::AutoText(auto_text)) => {
+                    let index = text.auto_texts.len();
+                    text.auto_texts.push(auto_text.clone());
+                    for ch in auto_text.content.as_deref().unwrap_or_default().chars() {
+                        text.chars.push(StyledChar {
+                            ch,
+                            style: auto_text.style.clone(),
+                            auto_text: Some(index),
+                            marker: None,
+                        });
+                    }
+                }
+                None => {}
+            }
+        }
+        text
+    }
+
+    /// Rebuilds the `TextContent`. Empty text without lists becomes `None`, like
+    /// shapes without text in API responses.
+    pub(crate) fn into_content(self) -> Option<TextContent> {
+        if self.chars.is_empty() {
+            return self.lists.map(|lists| TextContent {
+                text_elements: None,
+                lists: Some(lists),
+                extra: Default::default(),
+            });
+        }
+
+        let mut elements = Vec::new();
+        let mut index = 0u32;
+        let mut paragraph_start = 0usize;
+        while paragraph_start < self.chars.len() {
+            let paragraph_end = self.chars[paragraph_start..]
+                .iter()
+                .position(|c| c.ch == '\n')
+                .map_or(self.chars.len(), |p| paragraph_start + p + 1);
+            let paragraph = &self.chars[paragraph_start..paragraph_end];
+            let paragraph_len = utf16_len(paragraph);
+            let marker = paragraph
+                .last()
+                .and_then(|c| c.marker.clone())
+                .unwrap_or_else(default_marker);
+            elements.push(TextElement {
+                start_index: non_zero(index),
+                end_index: Some(index + paragraph_len),
+                kind: Some(TextElementKind::ParagraphMarker(marker)),
+                extra: Default::default(),
+            });
+
+            let mut run_start = 0;
+            while run_start < paragraph.len() {
+                let first = &paragraph[run_start];
+                let run_len = paragraph[run_start..]
+                    .iter()
+                    .take_while(|c| c.style == first.style && c.auto_text == first.auto_text)
+                    .count();
+                let run = &paragraph[run_start..run_start + run_len];
+                let content: String = run.iter().map(|c| c.ch).collect();
+                let end = index + utf16_len(run);
+                let kind = match first.auto_text {
+                    Some(auto_index) => TextElementKind::AutoText(AutoText {
+                        content: Some(content),
+                        style: first.style.clone(),
+                        ..self.auto_texts[auto_index].clone()
+                    }),
+                    None => TextElementKind::TextRun(TextRun {
+                        content: Some(content),
+                        style: first.style.clone(),
+                        extra: Default::default(),
+                    }),
+                };
+                elements.push(TextElement {
+                    start_index: non_zero(index),
+                    end_index: Some(end),
+                    kind: Some(kind),
+                    extra: Default::default(),
+                });
+                index = end;
+                run_start += run_len;
+            }
+            paragraph_start = paragraph_end;
+        }
+
+        Some(TextContent {
+            text_elements: Some(elements),
+            lists: self.lists,
+            extra: Default::default(),
+        })
+    }
+
+    /// The length of the text in UTF-16 code units.
+    pub(crate) fn len(&self) -> u32 {
+        utf16_len(&self.chars)
+    }
+
+    /// Resolves an optional request range to `[start, end)`; `None` selects all text.
+    pub(crate) fn resolve_range(&self, range: Option<&Range>) -> Result<(u32, u32), EngineError> {
+        let len = self.len();
+        let (start, end) = match range {
+            None => (0, len),
+            Some(range) => match range.range_type {
+                RangeType::All => (0, len),
+                RangeType::FromStartIndex => {
+                    (required_index(range.start_index, "startIndex")?, len)
+                }
+                RangeType::FixedRange => (
+                    required_index(range.start_index, "startIndex")?,
+                    required_index(range.end_index, "endIndex")?,
+                ),
+                RangeType::RangeTypeUnspecified => {
+                    return Err(EngineError::InvalidRange(
+                        "The range type must be specified.".to_string(),
+                    ))
+                }
+            },
+        };
+        if start > end || end > len {
+            return Err(EngineError::InvalidRange(format!(
+                "The range [{}, {}) is outside the text of length {}.",
+                start, end, len
+            )));
+        }
+        Ok((start, end))
+    }
+
+    /// Inserts `text` at a UTF-16 `index`. New characters take the style of the
+    /// preceding character in the same paragraph (or of the following one).
+    pub(crate) fn insert(&mut self, index: u32, text: &str) -> Result<(), EngineError> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        if self.chars.is_empty() {
+            if index != 0 {
+                return Err(EngineError::InvalidRange(format!(
+                    "The insertion index ({}) must be 0 for an empty text.",
+                    index
+                )));
+            }
+            // Text always ends with a paragraph break.
+            self.chars.push(StyledChar {
+                ch: '\n',
+                style: None,
+                auto_text: None,
+                marker: Some(default_marker()),
+            });
+        }
+        let len = self.len();
+        let ends_with_break = self.chars.last().is_some_and(|c| c.ch == '\n');
+        if index > len || (index == len && ends_with_break) {
+            return Err(EngineError::InvalidRange(format!(
+                "The insertion index ({}) must be inside the bounds of an existing paragraph.",
+                index
+            )));
+        }
+        let position = self.char_position(index)?;
+        if self.chars.get(position).is_some_and(|c| {
+            c.auto_text.is_some()
+                && position > 0
+                && self.chars[position - 1].auto_text == c.auto_text
+        }) {
+            return Err(EngineError::InvalidRange(format!(
+                "The insertion index ({}) is inside an auto text.",
+                index
+            )));
+        }
+
+        let style = position
+            .checked_sub(1)
+            .map(|p| &self.chars[p])
+            .filter(|c| c.ch != '\n' && c.auto_text.is_none())
+            .or_else(|| self.chars.get(position).filter(|c| c.auto_text.is_none()))
+            .and_then(|c| c.style.clone());
+        let marker = self.paragraph_marker_at(position);
+        let new_chars: Vec<StyledChar> = text
+            .chars()
+            .map(|ch| StyledChar {
+                ch,
+                style: style.clone(),
+                auto_text: None,
+                marker: (ch == '\n').then(|| marker.clone()),
+            })
+            .collect();
+        self.chars.splice(position..position, new_chars);
+        Ok(())
+    }
+
+    /// Deletes the UTF-16 range `[start, end)`. The final paragraph break is kept, and
+    /// auto texts are only deleted as a whole.
+    pub(crate) fn delete(&mut self, start: u32, end: u32) -> Result<(), EngineError> {
+        let mut from = self.char_position(start)?;
+        let mut to = self.char_position(end)?;
+        if to == self.chars.len() && from < to && self.chars[to - 1].ch == '\n' && from > 0 {
+            to -= 1;
+        }
+        if from >= to {
+            return Ok(());
+        }
+        // Widen the range to whole auto texts.
+        while from > 0
+            && self.chars[from].auto_text.is_some()
+            && self.chars[from - 1].auto_text == self.chars[from].auto_text
+        {
+            from -= 1;
+        }
+        while to > 0
+            && to < self.chars.len()
+            && self.chars[to].auto_text.is_some()
+            && self.chars[to - 1].auto_text == self.chars[to].auto_text
+        {
+            to += 1;
+        }
+        self.chars.drain(from..to);
+        Ok(())
+    }
+
+    /// Applies a text style with a field mask to the characters in `[start, end)`.
+    pub(crate) fn update_text_style(
+        &mut self,
+        start: u32,
+        end: u32,
+        style: &TextStyle,
+        fields: &str,
+    ) -> Result<(), EngineError> {
+        let from = self.char_position(start)?;
+        let to = self.char_position(end)?;
+        let mut merged: Vec<(Option<TextStyle>, Option<TextStyle>)> = Vec::new();
+        for c in &mut self.chars[from..to] {
+            if let Some((_, new_style)) = merged.iter().find(|(old, _)| *old == c.style) {
+                c.style = new_style.clone();
+                continue;
+            }
+            let new_style = merge_fields(&c.style.clone().unwrap_or_default(), style, fields)?;
+            let new_style = (new_style != TextStyle::default()).then_some(new_style);
+            merged.push((c.style.clone(), new_style.clone()));
+            c.style = new_style;
+        }
+        Ok(())
+    }
+
+    /// Applies a paragraph style with a field mask to every paragraph overlapping `[start, end)`.
+    pub(crate) fn update_paragraph_style(
+        &mut self,
+        start: u32,
+        end: u32,
+        style: &ParagraphStyle,
+        fields: &str,
+    ) -> Result<(), EngineError> {
+        for position in self.paragraph_breaks(start, end)? {
+            let marker = self.chars[position]
+                .marker
+                .get_or_insert_with(default_marker);
+            let merged = merge_fields(&marker.style.clone().unwrap_or_default(), style, fields)?;
+            marker.style = (merged != ParagraphStyle::default()).then_some(merged);
+        }
+        Ok(())
+    }
+
+    /// Replaces every occurrence of `find` with `replace`. Returns the number of occurrences.
+    pub(crate) fn replace_all(&mut self, find: &str, replace: &str, match_case: bool) -> usize {
+        let pattern: Vec<char> = find.chars().collect();
+        if pattern.is_empty() || pattern.len() > self.chars.len() {
+            return 0;
+        }
+        let mut result = Vec::with_capacity(self.chars.len());
+        let mut occurrences = 0;
+        let mut i = 0;
+        while i < self.chars.len() {
+            let candidate = self.chars.get(i..i + pattern.len());
+            let matches = candidate.is_some_and(|candidate| {
+                candidate.iter().zip(&pattern).all(|(c, p)| {
+                    c.auto_text.is_none()
+                        && if match_case {
+                            c.ch == *p
+                        } else {
+                            c.ch.to_lowercase().eq(p.to_lowercase())
+                        }
+                })
+            });
+            if !matches {
+                result.push(self.chars[i].clone());
+                i += 1;
+                continue;
+            }
+            let style = self.chars[i].style.clone();
+            let marker = self.paragraph_marker_at(i);
+            result.extend(replace.chars().map(|ch| StyledChar {
+                ch,
+                style: style.clone(),
+                auto_text: None,
+                marker: (ch == '\n').then(|| marker.clone()),
+            }));
+            occurrences += 1;
+            i += pattern.len();
+        }
+        self.chars = result;
+        occurrences
+    }
+
+    /// Positions of the `\n`s ending the paragraphs that overlap `[start, end)`.
+    fn paragraph_breaks(&self, start: u32, end: u32) -> Result<Vec<usize>, EngineError> {
+        let from = self.char_position(start)?;
+        let to = self.char_position(end)?;
+        let mut breaks = Vec::new();
+        let mut paragraph_start = 0;
+        for (position, c) in self.chars.iter().enumerate() {
+            if c.ch != '\n' {
+                continue;
+            }
+            let paragraph_end = position + 1;
+            let overlaps = if from == to {
+                paragraph_start <= from && from < paragraph_end
+            } else {
+                paragraph_start < to && from < paragraph_end
+            };
+            if overlaps {
+                breaks.push(position);
+            }
+            paragraph_start = paragraph_end;
+        }
+        Ok(breaks)
+    }
+
+    /// The marker of the paragraph containing the character at `position`.
+    fn paragraph_marker_at(&self, position: usize) -> ParagraphMarker {
+        self.chars[position..]
+            .iter()
+            .find(|c| c.ch == '\n')
+            .and_then(|c| c.marker.clone())
+            .unwrap_or_else(default_marker)
+    }
+
+    /// Maps a UTF-16 index to a character position.
+    fn char_position(&self, index: u32) -> Result<usize, EngineError> {
+        let mut units = 0u32;
+        for (position, c) in self.chars.iter().enumerate() {
+            if units == index {
+                return Ok(position);
+            }
+            if units > index {
+                break;
+            }
+            units += c.ch.len_utf16() as u32;
+        }
+        if units == index {
+            return Ok(self.chars.len());
+        }
+        Err(EngineError::InvalidRange(format!(
+            "The index ({}) is out of bounds or splits a character.",
+            index
+        )))
+    }
+}
+
+fn default_marker() -> ParagraphMarker {
+    ParagraphMarker {
+        style: None,
+        bullet: None,
+        extra: Default::default(),
+    }
+}
+
+fn utf16_len(chars: &[StyledChar]) -> u32 {
+    chars.iter().map(|c| c.ch.len_utf16() as u32).sum()
+}
+
+/// The API omits indices that are zero.
+fn non_zero(index: u32) -> Option<u32> {
+    (index != 0).then_some(index)
+}
+
+fn required_index(index: Option<u32>, name: &str) -> Result<u32, EngineError> {
+    index.ok_or_else(|| EngineError::InvalidRange(format!("The range requires '{}'.", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn editable(elements: serde_json::Value) -> EditableText {
+        let content: TextContent =
+            serde_json::from_value(serde_json::json!({ "textElements": elements })).unwrap();
+        EditableText::from_content(Some(&content))
+    }
+
+    fn hello() -> EditableText {
+        editable(serde_json::json!([
+            {"endIndex": 6, "paragraphMarker": {}},
+            {"endIndex": 6, "textRun": {"content": "Hello\n"}}
+        ]))
+    }
+
+    fn plain(text: &EditableText) -> String {
+        text.chars.iter().map(|c| c.ch).collect()
+    }
+
+    #[test]
+    fn test_delete_from_the_end_of_the_text_is_a_no_op() {
+        let mut text = hello();
+        let range = Range {
+            range_type: RangeType::FromStartIndex,
+            start_index: Some(6),
+            end_index: None,
+        };
+        let (start, end) = text.resolve_range(Some(&range)).unwrap();
+        text.delete(start, end).unwrap();
+        assert_eq!(plain(&text), "Hello\n");
+
+        text.delete(2, 2).unwrap();
+        assert_eq!(plain(&text), "Hello\n");
+    }
+
+    #[test]
+    fn test_delete_keeps_the_final_paragraph_break() {
+        let mut text = hello();
+        text.delete(3, 6).unwrap();
+        assert_eq!(plain(&text), "Hel\n");
+
+        // Deleting everything clears the text.
+        let mut text = hello();
+        text.delete(0, 6).unwrap();
+        assert!(text.into_content().is_none());
+    }
+
+    #[test]
+    fn test_delete_widens_to_whole_auto_texts() {
+        let mut text = editable(serde_json::json!([
+            {"endIndex": 8, "paragraphMarker": {}},
+            {"endIndex": 5, "textRun": {"content": "Page "}},
+            {"startIndex": 5, "endIndex": 7, "autoText": {"type": "SLIDE_NUMBER", "content": "12"}},
+            {"startIndex": 7, "endIndex": 8, "textRun": {"content": "\n"}}
+        ]));
+        text.delete(6, 7).unwrap();
+        assert_eq!(plain(&text), "Page \n");
+    }
+
+    #[test]
+    fn test_delete_rejects_indices_splitting_a_character() {
+        let mut text = editable(serde_json::json!([
+            {"endIndex": 3, "paragraphMarker": {}},
+            {"endIndex": 3, "textRun": {"content": "🌍\n"}}
+        ]));
+        assert!(matches!(
+            text.delete(1, 2),
+            Err(EngineError::InvalidRange(_))
+        ));
+        assert!(matches!(
+            text.resolve_range(Some(&Range {
+                range_type: RangeType::FixedRange,
+                start_index: Some(0),
+                end_index: Some(4),
+            })),
+            Err(EngineError::InvalidRange(_))
+        ));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::parse::ParseProblem;
 use thiserror::Error;
 
 /// Represents errors that can occur when interacting with the Google Slides API client.
@@ -11,6 +12,11 @@ pub enum SlidesApiError {
     #[error("Failed to deserialize JSON response: {0}")]
     JsonDeserialization(#[from] serde_json::Error),
 
+    /// A presentation JSON document does not match the models. Reports the JSON path of
+    /// the offending value, the expected type and a snippet of the value.
+    #[error("Failed to parse presentation {0}")]
+    PresentationParse(Box<ParseProblem>),
+
     /// An error reported by the Google Slides API itself (e.g., 4xx or 5xx status code).
     #[error("API returned an error: Status {status}, Message: {message}")]
     ApiError {
@@ -40,10 +46,32 @@ pub enum SlidesApiError {
     #[error("I/O error: {0}")]
     IoError(#[from] std::io::Error), // Added this variant
 
+    /// A request kept failing after being retried. Wraps the error of the last attempt.
+    #[error("Request failed after {attempts} attempts (last status: {}): {source}", last_status.map_or_else(|| "none".to_string(), |s| s.to_string()))]
+    RetryFailed {
+        /// Total number of attempts made, including the first one.
+        attempts: u32,
+        /// HTTP status of the last response, if any response was received.
+        last_status: Option<reqwest::StatusCode>,
+        /// The error returned by the last attempt.
+        source: Box<SlidesApiError>,
+    },
+
     /// An unexpected or unknown error occurred.
     #[error("Unknown error: {0}")]
     Unknown(String),
 }
 
+impl SlidesApiError {
+    /// Returns the HTTP status reported by the API, looking through `RetryFailed`.
+    pub fn status(&self) -> Option<reqwest::StatusCode> {
+        match self {
+            SlidesApiError::ApiError { status, .. } => Some(*status),
+            SlidesApiError::RetryFailed { last_status, .. } => *last_status,
+            _ => None,
+        }
+    }
+}
+
 /// A type alias for `Result<T, SlidesApiError>` for convenience within the crate.
 pub type Result<T> = std::result::Result<T, SlidesApiError>;
@@ -0,0 +1,299 @@
+//! Typed field masks for partial responses (the `fields` query parameter).
+//!
+//! A mask only selects paths that exist on the crate's models, and always selects the
+//! fields the models need to deserialize (`presentationId`, page and element `objectId`s,
+//! and the element kind).
+//!
+//! ```
+//! use gslides_tools::field_mask::{FieldMask, PageField, PresentationField};
+//!
+//! let mask = FieldMask::new()
+//!     .field(PresentationField::Title)
+//!     .field(PresentationField::Slides(vec![PageField::ObjectId]));
+//! assert_eq!(mask.to_string(), "presentationId,title,slides(objectId)");
+//! ```
+
+use std::fmt;
+
+/// A field of a model that can be selected in a `FieldMask`.
+pub trait FieldPath {
+    /// The JSON name of the field.
+    fn name(&self) -> &'static str;
+
+    /// The selected sub-fields, if only part of the field is requested.
+    /// An empty list selects the whole field.
+    fn sub_fields(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Fields that must always be selected for the model to deserialize.
+    fn required() -> Vec<Self>
+    where
+        Self: Sized;
+
+    /// Renders the field and its sub-selection, e.g. `slides(objectId,pageType)`.
+    fn to_path(&self) -> String {
+        let sub_fields = self.sub_fields();
+        if sub_fields.is_empty() {
+            self.name().to_string()
+        } else {
+            format!("{}({})", self.name(), sub_fields.join(","))
+        }
+    }
+}
+
+/// Renders a sub-selection of `fields`, adding the required fields first.
+/// An empty selection stays empty (the whole object is returned).
+fn render_selection<F: FieldPath>(fields: &[F]) -> Vec<String> {
+    if fields.is_empty() {
+        return Vec::new();
+    }
+    let mut paths: Vec<String> = Vec::new();
+    for path in F::required()
+        .iter()
+        .map(FieldPath::to_path)
+        .chain(fields.iter().map(FieldPath::to_path))
+    {
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// A field of `Presentation`.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations
+#[derive(Debug, Clone, PartialEq)]
+pub enum PresentationField {
+    PresentationId,
+    PageSize,
+    Title,
+    Locale,
+    RevisionId,
+    /// The slides, restricted to the given page fields (all fields if empty).
+    Slides(Vec<PageField>),
+    /// The masters, restricted to the given page fields (all fields if empty).
+    Masters(Vec<PageField>),
+    /// The layouts, restricted to the given page fields (all fields if empty).
+    Layouts(Vec<PageField>),
+    /// The notes master, restricted to the given page fields (all fields if empty).
+    NotesMaster(Vec<PageField>),
+}
+
+impl FieldPath for PresentationField {
+    fn name(&self) -> &'static str {
+        match self {
+            PresentationField::PresentationId => "presentationId",
+            PresentationField::PageSize => "pageSize",
+            PresentationField::Title => "title",
+            PresentationField::Locale => "locale",
+            PresentationField::RevisionId => "revisionId",
+            PresentationField::Slides(_) => "slides",
+            PresentationField::Masters(_) => "masters",
+            PresentationField::Layouts(_) => "layouts",
+            PresentationField::NotesMaster(_) => "notesMaster",
+        }
+    }
+
+    fn sub_fields(&self) -> Vec<String> {
+        match self {
+            PresentationField::Slides(fields)
+            | PresentationField::Masters(fields)
+            | PresentationField::Layouts(fields)
+            | PresentationField::NotesMaster(fields) => render_selection(fields),
+            _ => Vec::new(),
+        }
+    }
+
+    fn required() -> Vec<Self> {
+        vec![PresentationField::PresentationId]
+    }
+}
+
+/// A field of `Page`.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations.pages
+#[derive(Debug, Clone, PartialEq)]
+pub enum PageField {
+    ObjectId,
+    PageType,
+    RevisionId,
+    PageProperties,
+    SlideProperties,
+    LayoutProperties,
+    NotesProperties,
+    MasterProperties,
+    /// The page elements, restricted to the given element fields (all fields if empty).
+    PageElements(Vec<PageElementField>),
+}
+
+impl FieldPath for PageField {
+    fn name(&self) -> &'static str {
+        match self {
+            PageField::ObjectId => "objectId",
+            PageField::PageType => "pageType",
+            PageField::RevisionId => "revisionId",
+            PageField::PageProperties => "pageProperties",
+            PageField::SlideProperties => "slideProperties",
+            PageField::LayoutProperties => "layoutProperties",
+            PageField::NotesProperties => "notesProperties",
+            PageField::MasterProperties => "masterProperties",
+            PageField::PageElements(_) => "pageElements",
+        }
+    }
+
+    fn sub_fields(&self) -> Vec<String> {
+        match self {
+            PageField::PageElements(fields) => {
+                // Elements without a kind would not deserialize, so a selection that
+                // names no kind gets all of them.
+                let mut fields = fields.clone();
+                if !fields.is_empty() && !fields.iter().any(|f| PageElementField::KINDS.contains(f))
+                {
+                    fields.extend(PageElementField::KINDS);
+                }
+                render_selection(&fields)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn required() -> Vec<Self> {
+        vec![PageField::ObjectId]
+    }
+}
+
+/// A field of `PageElement`. The kind variants (`Shape`, `Image`, ...) select the whole kind object.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations.pages#PageElement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PageElementField {
+    ObjectId,
+    Size,
+    Transform,
+    Title,
+    Description,
+    ElementGroup,
+    Shape,
+    Image,
+    Video,
+    Line,
+    Table,
+    WordArt,
+    SheetsChart,
+    SpeakerSpotlight,
+}
+
+impl PageElementField {
+    /// All element kind fields. One of them must be present for a `PageElement` to deserialize.
+    pub const KINDS: [PageElementField; 9] = [
+        PageElementField::ElementGroup,
+        PageElementField::Shape,
+        PageElementField::Image,
+        PageElementField::Video,
+        PageElementField::Line,
+        PageElementField::Table,
+        PageElementField::WordArt,
+        PageElementField::SheetsChart,
+        PageElementField::SpeakerSpotlight,
+    ];
+}
+
+impl FieldPath for PageElementField {
+    fn name(&self) -> &'static str {
+        match self {
+            PageElementField::ObjectId => "objectId",
+            PageElementField::Size => "size",
+            PageElementField::Transform => "transform",
+            PageElementField::Title => "title",
+            PageElementField::Description => "description",
+            PageElementField::ElementGroup => "elementGroup",
+            PageElementField::Shape => "shape",
+            PageElementField::Image => "image",
+            PageElementField::Video => "video",
+            PageElementField::Line => "line",
+            PageElementField::Table => "table",
+            PageElementField::WordArt => "wordArt",
+            PageElementField::SheetsChart => "sheetsChart",
+            PageElementField::SpeakerSpotlight => "speakerSpotlight",
+        }
+    }
+
+    fn required() -> Vec<Self> {
+        vec![PageElementField::ObjectId]
+    }
+}
+
+/// A set of selected fields, rendered as the value of the `fields` query parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldMask<F: FieldPath> {
+    fields: Vec<F>,
+}
+
+/// A field mask for `presentations.get`.
+pub type PresentationFieldMask = FieldMask<PresentationField>;
+
+/// A field mask for `presentations.pages.get`.
+pub type PageFieldMask = FieldMask<PageField>;
+
+impl<F: FieldPath> Default for FieldMask<F> {
+    fn default() -> Self {
+        Self { fields: Vec::new() }
+    }
+}
+
+impl<F: FieldPath> FieldMask<F> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds a field to the mask.
+    pub fn field(mut self, field: F) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Returns `true` if no field was selected (the full resource is returned).
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+impl<F: FieldPath> fmt::Display for FieldMask<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&render_selection(&self.fields).join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::presentation::Presentation;
+
+    #[test]
+    fn test_nested_mask_adds_required_fields() {
+        let mask = PresentationFieldMask::new()
+            .field(PresentationField::Title)
+            .field(PresentationField::Slides(vec![
+                PageField::PageType,
+                PageField::PageElements(vec![PageElementField::Title]),
+            ]));
+        assert_eq!(
+            mask.to_string(),
+            "presentationId,title,slides(objectId,pageType,pageElements(objectId,title,\
+             elementGroup,shape,image,video,line,table,wordArt,sheetsChart,speakerSpotlight))"
+        );
+
+        let page_mask = PageFieldMask::new()
+            .field(PageField::PageElements(vec![PageElementField::Shape]))
+            .field(PageField::ObjectId);
+        assert_eq!(
+            page_mask.to_string(),
+            "objectId,pageElements(objectId,shape)"
+        );
+
+        // A response restricted to the default mask fields still deserializes.
+        let partial =
+            r#"{"presentationId": "p1", "title": "Deck", "slides": [{"objectId": "s1"}]}"#;
+        let presentation: Presentation = serde_json::from_str(partial).unwrap();
+        assert_eq!(presentation.slides.unwrap()[0].object_id, "s1");
+    }
+}
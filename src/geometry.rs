@@ -0,0 +1,473 @@
+//! Absolute geometry of page elements.
+//!
+//! A page element's `transform` is relative to its group; its place on the page is the
+//! transform preconcatenated with those of all its groups. `Matrix` does that composition
+//! with translations in EMU, and `ElementGeometry` derives the element's axis-aligned
+//! bounding box, its rotated box and its rotation angle. Lengths are typed (`Emu`, `Pt`,
+//! `Px`) so unit conversions are explicit.
+//!
+//! ```
+//! use gslides_tools::geometry::page_geometry;
+//! use gslides_tools::Presentation;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let presentation: Presentation =
+//!     serde_json::from_str(&std::fs::read_to_string("base_presentation.json")?)?;
+//! for slide in presentation.slides.iter().flatten() {
+//!     for geometry in page_geometry(slide) {
+//!         if let Some(bounds) = geometry.bounds() {
+//!             println!("{} at ({}, {}) pt", geometry.element.object_id, bounds.x.to_pt().0, bounds.y.to_pt().0);
+//!         }
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::models::common::{AffineTransform, Dimension, Size, Unit};
+use crate::models::elements::PageElement;
+use crate::models::page::Page;
+use crate::visit::{walk_page, VisitContext, Visitor};
+use std::ops::{Add, Mul, Sub};
+
+/// EMU per point.
+pub const EMU_PER_PT: f64 = 12_700.0;
+/// EMU per inch.
+pub const EMU_PER_INCH: f64 = 914_400.0;
+/// EMU per CSS pixel (1/96 inch).
+pub const EMU_PER_PX: f64 = EMU_PER_INCH / 96.0;
+
+/// A length in English Metric Units, the API's native unit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Emu(pub f64);
+
+/// A length in points (1/72 inch).
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Pt(pub f64);
+
+/// A length in CSS pixels (1/96 inch).
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Px(pub f64);
+
+impl Emu {
+    /// Converts a `Dimension`; a missing magnitude is zero and a missing unit is EMU.
+    pub fn from_dimension(dimension: &Dimension) -> Self {
+        from_unit(dimension.magnitude.unwrap_or(0.0), dimension.unit.as_ref())
+    }
+
+    pub fn to_pt(self) -> Pt {
+        Pt(self.0 / EMU_PER_PT)
+    }
+
+    pub fn to_px(self) -> Px {
+        Px(self.0 / EMU_PER_PX)
+    }
+}
+
+impl From<Pt> for Emu {
+    fn from(pt: Pt) -> Self {
+        Emu(pt.0 * EMU_PER_PT)
+    }
+}
+
+impl From<Px> for Emu {
+    fn from(px: Px) -> Self {
+        Emu(px.0 * EMU_PER_PX)
+    }
+}
+
+impl From<Emu> for Pt {
+    fn from(emu: Emu) -> Self {
+        emu.to_pt()
+    }
+}
+
+impl From<Emu> for Px {
+    fn from(emu: Emu) -> Self {
+        emu.to_px()
+    }
+}
+
+impl Add for Emu {
+    type Output = Emu;
+
+    fn add(self, other: Emu) -> Emu {
+        Emu(self.0 + other.0)
+    }
+}
+
+impl Sub for Emu {
+    type Output = Emu;
+
+    fn sub(self, other: Emu) -> Emu {
+        Emu(self.0 - other.0)
+    }
+}
+
+impl Mul<f64> for Emu {
+    type Output = Emu;
+
+    fn mul(self, factor: f64) -> Emu {
+        Emu(self.0 * factor)
+    }
+}
+
+/// A point on the page.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Point {
+    pub x: Emu,
+    pub y: Emu,
+}
+
+/// An axis-aligned rectangle on the page.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Rect {
+    pub x: Emu,
+    pub y: Emu,
+    pub width: Emu,
+    pub height: Emu,
+}
+
+impl Rect {
+    /// The smallest rectangle containing all `points`, or `None` if there are none.
+    pub fn enclosing(points: impl IntoIterator<Item = Point>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let (mut min, mut max) = (first, first);
+        for point in points {
+            min.x = Emu(min.x.0.min(point.x.0));
+            min.y = Emu(min.y.0.min(point.y.0));
+            max.x = Emu(max.x.0.max(point.x.0));
+            max.y = Emu(max.y.0.max(point.y.0));
+        }
+        Some(Rect {
+            x: min.x,
+            y: min.y,
+            width: max.x - min.x,
+            height: max.y - min.y,
+        })
+    }
+
+    pub fn right(&self) -> Emu {
+        self.x + self.width
+    }
+
+    pub fn bottom(&self) -> Emu {
+        self.y + self.height
+    }
+
+    /// Returns true if the rectangles share an area (touching edges do not count).
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.x < other.right()
+            && other.x < self.right()
+            && self.y < other.bottom()
+            && other.y < self.bottom()
+    }
+
+    /// Returns true if `other` lies entirely inside this rectangle.
+    pub fn contains(&self, other: &Rect) -> bool {
+        self.x <= other.x
+            && self.y <= other.y
+            && other.right() <= self.right()
+            && other.bottom() <= self.bottom()
+    }
+}
+
+/// A rectangle rotated about its center, as an element appears on the page.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RotatedRect {
+    pub center: Point,
+    /// The width along the element's own x axis.
+    pub width: Emu,
+    /// The height along the element's own y axis.
+    pub height: Emu,
+    /// The clockwise rotation of the element's x axis, in degrees in `(-180, 180]`.
+    pub rotation_degrees: f64,
+}
+
+/// An affine matrix with the translation in EMU:
+///
+/// ```text
+/// x' = scale_x * x + shear_x * y + translate_x
+/// y' = shear_y * x + scale_y * y + translate_y
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix {
+    pub scale_x: f64,
+    pub shear_x: f64,
+    pub translate_x: f64,
+    pub shear_y: f64,
+    pub scale_y: f64,
+    pub translate_y: f64,
+}
+
+impl Default for Matrix {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Matrix {
+    pub const IDENTITY: Matrix = Matrix {
+        scale_x: 1.0,
+        shear_x: 0.0,
+        translate_x: 0.0,
+        shear_y: 0.0,
+        scale_y: 1.0,
+        translate_y: 0.0,
+    };
+
+    /// Reads an `AffineTransform`. Unset components are zero, as in API responses (the
+    /// API omits zero values), and translations in points are converted to EMU.
+    pub fn from_transform(transform: &AffineTransform) -> Self {
+        let unit = transform.unit.as_ref();
+        Matrix {
+            scale_x: transform.scale_x.unwrap_or(0.0),
+            shear_x: transform.shear_x.unwrap_or(0.0),
+            translate_x: from_unit(transform.translate_x.unwrap_or(0.0), unit).0,
+            shear_y: transform.shear_y.unwrap_or(0.0),
+            scale_y: transform.scale_y.unwrap_or(0.0),
+            translate_y: from_unit(transform.translate_y.unwrap_or(0.0), unit).0,
+        }
+    }
+
+    /// Reads an element's transform; elements without one are not transformed.
+    pub fn of_element(element: &PageElement) -> Self {
+        element
+            .transform
+            .as_ref()
+            .map_or(Self::IDENTITY, Self::from_transform)
+    }
+
+    /// Writes the matrix as an `AffineTransform` in EMU.
+    pub fn to_transform(&self) -> AffineTransform {
+        AffineTransform {
+            scale_x: Some(self.scale_x),
+            scale_y: Some(self.scale_y),
+            shear_x: Some(self.shear_x),
+            shear_y: Some(self.shear_y),
+            translate_x: Some(self.translate_x),
+            translate_y: Some(self.translate_y),
+            unit: Some(Unit::Emu),
+            extra: Default::default(),
+        }
+    }
+
+    /// Returns `self × inner`: applies `inner` first, then `self`. A group's absolute
+    /// matrix times a child's transform is the child's absolute matrix.
+    pub fn multiply(&self, inner: &Matrix) -> Matrix {
+        let (a, b) = (self, inner);
+        Matrix {
+            scale_x: a.scale_x * b.scale_x + a.shear_x * b.shear_y,
+            shear_x: a.scale_x * b.shear_x + a.shear_x * b.scale_y,
+            translate_x: a.scale_x * b.translate_x + a.shear_x * b.translate_y + a.translate_x,
+            shear_y: a.shear_y * b.scale_x + a.scale_y * b.shear_y,
+            scale_y: a.shear_y * b.shear_x + a.scale_y * b.scale_y,
+            translate_y: a.shear_y * b.translate_x + a.scale_y * b.translate_y + a.translate_y,
+        }
+    }
+
+    /// Maps a point from element space to page space.
+    pub fn apply(&self, point: Point) -> Point {
+        let (x, y) = (point.x.0, point.y.0);
+        Point {
+            x: Emu(self.scale_x * x + self.shear_x * y + self.translate_x),
+            y: Emu(self.shear_y * x + self.scale_y * y + self.translate_y),
+        }
+    }
+
+    /// The clockwise rotation of the transformed x axis, in degrees in `(-180, 180]`.
+    pub fn rotation_degrees(&self) -> f64 {
+        let degrees = self.shear_y.atan2(self.scale_x).to_degrees();
+        if degrees <= -180.0 {
+            degrees + 360.0
+        } else {
+            degrees
+        }
+    }
+
+    /// The corners of a `width` x `height` box in page space: top-left, top-right,
+    /// bottom-right, bottom-left (before transformation).
+    pub fn corners(&self, width: Emu, height: Emu) -> [Point; 4] {
+        [
+            (Emu(0.0), Emu(0.0)),
+            (width, Emu(0.0)),
+            (width, height),
+            (Emu(0.0), height),
+        ]
+        .map(|(x, y)| self.apply(Point { x, y }))
+    }
+
+    /// The axis-aligned bounding box of a transformed `width` x `height` box.
+    pub fn bounds(&self, width: Emu, height: Emu) -> Rect {
+        Rect::enclosing(self.corners(width, height)).unwrap_or_default()
+    }
+
+    /// The transformed `width` x `height` box as a rotated rectangle. Exact for
+    /// rotations, scales and flips; a skewed box is approximated by its area.
+    pub fn rotated_box(&self, width: Emu, height: Emu) -> RotatedRect {
+        let x_scale = self.scale_x.hypot(self.shear_y);
+        let determinant = self.scale_x * self.scale_y - self.shear_x * self.shear_y;
+        let y_scale = if x_scale == 0.0 {
+            self.shear_x.hypot(self.scale_y)
+        } else {
+            determinant.abs() / x_scale
+        };
+        RotatedRect {
+            center: self.apply(Point {
+                x: width * 0.5,
+                y: height * 0.5,
+            }),
+            width: width * x_scale,
+            height: height * y_scale,
+            rotation_degrees: self.rotation_degrees(),
+        }
+    }
+}
+
+/// The absolute geometry of one page element.
+#[derive(Debug, Clone)]
+pub struct ElementGeometry<'a> {
+    pub element: &'a PageElement,
+    /// The element's transform preconcatenated with its groups' transforms.
+    pub transform: Matrix,
+}
+
+impl ElementGeometry<'_> {
+    /// The element's unscaled size, if it has one. Groups and lines may not.
+    pub fn size(&self) -> Option<(Emu, Emu)> {
+        self.element.size.as_ref().map(size_in_emu)
+    }
+
+    /// The axis-aligned bounding box on the page.
+    pub fn bounds(&self) -> Option<Rect> {
+        let (width, height) = self.size()?;
+        Some(self.transform.bounds(width, height))
+    }
+
+    /// The element's box on the page, rotated like the element.
+    pub fn rotated_bounds(&self) -> Option<RotatedRect> {
+        let (width, height) = self.size()?;
+        Some(self.transform.rotated_box(width, height))
+    }
+
+    /// The clockwise rotation of the element on the page, in degrees.
+    pub fn rotation_degrees(&self) -> f64 {
+        self.transform.rotation_degrees()
+    }
+}
+
+/// Returns the absolute geometry of every element on a page, groups and their children
+/// included, in `visit::walk_page` order.
+pub fn page_geometry(page: &Page) -> Vec<ElementGeometry<'_>> {
+    #[derive(Default)]
+    struct Collector<'a>(Vec<ElementGeometry<'a>>);
+
+    impl<'a> Visitor<'a> for Collector<'a> {
+        fn visit_element(&mut self, element: &'a PageElement, context: &VisitContext<'_>) {
+            self.0.push(ElementGeometry {
+                element,
+                transform: Matrix::from_transform(context.transform),
+            });
+        }
+    }
+
+    let mut collector = Collector::default();
+    walk_page(
+        page,
+        &page.page_type.clone().unwrap_or_default(),
+        &mut collector,
+    );
+    collector.0
+}
+
+/// Converts a `Size` to EMU; missing dimensions are zero.
+pub fn size_in_emu(size: &Size) -> (Emu, Emu) {
+    let length = |d: Option<&Dimension>| d.map_or(Emu(0.0), Emu::from_dimension);
+    (length(size.width.as_ref()), length(size.height.as_ref()))
+}
+
+fn from_unit(magnitude: f64, unit: Option<&Unit>) -> Emu {
+    match unit {
+        Some(Unit::Pt) => Pt(magnitude).into(),
+        _ => Emu(magnitude),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::{PageBuilder, ShapeBuilder};
+    use crate::models::elements::PageElementKind;
+    use crate::models::group::Group;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-6
+    }
+
+    #[test]
+    fn test_nested_groups_compose_into_absolute_geometry() {
+        // A 90° clockwise rotation inside a group that doubles and moves its children.
+        let rotated = ShapeBuilder::text_box("box_1")
+            .set_size(200.0, 100.0)
+            .set_transform(
+                Matrix {
+                    scale_x: 0.0,
+                    shear_x: -1.0,
+                    translate_x: 100.0,
+                    shear_y: 1.0,
+                    scale_y: 0.0,
+                    translate_y: 0.0,
+                }
+                .to_transform(),
+            )
+            .build();
+        let mut inner = ShapeBuilder::text_box("inner_group").build();
+        inner.element_kind = PageElementKind::ElementGroup(Group {
+            children: vec![rotated],
+            extra: Default::default(),
+        });
+        let mut outer = ShapeBuilder::text_box("outer_group")
+            .set_transform(
+                Matrix {
+                    scale_x: 2.0,
+                    scale_y: 2.0,
+                    translate_x: Emu::from(Pt(10.0)).0,
+                    ..Matrix::IDENTITY
+                }
+                .to_transform(),
+            )
+            .build();
+        outer.element_kind = PageElementKind::ElementGroup(Group {
+            children: vec![inner],
+            extra: Default::default(),
+        });
+        let page = PageBuilder::slide("slide_1").add_element(outer).build();
+
+        let geometry = page_geometry(&page);
+        let ids: Vec<_> = geometry
+            .iter()
+            .map(|g| g.element.object_id.as_str())
+            .collect();
+        assert_eq!(ids, ["outer_group", "inner_group", "box_1"]);
+
+        let child = &geometry[2];
+        assert!(close(child.rotation_degrees(), 90.0));
+        let bounds = child.bounds().unwrap();
+        assert!(close(bounds.x.0, 127_000.0));
+        assert!(close(bounds.y.0, 0.0));
+        assert!(close(bounds.width.0, 200.0) && close(bounds.height.0, 400.0));
+        let rotated = child.rotated_bounds().unwrap();
+        assert!(close(rotated.width.0, 400.0) && close(rotated.height.0, 200.0));
+        assert!(close(rotated.center.x.0, 127_100.0) && close(rotated.center.y.0, 200.0));
+
+        assert!(close(Emu(12_700.0).to_pt().0, 1.0));
+        assert!(close(Emu::from(Px(96.0)).0, EMU_PER_INCH));
+        assert!(bounds.intersects(&Rect {
+            x: Emu(127_050.0),
+            y: Emu(100.0),
+            width: Emu(10.0),
+            height: Emu(10.0),
+        }));
+    }
+}
@@ -1,21 +1,58 @@
 #[cfg(not(target_arch = "wasm32"))]
 #[cfg(feature = "yup-oauth2")]
+pub mod auth;
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(feature = "yup-oauth2")]
 pub mod client;
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(feature = "yup-oauth2")]
+pub mod drive;
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(feature = "emulator")]
+pub mod emulator;
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(feature = "yup-oauth2")]
+pub mod retry;
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(feature = "yup-oauth2")]
+pub mod sink;
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(feature = "yup-oauth2")]
+pub mod watch;
 
 #[cfg(not(target_arch = "wasm32"))]
 #[cfg(feature = "yup-oauth2")]
-pub use client::get_presentation_sa;
+pub use client::{batch_update_presentation_sa, get_presentation_sa, SlidesClient};
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod cache;
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(feature = "conformance")]
+pub mod conformance;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod merge;
+pub mod builders;
 pub mod converters;
 pub mod errors;
+pub mod field_mask;
+pub mod geometry;
 pub mod models;
+pub mod notes;
+pub mod parse;
+pub mod query;
+pub mod style;
+pub mod template;
+pub mod theme;
+pub mod visit;
 
 pub use converters::markdown;
 pub use errors::{Result, SlidesApiError};
 pub use models::presentation::Presentation;
+pub use parse::parse_presentation;
 
 // features
 pub mod diff;
+pub mod engine;
 pub use diff::comparer::ComparerBuilder;
 
 use wasm_bindgen::prelude::*;
@@ -44,12 +81,11 @@ pub fn convert_json_to_svg(presentation_json_string: &str) -> std::result::Resul
     log::info!("Received presentation JSON, attempting deserialization...");
 
     // 1. Deserialize the JSON string into a Presentation object
-    let presentation: Presentation =
-        serde_json::from_str(presentation_json_string).map_err(|e| {
-            let error_msg = format!("JSON Deserialization Error: {}", e);
-            log::error!("{}", error_msg);
-            JsValue::from_str(&error_msg)
-        })?;
+    let presentation = parse_presentation(presentation_json_string).map_err(|e| {
+        let error_msg = format!("JSON Deserialization Error: {}", e);
+        log::error!("{}", error_msg);
+        JsValue::from_str(&error_msg)
+    })?;
 
     log::info!("Deserialization successful. Starting SVG conversion...");
 
@@ -0,0 +1,507 @@
+//! Mail merge: generates one presentation per CSV or JSON row from a template.
+//!
+//! Each row fills the template through `TemplateFiller`, so `{{column}}` tokens in text runs
+//! and table cells take the row's values. Decks can be generated fully offline
+//! (`MailMerge::write_offline`, which writes each deck as JSON plus one SVG per slide for
+//! review), or published by copying the template in Drive and sending the fill requests
+//! (`MailMerge::publish`, requires the `yup-oauth2` feature). Both return a `MergeManifest`
+//! listing the generated decks and the rows that failed.
+//!
+//! ```no_run
+//! use gslides_tools::merge::{read_csv_rows, MailMerge};
+//! use gslides_tools::Presentation;
+//!
+//! # fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let template: Presentation =
+//!     serde_json::from_str(&std::fs::read_to_string("template.json")?)?;
+//! let rows = read_csv_rows(std::fs::File::open("customers.csv")?)?;
+//! let manifest = MailMerge::new(template)
+//!     .set_name_column("customer")
+//!     .write_offline(&rows, "out")?;
+//! println!("{} decks, {} failed rows", manifest.generated.len(), manifest.failed.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::converters::svg::convert_presentation_to_svg;
+use crate::models::presentation::Presentation;
+use crate::template::{FilledPresentation, TemplateError, TemplateFiller};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[cfg(feature = "yup-oauth2")]
+use crate::drive::DriveClient;
+#[cfg(feature = "yup-oauth2")]
+use crate::models::drive::CopyFileRequest;
+
+/// One data row: column names mapped to values.
+pub type Row = Map<String, Value>;
+
+/// The name of the manifest file written by `MailMerge::write_offline`.
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Errors raised while reading rows or writing merge output.
+#[derive(Error, Debug)]
+pub enum MergeError {
+    /// The CSV input could not be read.
+    #[error("Failed to read CSV rows: {0}")]
+    Csv(#[from] csv::Error),
+
+    /// JSON input or output failed.
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// Reading or writing files failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The input is valid JSON but not a list of row objects.
+    #[error("Invalid rows: {0}")]
+    InvalidRows(String),
+}
+
+/// Reads rows from CSV with a header line. All values are strings.
+pub fn read_csv_rows<R: Read>(reader: R) -> Result<Vec<Row>, MergeError> {
+    let mut reader = csv::Reader::from_reader(reader);
+    let headers = reader.headers()?.clone();
+    reader
+        .records()
+        .map(|record| {
+            let record = record?;
+            Ok(headers
+                .iter()
+                .zip(record.iter())
+                .map(|(header, value)| (header.to_string(), Value::String(value.to_string())))
+                .collect())
+        })
+        .collect()
+}
+
+/// Reads rows from a JSON array of objects. Values may be nested, e.g. lists used by
+/// `{{#each}}` slides.
+pub fn read_json_rows<R: Read>(reader: R) -> Result<Vec<Row>, MergeError> {
+    let Value::Array(items) = serde_json::from_reader(reader)? else {
+        return Err(MergeError::InvalidRows(
+            "expected a JSON array of objects".to_string(),
+        ));
+    };
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| match item {
+            Value::Object(row) => Ok(row),
+            _ => Err(MergeError::InvalidRows(format!(
+                "row {} is not a JSON object",
+                index + 1
+            ))),
+        })
+        .collect()
+}
+
+/// The outcome of a merge run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeManifest {
+    /// The decks that were generated.
+    pub generated: Vec<GeneratedDeck>,
+    /// The rows that could not be turned into a deck.
+    pub failed: Vec<FailedRow>,
+}
+
+/// A deck generated from one row.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedDeck {
+    /// The 1-based position of the row in the input (not counting a CSV header).
+    pub row_number: usize,
+    /// The deck's name (its title, and the name of the Drive copy).
+    pub name: String,
+    /// The ID of the published presentation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presentation_id: Option<String>,
+    /// The presentation JSON written offline.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_path: Option<PathBuf>,
+    /// The SVG of each slide written offline.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub svg_paths: Vec<PathBuf>,
+}
+
+/// A row that failed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailedRow {
+    /// The 1-based position of the row in the input (not counting a CSV header).
+    pub row_number: usize,
+    /// The deck's name.
+    pub name: String,
+    /// The ID of a copy that was created before the failure, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presentation_id: Option<String>,
+    /// What went wrong.
+    pub error: String,
+}
+
+impl MergeManifest {
+    /// Writes the manifest as pretty-printed JSON.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), MergeError> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Generates one presentation per row from a template.
+#[derive(Debug, Clone)]
+pub struct MailMerge {
+    template: Presentation,
+    filler: TemplateFiller,
+    name_column: Option<String>,
+}
+
+impl MailMerge {
+    /// Creates a merge of `template` that fails rows with missing columns.
+    pub fn new(template: Presentation) -> Self {
+        Self {
+            template,
+            filler: TemplateFiller::new(),
+            name_column: None,
+        }
+    }
+
+    /// Sets the filler used for each row, e.g. to allow missing values.
+    pub fn set_filler(mut self, filler: TemplateFiller) -> Self {
+        self.filler = filler;
+        self
+    }
+
+    /// Names each deck after the value of `column`. Without it (or when the row has no
+    /// such value), decks are named `<template title> <row number>`.
+    pub fn set_name_column(mut self, column: impl Into<String>) -> Self {
+        self.name_column = Some(column.into());
+        self
+    }
+
+    /// The name of the deck generated from a row.
+    pub fn row_name(&self, row_number: usize, row: &Row) -> String {
+        let value = self
+            .name_column
+            .as_ref()
+            .and_then(|column| row.get(column))
+            .and_then(|value| match value {
+                Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+                Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            });
+        value.unwrap_or_else(|| {
+            let title = self.template.title.as_deref().unwrap_or("Presentation");
+            format!("{} {}", title, row_number)
+        })
+    }
+
+    /// Fills the template with one row. The filled deck's title is set to the row's name.
+    pub fn fill_row(
+        &self,
+        row_number: usize,
+        row: &Row,
+    ) -> Result<FilledPresentation, TemplateError> {
+        let mut filled = self.filler.fill(&self.template, row)?;
+        filled.presentation.title = Some(self.row_name(row_number, row));
+        Ok(filled)
+    }
+
+    /// Generates every deck offline into `out_dir`: `<n>_<name>.json` with the filled
+    /// presentation, `<n>_<name>_slide_<k>.svg` per slide, and `manifest.json`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the manifest. Rows that cannot be filled or rendered are
+    /// listed as failed; only I/O errors abort the run.
+    pub fn write_offline(
+        &self,
+        rows: &[Row],
+        out_dir: impl AsRef<Path>,
+    ) -> Result<MergeManifest, MergeError> {
+        let out_dir = out_dir.as_ref();
+        fs::create_dir_all(out_dir)?;
+        let mut manifest = MergeManifest::default();
+        for (index, row) in rows.iter().enumerate() {
+            let row_number = index + 1;
+            let name = self.row_name(row_number, row);
+            let rendered = self
+                .fill_row(row_number, row)
+                .map_err(|e| e.to_string())
+                .and_then(|filled| {
+                    let svgs = convert_presentation_to_svg(&filled.presentation)
+                        .map_err(|e| e.to_string())?;
+                    Ok((filled.presentation, svgs))
+                });
+            let (presentation, svgs) = match rendered {
+                Ok(rendered) => rendered,
+                Err(error) => {
+                    manifest.failed.push(FailedRow {
+                        row_number,
+                        name,
+                        presentation_id: None,
+                        error,
+                    });
+                    continue;
+                }
+            };
+
+            let stem = format!("{:03}_{}", row_number, file_name_part(&name));
+            let json_path = out_dir.join(format!("{}.json", stem));
+            fs::write(&json_path, serde_json::to_string_pretty(&presentation)?)?;
+            let mut svg_paths = Vec::with_capacity(svgs.len());
+            for (slide_index, svg) in svgs.iter().enumerate() {
+                let svg_path = out_dir.join(format!("{}_slide_{}.svg", stem, slide_index + 1));
+                fs::write(&svg_path, svg)?;
+                svg_paths.push(svg_path);
+            }
+            manifest.generated.push(GeneratedDeck {
+                row_number,
+                name,
+                presentation_id: None,
+                json_path: Some(json_path),
+                svg_paths,
+            });
+        }
+        manifest.write(out_dir.join(MANIFEST_FILE_NAME))?;
+        Ok(manifest)
+    }
+
+    /// Publishes every deck: copies the template presentation in Drive (object IDs are
+    /// kept by the copy) and sends the row's fill requests to the copy.
+    ///
+    /// # Arguments
+    ///
+    /// * `drive` - The client used for the copies; its `SlidesClient` sends the fill requests.
+    /// * `template_presentation_id` - The ID of the template deck this merge was built from.
+    /// * `rows` - The data rows.
+    /// * `folder_id` - Optional Drive folder for the copies.
+    ///
+    /// # Returns
+    ///
+    /// The manifest. A row whose update fails after its copy was created is listed as failed
+    /// together with the copy's ID.
+    #[cfg(feature = "yup-oauth2")]
+    pub async fn publish(
+        &self,
+        drive: &DriveClient,
+        template_presentation_id: &str,
+        rows: &[Row],
+        folder_id: Option<&str>,
+    ) -> MergeManifest {
+        let mut manifest = MergeManifest::default();
+        for (index, row) in rows.iter().enumerate() {
+            let row_number = index + 1;
+            let name = self.row_name(row_number, row);
+            let failed = |error: String, presentation_id: Option<String>| FailedRow {
+                row_number,
+                name: name.clone(),
+                presentation_id,
+                error,
+            };
+            let filled = match self.fill_row(row_number, row) {
+                Ok(filled) => filled,
+                Err(e) => {
+                    manifest.failed.push(failed(e.to_string(), None));
+                    continue;
+                }
+            };
+            let copy_request = CopyFileRequest {
+                name: Some(name.clone()),
+                parents: folder_id.map(|id| vec![id.to_string()]),
+                description: None,
+            };
+            let copy = match drive
+                .copy_file(template_presentation_id, &copy_request)
+                .await
+            {
+                Ok(copy) => copy,
+                Err(e) => {
+                    manifest.failed.push(failed(e.to_string(), None));
+                    continue;
+                }
+            };
+            if !filled.requests.is_empty() {
+                if let Err(e) = drive
+                    .slides_client()
+                    .batch_update(&copy.id, filled.requests, None)
+                    .await
+                {
+                    manifest.failed.push(failed(e.to_string(), Some(copy.id)));
+                    continue;
+                }
+            }
+            manifest.generated.push(GeneratedDeck {
+                row_number,
+                name,
+                presentation_id: Some(copy.id),
+                json_path: None,
+                svg_paths: Vec::new(),
+            });
+        }
+        manifest
+    }
+}
+
+/// Makes a deck name safe for use in a file name.
+fn file_name_part(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// A template with a `{{customer}}` token in a shape and a `{{region}}` token in a
+    /// table cell.
+    fn template_json() -> Value {
+        json!({
+            "presentationId": "template",
+            "title": "Offer",
+            "pageSize": {"width": {"magnitude": 9144000, "unit": "EMU"},
+                "height": {"magnitude": 5143500, "unit": "EMU"}},
+            "slides": [{"objectId": "slide1", "pageElements": [
+                {"objectId": "title", "shape": {"text": {"textElements": [
+                    {"endIndex": 18, "paragraphMarker": {}},
+                    {"endIndex": 18, "textRun": {"content": "Dear {{customer}}\n"}}
+                ]}}},
+                {"objectId": "table1", "table": {"rows": 1, "columns": 1, "tableRows": [
+                    {"tableCells": [{"text": {"textElements": [
+                        {"endIndex": 19, "paragraphMarker": {}},
+                        {"endIndex": 19, "textRun": {"content": "Region: {{region}}\n"}}
+                    ]}}]}
+                ]}}
+            ]}]
+        })
+    }
+
+    #[test]
+    fn test_write_offline_from_csv_rows() {
+        let template: Presentation = serde_json::from_value(template_json()).unwrap();
+        let csv = "customer,region\nACME Corp,EU\n,US\n";
+        let rows = read_csv_rows(csv.as_bytes()).unwrap();
+        assert_eq!(rows.len(), 2);
+
+        let dir = std::env::temp_dir().join(format!("gslides-merge-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let manifest = MailMerge::new(template.clone())
+            .set_name_column("customer")
+            .write_offline(&rows, &dir)
+            .unwrap();
+
+        assert_eq!(manifest.generated.len(), 2);
+        let first = &manifest.generated[0];
+        assert_eq!(first.name, "ACME Corp");
+        assert_eq!(manifest.generated[1].name, "Offer 2");
+        let json = fs::read_to_string(first.json_path.as_ref().unwrap()).unwrap();
+        assert!(json.contains("Dear ACME Corp\\n"));
+        assert!(json.contains("Region: EU\\n"));
+        assert_eq!(first.svg_paths.len(), 1);
+        assert!(first.svg_paths[0].exists());
+        assert!(dir.join(MANIFEST_FILE_NAME).exists());
+
+        // A row without the `customer` column is reported instead of aborting the run.
+        let rows = read_json_rows(r#"[{"region": "EU"}]"#.as_bytes()).unwrap();
+        let manifest = MailMerge::new(template)
+            .write_offline(&rows, dir.join("missing"))
+            .unwrap();
+        assert!(manifest.generated.is_empty());
+        assert_eq!(manifest.failed[0].row_number, 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(all(feature = "emulator", feature = "yup-oauth2"))]
+    #[tokio::test]
+    async fn test_publish_fills_copies_and_reports_orphaned_copies() {
+        use crate::auth::StaticTokenProvider;
+        use crate::client::SlidesClient;
+        use crate::emulator::SlidesEmulator;
+
+        // The template in Drive no longer has the logo image of the local template, so
+        // a row with a logo fails after its copy was created.
+        let mut template = template_json();
+        let emulator = SlidesEmulator::builder()
+            .add_presentation_json(&template.to_string())
+            .unwrap()
+            .start()
+            .await
+            .unwrap();
+        template["slides"][0]["pageElements"]
+            .as_array_mut()
+            .unwrap()
+            .push(json!({"objectId": "logo", "title": "{{logo}}",
+                "image": {"contentUrl": "https://example.com/placeholder.png"}}));
+        let template: Presentation = serde_json::from_value(template).unwrap();
+        let drive = SlidesClient::builder()
+            .set_token_provider(StaticTokenProvider::new("test-token"))
+            .set_base_url(emulator.base_url())
+            .build()
+            .unwrap()
+            .drive()
+            .set_base_url(emulator.drive_base_url());
+
+        let rows = read_json_rows(
+            r#"[{"customer": "ACME Corp", "region": "EU", "logo": ""},
+                {"customer": "Globex", "region": "US", "logo": "https://example.com/globex.png"}]"#
+                .as_bytes(),
+        )
+        .unwrap();
+        let manifest = MailMerge::new(template)
+            .set_name_column("customer")
+            .publish(&drive, "template", &rows, Some("folder1"))
+            .await;
+
+        assert_eq!(manifest.generated.len(), 1);
+        let generated = &manifest.generated[0];
+        assert_eq!(generated.name, "ACME Corp");
+        let copy_id = generated.presentation_id.clone().unwrap();
+        let copy = emulator.presentation_json(&copy_id).unwrap();
+        assert_eq!(copy["title"], "ACME Corp");
+        let copy_text = copy["slides"].to_string();
+        assert!(copy_text.contains("Dear ACME Corp\\n"));
+        assert!(copy_text.contains("Region: EU\\n"));
+        let template_text = emulator.presentation_json("template").unwrap().to_string();
+        assert!(template_text.contains("{{customer}}"));
+
+        assert_eq!(manifest.failed.len(), 1);
+        let failed = &manifest.failed[0];
+        assert_eq!((failed.row_number, failed.name.as_str()), (2, "Globex"));
+        let orphan_id = failed.presentation_id.clone().unwrap();
+        assert_ne!(orphan_id, copy_id);
+        assert!(failed
+            .error
+            .contains("The object (logo) could not be found."));
+        let orphan_text = emulator.presentation_json(&orphan_id).unwrap().to_string();
+        assert!(orphan_text.contains("{{customer}}"));
+
+        // The fill requests were sent to the copies, never to the template.
+        let batch_paths: Vec<String> = emulator
+            .requests()
+            .iter()
+            .filter(|r| r.path().ends_with(":batchUpdate"))
+            .map(|r| r.path().to_string())
+            .collect();
+        assert_eq!(
+            batch_paths,
+            [
+                format!("/v1/presentations/{}:batchUpdate", copy_id),
+                format!("/v1/presentations/{}:batchUpdate", orphan_id)
+            ]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::models::common::ExtraFields;
 use serde::{Deserialize, Serialize};
 
 // Import TextStyle needed for bulletStyle
@@ -23,4 +24,7 @@ pub struct Bullet {
     /// The paragraph-specific text style applied to this bullet.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bullet_style: Option<TextStyle>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
@@ -1,3 +1,4 @@
+use crate::models::common::ExtraFields;
 use serde::{Deserialize, Serialize};
 
 /// An RGB color.
@@ -6,13 +7,32 @@ use serde::{Deserialize, Serialize};
 pub struct RgbColor {
     /// The red component of the color, from 0.0 to 1.0.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub red: Option<f32>,
     /// The green component of the color, from 0.0 to 1.0.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub green: Option<f32>,
     /// The blue component of the color, from 0.0 to 1.0.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub blue: Option<f32>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl RgbColor {
+    /// Formats the color as `#rrggbb`. Missing components count as 0.
+    pub fn to_hex(&self) -> String {
+        let channel = |value: Option<f32>| (value.unwrap_or(0.0) * 255.0).round() as u8;
+        format!(
+            "#{:02x}{:02x}{:02x}",
+            channel(self.red),
+            channel(self.green),
+            channel(self.blue)
+        )
+    }
 }
 
 /// Theme color types.
@@ -58,6 +78,9 @@ pub struct OpaqueColor {
     /// The specific color choice. Uses flatten to represent the union based on JSON key.
     #[serde(flatten)]
     pub color_kind: OpaqueColorContent,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 // --- END REVERTED Definition ---
 
@@ -71,6 +94,9 @@ pub struct OptionalColor {
     // This now uses the OpaqueColor struct again.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub opaque_color: Option<OpaqueColor>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// A pair mapping a theme color type to the concrete color it represents.
@@ -82,6 +108,9 @@ pub struct ThemeColorPair {
     pub theme_color_type: ThemeColorType,
     /// The concrete RGB color corresponding to the theme color type above.
     pub color: RgbColor,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// A color scheme defines the mapping of theme color types to concrete colors used on a page.
@@ -90,4 +119,17 @@ pub struct ThemeColorPair {
 pub struct ColorScheme {
     /// The ThemeColorType and corresponding concrete color pairs.
     pub colors: Vec<ThemeColorPair>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl ColorScheme {
+    /// Returns the concrete color of `theme_color_type`, if the scheme defines it.
+    pub fn rgb(&self, theme_color_type: &ThemeColorType) -> Option<&RgbColor> {
+        self.colors
+            .iter()
+            .find(|pair| pair.theme_color_type == *theme_color_type)
+            .map(|pair| &pair.color)
+    }
 }
@@ -1,4 +1,29 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
+
+/// JSON fields that a model does not describe.
+///
+/// Every model keeps such fields when it is deserialized and writes them back when it is
+/// serialized, so a presentation round-trips without losing data.
+pub type ExtraFields = serde_json::Map<String, serde_json::Value>;
+
+/// Serializes an optional number the way the API writes it: whole numbers have no
+/// fractional part, so `3000000` is not written back as `3000000.0`, and `f32` values keep
+/// their shortest decimal form (`0.2`, not `0.20000000298023224`) in `serde_json::Value`s.
+pub(crate) fn serialize_number<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Copy + Into<f64> + std::fmt::Display,
+    S: Serializer,
+{
+    let Some(number) = value else {
+        return serializer.serialize_none();
+    };
+    let float: f64 = (*number).into();
+    if float.fract() == 0.0 && float.abs() < 9_007_199_254_740_992.0 {
+        serializer.serialize_i64(float as i64)
+    } else {
+        serializer.serialize_f64(number.to_string().parse().unwrap_or(float))
+    }
+}
 
 /// Specifies a unit of length.
 /// Derived from: https://developers.google.com/slides/api/reference/rest/v1/Dimension#Unit
@@ -21,10 +46,14 @@ pub enum Unit {
 pub struct Dimension {
     /// The magnitude.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub magnitude: Option<f64>,
     /// The units for magnitude.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub unit: Option<Unit>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// A width and height.
@@ -38,6 +67,9 @@ pub struct Size {
     /// The height. Missing height does not inherit from parents.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub height: Option<Dimension>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// AffineTransform uses a 3x3 matrix with an implied last row of [ 0 0 1 ]
@@ -54,23 +86,32 @@ pub struct Size {
 pub struct AffineTransform {
     /// The X coordinate scaling element.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub scale_x: Option<f64>,
     /// The Y coordinate scaling element.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub scale_y: Option<f64>,
     /// The X coordinate shearing element.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub shear_x: Option<f64>,
     /// The Y coordinate shearing element.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub shear_y: Option<f64>,
     /// The X coordinate translation element.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub translate_x: Option<f64>,
     /// The Y coordinate translation element.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub translate_y: Option<f64>,
     /// The units for the translation elements.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub unit: Option<Unit>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
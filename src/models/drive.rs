@@ -0,0 +1,218 @@
+use serde::{Deserialize, Serialize};
+
+/// The MIME type of a Google Slides file in Drive.
+pub const PRESENTATION_MIME_TYPE: &str = "application/vnd.google-apps.presentation";
+
+/// The MIME type of a Drive folder.
+pub const FOLDER_MIME_TYPE: &str = "application/vnd.google-apps.folder";
+
+/// The metadata of a file in Google Drive (the subset used by this crate).
+/// Derived from: https://developers.google.com/drive/api/reference/rest/v3/files#File
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DriveFile {
+    /// The ID of the file. For Slides files this is the presentation ID.
+    pub id: String,
+
+    /// The name of the file.
+    pub name: Option<String>,
+
+    /// The MIME type of the file.
+    pub mime_type: Option<String>,
+
+    /// The IDs of the parent folders which contain the file.
+    pub parents: Option<Vec<String>>,
+
+    /// The time at which the file was created (RFC 3339).
+    pub created_time: Option<String>,
+
+    /// The last time the file was modified by anyone (RFC 3339).
+    pub modified_time: Option<String>,
+
+    /// A link for opening the file in a relevant Google editor or viewer.
+    pub web_view_link: Option<String>,
+
+    /// Whether the file has been trashed.
+    pub trashed: Option<bool>,
+}
+
+/// A list of files, as returned by one page of `files.list`.
+/// Derived from: https://developers.google.com/drive/api/reference/rest/v3/files/list#response-body
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileList {
+    /// The list of files.
+    #[serde(default)]
+    pub files: Vec<DriveFile>,
+
+    /// The page token for the next page of files. Absent on the last page.
+    pub next_page_token: Option<String>,
+
+    /// Whether the search process was incomplete.
+    pub incomplete_search: Option<bool>,
+}
+
+/// The metadata for a file to create with `files.copy`. Unset fields are inherited from the source.
+/// Derived from: https://developers.google.com/drive/api/reference/rest/v3/files/copy
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyFileRequest {
+    /// The name of the copy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// The folder(s) to place the copy in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parents: Option<Vec<String>>,
+
+    /// A short description of the copy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Information about a Drive user.
+/// Derived from: https://developers.google.com/drive/api/reference/rest/v3/User
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DriveUser {
+    /// A plain text displayable name for this user.
+    pub display_name: Option<String>,
+
+    /// The email address of the user.
+    pub email_address: Option<String>,
+
+    /// The user's ID as visible in Permission resources.
+    pub permission_id: Option<String>,
+
+    /// Whether this user is the requesting user.
+    pub me: Option<bool>,
+}
+
+/// The metadata for a revision of a file.
+/// Derived from: https://developers.google.com/drive/api/reference/rest/v3/revisions#Revision
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Revision {
+    /// The ID of the revision.
+    pub id: String,
+
+    /// The MIME type of the revision.
+    pub mime_type: Option<String>,
+
+    /// The last time the revision was modified (RFC 3339).
+    pub modified_time: Option<String>,
+
+    /// Whether to keep this revision forever, even if it is no longer the head revision.
+    pub keep_forever: Option<bool>,
+
+    /// Whether this revision is published (Docs Editors files only).
+    pub published: Option<bool>,
+
+    /// The last user to modify this revision.
+    pub last_modifying_user: Option<DriveUser>,
+}
+
+/// A list of revisions, as returned by one page of `revisions.list`.
+/// Derived from: https://developers.google.com/drive/api/reference/rest/v3/revisions/list#response-body
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevisionList {
+    /// The list of revisions.
+    #[serde(default)]
+    pub revisions: Vec<Revision>,
+
+    /// The page token for the next page of revisions. Absent on the last page.
+    pub next_page_token: Option<String>,
+}
+
+/// Formats a Slides file can be exported to with `files.export`.
+/// Derived from: https://developers.google.com/drive/api/guides/ref-export-formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExportFormat {
+    /// Portable Document Format.
+    Pdf,
+    /// Microsoft PowerPoint.
+    Pptx,
+    /// OpenDocument Presentation.
+    Odp,
+    /// Plain text.
+    PlainText,
+}
+
+impl ExportFormat {
+    /// The MIME type sent as the `mimeType` parameter of `files.export`.
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Pdf => "application/pdf",
+            ExportFormat::Pptx => {
+                "application/vnd.openxmlformats-officedocument.presentationml.presentation"
+            }
+            ExportFormat::Odp => "application/vnd.oasis.opendocument.presentation",
+            ExportFormat::PlainText => "text/plain",
+        }
+    }
+
+    /// The conventional file extension, without the dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Pdf => "pdf",
+            ExportFormat::Pptx => "pptx",
+            ExportFormat::Odp => "odp",
+            ExportFormat::PlainText => "txt",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_drive_models_round_trip_api_json() {
+        let request = CopyFileRequest {
+            name: Some("Copy".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({"name": "Copy"})
+        );
+
+        let list: FileList = serde_json::from_value(json!({
+            "nextPageToken": "page-2",
+            "files": [{
+                "id": "deck",
+                "name": "Deck",
+                "mimeType": PRESENTATION_MIME_TYPE,
+                "webViewLink": "https://docs.google.com/presentation/d/deck/edit",
+                "trashed": false
+            }]
+        }))
+        .unwrap();
+        assert_eq!(list.next_page_token.as_deref(), Some("page-2"));
+        assert_eq!(list.files[0].id, "deck");
+        assert_eq!(
+            list.files[0].mime_type.as_deref(),
+            Some(PRESENTATION_MIME_TYPE)
+        );
+
+        let revisions: RevisionList = serde_json::from_value(json!({
+            "revisions": [{"id": "1", "lastModifyingUser": {"displayName": "Ada", "me": true}}]
+        }))
+        .unwrap();
+        let user = revisions.revisions[0].last_modifying_user.as_ref().unwrap();
+        assert_eq!(user.display_name.as_deref(), Some("Ada"));
+        assert!(serde_json::from_value::<FileList>(json!({}))
+            .unwrap()
+            .files
+            .is_empty());
+    }
+
+    #[test]
+    fn test_export_format_mime_types() {
+        assert_eq!(ExportFormat::Pdf.mime_type(), "application/pdf");
+        assert_eq!(ExportFormat::Pptx.extension(), "pptx");
+        assert_eq!(ExportFormat::PlainText.mime_type(), "text/plain");
+    }
+}
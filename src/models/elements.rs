@@ -1,4 +1,4 @@
-use crate::models::common::{AffineTransform, Size};
+use crate::models::common::{AffineTransform, ExtraFields, Size};
 use crate::models::group::Group;
 use crate::models::image::Image;
 use crate::models::line::Line;
@@ -8,16 +8,17 @@ use crate::models::speaker_spotlight::SpeakerSpotlight;
 use crate::models::table::Table;
 use crate::models::video::Video;
 use crate::models::wordart::WordArt;
-// --- Imports needed for manual Deserialize ---
-use serde::de::{self, Deserializer, MapAccess, Visitor};
-use serde::{Deserialize, Serialize};
-use std::fmt;
-// ---
+use crate::parse::deserialize_nested;
+use serde::ser::SerializeMap;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
 
 /// The specific kind of PageElement represented as an enum with associated data.
+/// Serialized as a single-key object, e.g. `{"shape": {...}}`, which is flattened into
+/// the page element.
 /// NOTE: PartialEq removed as it might not be derivable/needed depending on nested types. Add back if necessary and feasible.
-#[derive(Debug, Clone, Serialize)] // Deserialize is handled manually for PageElement wrapper
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum PageElementKind {
     ElementGroup(Group),
     Shape(Shape),
@@ -28,11 +29,123 @@ pub enum PageElementKind {
     WordArt(WordArt),
     SheetsChart(SheetsChart),
     SpeakerSpotlight(SpeakerSpotlight),
+    /// An element kind this library does not know yet, e.g. one added to the API later.
+    /// `kind` is its JSON key and `raw` its value, written back unchanged.
+    Unknown {
+        kind: String,
+        raw: Value,
+    },
+}
+
+impl PageElementKind {
+    /// The JSON keys of the known element kinds.
+    const KNOWN_KINDS: [&'static str; 9] = [
+        "elementGroup",
+        "shape",
+        "image",
+        "video",
+        "line",
+        "table",
+        "wordArt",
+        "sheetsChart",
+        "speakerSpotlight",
+    ];
+
+    /// The JSON key of this kind, e.g. `shape`.
+    pub fn kind_name(&self) -> &str {
+        match self {
+            PageElementKind::ElementGroup(_) => "elementGroup",
+            PageElementKind::Shape(_) => "shape",
+            PageElementKind::Image(_) => "image",
+            PageElementKind::Video(_) => "video",
+            PageElementKind::Line(_) => "line",
+            PageElementKind::Table(_) => "table",
+            PageElementKind::WordArt(_) => "wordArt",
+            PageElementKind::SheetsChart(_) => "sheetsChart",
+            PageElementKind::SpeakerSpotlight(_) => "speakerSpotlight",
+            PageElementKind::Unknown { kind, .. } => kind,
+        }
+    }
+
+    /// Removes the element kind from the remaining fields of a page element.
+    ///
+    /// A known kind is preferred. Otherwise the first field holding an object is taken
+    /// as an unknown kind, since every element kind is an object.
+    ///
+    /// Errors name the path of the failing field below the page element, since the value
+    /// is deserialized separately from the rest of the document.
+    fn take_from(fields: &mut ExtraFields) -> Result<Option<Self>, String> {
+        let key = Self::KNOWN_KINDS
+            .iter()
+            .find(|kind| fields.contains_key(**kind))
+            .map(|kind| kind.to_string())
+            .or_else(|| {
+                fields
+                    .iter()
+                    .find(|(_, value)| value.is_object())
+                    .map(|(key, _)| key.clone())
+            });
+        let Some(kind) = key else {
+            return Ok(None);
+        };
+        let raw = fields.shift_remove(&kind).unwrap_or_default();
+        let element_kind = match kind.as_str() {
+            "elementGroup" => PageElementKind::ElementGroup(deserialize_nested(&kind, raw)?),
+            "shape" => PageElementKind::Shape(deserialize_nested(&kind, raw)?),
+            "image" => PageElementKind::Image(deserialize_nested(&kind, raw)?),
+            "video" => PageElementKind::Video(deserialize_nested(&kind, raw)?),
+            "line" => PageElementKind::Line(deserialize_nested(&kind, raw)?),
+            "table" => PageElementKind::Table(deserialize_nested(&kind, raw)?),
+            "wordArt" => PageElementKind::WordArt(deserialize_nested(&kind, raw)?),
+            "sheetsChart" => PageElementKind::SheetsChart(deserialize_nested(&kind, raw)?),
+            "speakerSpotlight" => {
+                PageElementKind::SpeakerSpotlight(deserialize_nested(&kind, raw)?)
+            }
+            _ => PageElementKind::Unknown { kind, raw },
+        };
+        Ok(Some(element_kind))
+    }
+}
+
+impl Serialize for PageElementKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(1))?;
+        match self {
+            PageElementKind::ElementGroup(group) => map.serialize_entry("elementGroup", group)?,
+            PageElementKind::Shape(shape) => map.serialize_entry("shape", shape)?,
+            PageElementKind::Image(image) => map.serialize_entry("image", image)?,
+            PageElementKind::Video(video) => map.serialize_entry("video", video)?,
+            PageElementKind::Line(line) => map.serialize_entry("line", line)?,
+            PageElementKind::Table(table) => map.serialize_entry("table", table)?,
+            PageElementKind::WordArt(word_art) => map.serialize_entry("wordArt", word_art)?,
+            PageElementKind::SheetsChart(chart) => map.serialize_entry("sheetsChart", chart)?,
+            PageElementKind::SpeakerSpotlight(spotlight) => {
+                map.serialize_entry("speakerSpotlight", spotlight)?
+            }
+            PageElementKind::Unknown { kind, raw } => map.serialize_entry(kind, raw)?,
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for PageElementKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut fields = ExtraFields::deserialize(deserializer)?;
+        PageElementKind::take_from(&mut fields)
+            .map_err(de::Error::custom)?
+            .ok_or_else(|| de::Error::custom("missing element kind field (e.g. shape, image)"))
+    }
 }
 
 /// A visual element rendered on a page.
-/// NOTE: Manual Deserialize implemented below. PartialEq likely removed due to complexity.
-#[derive(Debug, Clone, Serialize)] // Removed Deserialize derive
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations.pages#PageElement
+#[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PageElement {
     /// The object ID for this page element.
@@ -49,249 +162,111 @@ pub struct PageElement {
     /// The description of the page element.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    /// The specific kind of element and its properties.
-    // No longer flattened, handled by manual Deserialize below.
+    /// The specific kind of element and its properties, keyed by the kind
+    /// (e.g. `shape`, `image`). Kinds this library does not know become
+    /// `PageElementKind::Unknown` instead of failing the whole presentation.
+    #[serde(flatten)]
     pub element_kind: PageElementKind,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
-// --- Manual Deserialization Implementation for PageElement ---
+/// The fields of a page element, before its kind is split from the unknown fields.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageElementFields {
+    object_id: String,
+    size: Option<Size>,
+    transform: Option<AffineTransform>,
+    title: Option<String>,
+    description: Option<String>,
+    #[serde(flatten)]
+    rest: ExtraFields,
+}
 
 impl<'de> Deserialize<'de> for PageElement {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        // Define field names as constants
-        const FIELD_OBJECT_ID: &str = "objectId";
-        const FIELD_SIZE: &str = "size";
-        const FIELD_TRANSFORM: &str = "transform";
-        const FIELD_TITLE: &str = "title";
-        const FIELD_DESCRIPTION: &str = "description";
-        // Element kind fields
-        const FIELD_ELEMENT_GROUP: &str = "elementGroup";
-        const FIELD_SHAPE: &str = "shape";
-        const FIELD_IMAGE: &str = "image";
-        const FIELD_VIDEO: &str = "video";
-        const FIELD_LINE: &str = "line";
-        const FIELD_TABLE: &str = "table";
-        const FIELD_WORD_ART: &str = "wordArt";
-        const FIELD_SHEETS_CHART: &str = "sheetsChart";
-        const FIELD_SPEAKER_SPOTLIGHT: &str = "speakerSpotlight";
-
-        // Visitor implementation
-        struct PageElementVisitor;
-
-        impl<'de> Visitor<'de> for PageElementVisitor {
-            type Value = PageElement;
-
-            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("struct PageElement")
-            }
-
-            fn visit_map<V>(self, mut map: V) -> Result<PageElement, V::Error>
-            where
-                V: MapAccess<'de>,
-            {
-                // Local variables captured from outer scope
-                let mut object_id: Option<String> = None;
-                let mut size: Option<Size> = None;
-                let mut transform: Option<AffineTransform> = None;
-                let mut title: Option<String> = None;
-                let mut description: Option<String> = None;
-                let mut element_kind: Option<PageElementKind> = None;
+        let mut fields = PageElementFields::deserialize(deserializer)?;
+        let element_kind = PageElementKind::take_from(&mut fields.rest)
+            .map_err(de::Error::custom)?
+            .ok_or_else(|| {
+                de::Error::custom(format!(
+                    "missing element kind field (e.g. shape, image) in page element '{}'",
+                    fields.object_id
+                ))
+            })?;
+        Ok(PageElement {
+            object_id: fields.object_id,
+            size: fields.size,
+            transform: fields.transform,
+            title: fields.title,
+            description: fields.description,
+            element_kind,
+            extra: fields.rest,
+        })
+    }
+}
 
-                // println!("--- Deserializing PageElement ---"); // Add trace
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converters::svg::convert_presentation_to_svg;
+    use crate::models::presentation::Presentation;
+    use crate::models::shape::ShapeType;
 
-                // Iterate over map keys
-                while let Some(key) = map.next_key::<String>()? {
-                    // println!("Found key: {}", key); // Print each key encountered
-                    match key.as_str() {
-                        FIELD_OBJECT_ID => {
-                            if object_id.is_some() {
-                                return Err(de::Error::duplicate_field(FIELD_OBJECT_ID));
-                            }
-                            object_id = Some(map.next_value()?);
-                        }
-                        FIELD_SIZE => {
-                            if size.is_some() {
-                                return Err(de::Error::duplicate_field(FIELD_SIZE));
-                            }
-                            size = Some(map.next_value()?);
-                        }
-                        FIELD_TRANSFORM => {
-                            if transform.is_some() {
-                                return Err(de::Error::duplicate_field(FIELD_TRANSFORM));
-                            }
-                            transform = Some(map.next_value()?);
-                        }
-                        FIELD_TITLE => {
-                            if title.is_some() {
-                                return Err(de::Error::duplicate_field(FIELD_TITLE));
-                            }
-                            title = Some(map.next_value()?);
-                        }
-                        FIELD_DESCRIPTION => {
-                            if description.is_some() {
-                                return Err(de::Error::duplicate_field(FIELD_DESCRIPTION));
-                            }
-                            description = Some(map.next_value()?);
-                        }
-                        // Element Kind handling: Check if already found, then deserialize value
-                        FIELD_ELEMENT_GROUP => {
-                            // println!("Matched key: {}", FIELD_ELEMENT_GROUP); // Add trace
-                            if element_kind.is_some() { /* ... error ... */ }
-                            match map.next_value() {
-                                // Explicitly handle result for debugging
-                                Ok(value) => {
-                                    element_kind = Some(PageElementKind::ElementGroup(value))
-                                }
-                                Err(e) => {
-                                    eprintln!("Error deserializing ElementGroup: {}", e);
-                                    return Err(e); // Propagate the actual error
-                                }
-                            }
-                        }
-                        FIELD_SHAPE => {
-                            // println!("Matched key: {}", FIELD_SHAPE); // Add trace
-                            if element_kind.is_some() { /* ... error ... */ }
-                            match map.next_value() {
-                                // Explicitly handle result for debugging
-                                Ok(value) => element_kind = Some(PageElementKind::Shape(value)),
-                                Err(e) => {
-                                    eprintln!("Error deserializing Shape: {}", e);
-                                    return Err(e); // Propagate the actual error
-                                }
-                            }
-                        }
-                        // --- Repeat the pattern for ALL element kind fields ---
-                        FIELD_IMAGE => {
-                            // println!("Matched key: {}", FIELD_IMAGE);
-                            if element_kind.is_some() { /* ... error ... */ }
-                            match map.next_value() {
-                                Ok(value) => element_kind = Some(PageElementKind::Image(value)),
-                                Err(e) => {
-                                    eprintln!("Error deserializing Image: {}", e);
-                                    return Err(e);
-                                }
-                            }
-                        }
-                        FIELD_VIDEO => {
-                            // println!("Matched key: {}", FIELD_VIDEO);
-                            if element_kind.is_some() { /* ... error ... */ }
-                            match map.next_value() {
-                                Ok(value) => element_kind = Some(PageElementKind::Video(value)),
-                                Err(e) => {
-                                    eprintln!("Error deserializing Video: {}", e);
-                                    return Err(e);
-                                }
-                            }
-                        }
-                        FIELD_LINE => {
-                            // println!("Matched key: {}", FIELD_LINE);
-                            if element_kind.is_some() { /* ... error ... */ }
-                            match map.next_value() {
-                                Ok(value) => element_kind = Some(PageElementKind::Line(value)),
-                                Err(e) => {
-                                    eprintln!("Error deserializing Line: {}", e);
-                                    return Err(e);
-                                }
-                            }
-                        }
-                        FIELD_TABLE => {
-                            // println!("Matched key: {}", FIELD_TABLE);
-                            if element_kind.is_some() { /* ... error ... */ }
-                            match map.next_value() {
-                                Ok(value) => element_kind = Some(PageElementKind::Table(value)),
-                                Err(e) => {
-                                    eprintln!("Error deserializing Table: {}", e);
-                                    return Err(e);
-                                }
-                            }
-                        }
-                        FIELD_WORD_ART => {
-                            // println!("Matched key: {}", FIELD_WORD_ART);
-                            if element_kind.is_some() { /* ... error ... */ }
-                            match map.next_value() {
-                                Ok(value) => element_kind = Some(PageElementKind::WordArt(value)),
-                                Err(e) => {
-                                    eprintln!("Error deserializing WordArt: {}", e);
-                                    return Err(e);
-                                }
-                            }
-                        }
-                        FIELD_SHEETS_CHART => {
-                            // println!("Matched key: {}", FIELD_SHEETS_CHART);
-                            if element_kind.is_some() { /* ... error ... */ }
-                            match map.next_value() {
-                                Ok(value) => {
-                                    element_kind = Some(PageElementKind::SheetsChart(value))
-                                }
-                                Err(e) => {
-                                    eprintln!("Error deserializing SheetsChart: {}", e);
-                                    return Err(e);
-                                }
-                            }
-                        }
-                        FIELD_SPEAKER_SPOTLIGHT => {
-                            // println!("Matched key: {}", FIELD_SPEAKER_SPOTLIGHT);
-                            if element_kind.is_some() { /* ... error ... */ }
-                            match map.next_value() {
-                                Ok(value) => {
-                                    element_kind = Some(PageElementKind::SpeakerSpotlight(value))
-                                }
-                                Err(e) => {
-                                    eprintln!("Error deserializing SpeakerSpotlight: {}", e);
-                                    return Err(e);
-                                }
-                            }
-                        }
-                        // Ignore unknown fields if necessary, or return an error
-                        _ => {
-                            println!("Ignoring unknown key: {}", key); // Add trace
-                            let _ = map.next_value::<serde_json::Value>()?; // Consume the value to advance map
-                                                                            // Optionally log unknown field: log::debug!("Ignoring unknown field: {}", key);
-                        }
+    #[test]
+    fn test_unknown_element_kinds_and_enum_values_are_kept() {
+        let json = r#"{
+            "presentationId": "p1",
+            "pageSize": {
+                "width": {"magnitude": 9144000, "unit": "EMU"},
+                "height": {"magnitude": 5143500, "unit": "EMU"}
+            },
+            "slides": [{
+                "objectId": "s1",
+                "pageElements": [
+                    {
+                        "objectId": "e1",
+                        "size": {
+                            "width": {"magnitude": 3000000, "unit": "EMU"},
+                            "height": {"magnitude": 1000000, "unit": "EMU"}
+                        },
+                        "hologram": {"depth": 3}
+                    },
+                    {
+                        "objectId": "e2",
+                        "shape": {"shapeType": "HEXAGRAM_STAR"}
                     }
-                }
-
-                // println!("--- Finished processing keys for PageElement ---");
-                // println!("Final element_kind is Some: {}", element_kind.is_some());
-
-                // Check required fields and construct PageElement
-                let object_id =
-                    object_id.ok_or_else(|| de::Error::missing_field(FIELD_OBJECT_ID))?;
-                let element_kind = element_kind.ok_or_else(|| {
-                    de::Error::custom("Missing element kind field (e.g., shape, image)")
-                })?;
-
-                Ok(PageElement {
-                    object_id,
-                    size,
-                    transform,
-                    title,
-                    description,
-                    element_kind,
-                })
-            }
-        }
+                ]
+            }]
+        }"#;
+        let presentation: Presentation = serde_json::from_str(json).unwrap();
+        let elements = presentation.slides.as_ref().unwrap()[0]
+            .page_elements
+            .as_ref()
+            .unwrap();
+        let PageElementKind::Unknown { kind, raw } = &elements[0].element_kind else {
+            panic!("expected an unknown element kind");
+        };
+        assert_eq!(kind, "hologram");
+        assert_eq!(raw, &serde_json::json!({"depth": 3}));
+        let PageElementKind::Shape(shape) = &elements[1].element_kind else {
+            panic!("expected a shape");
+        };
+        assert_eq!(
+            shape.shape_type,
+            Some(ShapeType::Unknown("HEXAGRAM_STAR".to_string()))
+        );
+        assert_eq!(
+            serde_json::to_value(&presentation).unwrap(),
+            serde_json::from_str::<Value>(json).unwrap()
+        );
 
-        // Define the fields PageElement expects
-        const FIELDS: &[&str] = &[
-            FIELD_OBJECT_ID,
-            FIELD_SIZE,
-            FIELD_TRANSFORM,
-            FIELD_TITLE,
-            FIELD_DESCRIPTION,
-            FIELD_ELEMENT_GROUP,
-            FIELD_SHAPE,
-            FIELD_IMAGE,
-            FIELD_VIDEO,
-            FIELD_LINE,
-            FIELD_TABLE,
-            FIELD_WORD_ART,
-            FIELD_SHEETS_CHART,
-            FIELD_SPEAKER_SPOTLIGHT,
-        ];
-        deserializer.deserialize_struct("PageElement", FIELDS, PageElementVisitor)
+        let svgs = convert_presentation_to_svg(&presentation).unwrap();
+        assert!(svgs[0].contains("Unknown (hologram) Placeholder"));
     }
 }
@@ -1,3 +1,4 @@
+use crate::models::common::ExtraFields;
 use serde::{Deserialize, Serialize};
 
 /// Represents a font family and weight used to style a TextRun.
@@ -17,4 +18,7 @@ pub struct WeightedFontFamily {
     /// to CSS font-weight values. Default is 400 ("normal"). Weights >= 700 are bold.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub weight: Option<i32>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
@@ -1,3 +1,4 @@
+use crate::models::common::ExtraFields;
 use serde::{Deserialize, Serialize};
 
 // Import PageElement as Group contains children of this type
@@ -11,4 +12,7 @@ use crate::models::elements::PageElement;
 pub struct Group {
     /// The collection of elements in the group.
     pub children: Vec<PageElement>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
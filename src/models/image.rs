@@ -1,3 +1,4 @@
+use crate::models::common::ExtraFields;
 use serde::{Deserialize, Serialize};
 
 // Import necessary types
@@ -26,4 +27,7 @@ pub struct Image {
     /// The placeholder information for the image. If set, the image is a placeholder image.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub placeholder: Option<Placeholder>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
@@ -1,3 +1,4 @@
+use crate::models::common::ExtraFields;
 use serde::{Deserialize, Serialize};
 
 // Import necessary types
@@ -13,14 +14,17 @@ use crate::models::shape_properties::{Outline, Shadow}; // Reusing Outline and S
 pub struct ImageProperties {
     /// The brightness effect of the image. Value between -1.0 and 1.0. Read-only.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub brightness: Option<f32>, // Read-only
 
     /// The contrast effect of the image. Value between -1.0 and 1.0. Read-only.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub contrast: Option<f32>, // Read-only
 
     /// The transparency effect of the image. Value between 0.0 and 1.0. Read-only.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub transparency: Option<f32>, // Read-only
 
     /// The crop properties of the image. If not set, image is not cropped. Read-only.
@@ -42,6 +46,9 @@ pub struct ImageProperties {
     /// The recolor effect of the image. If not set, image is not recolored. Read-only.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub recolor: Option<Recolor>, // Read-only
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 // NOTE: Placeholder structs CropProperties and Recolor need definition,
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 // Import necessary types from other modules
-use crate::models::common::Dimension;
+use crate::models::common::{Dimension, ExtraFields};
 use crate::models::link::Link;
 use crate::models::shape_properties::{DashStyle, SolidFill}; // DashStyle already defined
 
@@ -32,6 +32,9 @@ pub enum ArrowStyle {
     OpenSquare,
     /// Hollow diamond arrow head.
     OpenDiamond,
+    /// An arrow style this library does not know yet, kept as the API sent it.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Properties for one end of a Line connection.
@@ -46,6 +49,9 @@ pub struct LineConnection {
     /// Refer to the API guide for connection site indices.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub connection_site_index: Option<i32>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// The fill properties for a Line. Currently only solid fill is supported.
@@ -63,6 +69,9 @@ pub struct LineFill {
     /// The specific fill type. Uses flatten to represent the union based on JSON key.
     #[serde(flatten)]
     pub fill_kind: LineFillContent,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// The type of the line. Corresponds to ECMA-376 ST_ShapeType connector types.
@@ -93,6 +102,9 @@ pub enum LineType {
     CurvedConnector_5,
     /// Straight line (not a connector).
     StraightLine,
+    /// A line type this library does not know yet, kept as the API sent it.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// The category of the line. Matches the category specified in CreateLineRequest.
@@ -141,6 +153,9 @@ pub struct LineProperties {
     /// Only valid for connector types.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub end_connection: Option<LineConnection>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// A PageElement kind representing a line (connector or non-connector).
@@ -158,4 +173,7 @@ pub struct Line {
     // It might not always be populated in GET responses, hence optional here.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub line_category: Option<LineCategory>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
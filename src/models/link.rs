@@ -1,20 +1,5 @@
-use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
-
-impl Serialize for Link {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        match &self.destination {
-            LinkKind::None => {
-                // If the destination is LinkKind::None, serialize an empty JSON object {}
-                let map = serializer.serialize_map(Some(0))?;
-                map.end()
-            }
-            other_kind => other_kind.serialize(serializer),
-        }
-    }
-}
+use crate::models::common::ExtraFields;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Describes the type of relative link between slides.
 /// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations.pages/other#RelativeSlideLink
@@ -65,10 +50,73 @@ impl Default for LinkKind {
 
 /// A hypertext link.
 /// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations.pages/other#Link
-#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Link {
-    /// The destination of the link. Uses flatten to represent the union based on JSON key.
-    #[serde(flatten)]
+    /// The destination of the link. `LinkKind::None` is written as an empty object.
     pub destination: LinkKind,
+    /// Fields of the JSON object that are not modeled above.
+    pub extra: ExtraFields,
+}
+
+/// The JSON shape of a `Link`: at most one destination key, plus unknown fields.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LinkFields {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    relative_link: Option<RelativeSlideLink>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    page_object_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    slide_index: Option<i32>,
+    #[serde(flatten)]
+    extra: ExtraFields,
+}
+
+impl Serialize for Link {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut fields = LinkFields {
+            url: None,
+            relative_link: None,
+            page_object_id: None,
+            slide_index: None,
+            extra: self.extra.clone(),
+        };
+        match &self.destination {
+            LinkKind::None => {}
+            LinkKind::Url(url) => fields.url = Some(url.clone()),
+            LinkKind::RelativeLink(link) => fields.relative_link = Some(link.clone()),
+            LinkKind::PageObjectId(id) => fields.page_object_id = Some(id.clone()),
+            LinkKind::SlideIndex(index) => fields.slide_index = Some(*index),
+        }
+        fields.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Link {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let fields = LinkFields::deserialize(deserializer)?;
+        let destination = if let Some(url) = fields.url {
+            LinkKind::Url(url)
+        } else if let Some(link) = fields.relative_link {
+            LinkKind::RelativeLink(link)
+        } else if let Some(id) = fields.page_object_id {
+            LinkKind::PageObjectId(id)
+        } else if let Some(index) = fields.slide_index {
+            LinkKind::SlideIndex(index)
+        } else {
+            LinkKind::None
+        };
+        Ok(Link {
+            destination,
+            extra: fields.extra,
+        })
+    }
 }
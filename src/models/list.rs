@@ -1,5 +1,6 @@
+use crate::models::common::ExtraFields;
 use indexmap::IndexMap;
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize};
 
 // Import TextStyle needed for NestingLevel's bulletStyle
 use crate::models::properties::TextStyle;
@@ -12,6 +13,9 @@ pub struct NestingLevel {
     /// The style of a bullet at this level of nesting.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bullet_style: Option<TextStyle>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// A List describes the look and feel of bullets belonging to paragraphs associated with a list ID.
@@ -24,6 +28,36 @@ pub struct List {
 
     /// A map of nesting levels (0-8) to the properties of bullets at the associated level.
     /// The keys are integers representing the nesting level.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_nesting_levels"
+    )]
     pub nesting_level: Option<IndexMap<i32, NestingLevel>>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// Parses the nesting level keys, which are JSON strings such as `"0"`, explicitly: serde
+/// cannot turn a string key into an integer once the list has been buffered, as it is
+/// inside a flattened page element.
+fn deserialize_nesting_levels<'de, D>(
+    deserializer: D,
+) -> Result<Option<IndexMap<i32, NestingLevel>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let Some(levels) = Option::<IndexMap<String, NestingLevel>>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    levels
+        .into_iter()
+        .map(|(key, level)| {
+            key.parse()
+                .map(|key| (key, level))
+                .map_err(|_| de::Error::custom(format!("invalid nesting level key '{}'", key)))
+        })
+        .collect::<Result<_, _>>()
+        .map(Some)
 }
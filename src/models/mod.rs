@@ -1,6 +1,7 @@
 pub mod bullet;
 pub mod colors;
 pub mod common;
+pub mod drive;
 pub mod elements;
 pub mod font;
 pub mod group;
@@ -15,6 +16,8 @@ pub mod picture;
 pub mod placeholder;
 pub mod presentation;
 pub mod properties;
+pub mod requests;
+pub mod responses;
 pub mod shape;
 pub mod shape_properties;
 pub mod sheets_chart;
@@ -23,5 +26,6 @@ pub mod table;
 pub mod table_properties;
 pub mod text;
 pub mod text_element;
+pub mod thumbnail;
 pub mod video;
 pub mod wordart;
@@ -1,15 +1,13 @@
 // src/models/page.rs
 
+use crate::models::common::ExtraFields;
 use serde::{Deserialize, Serialize};
 
 // Import necessary structs
 use crate::models::elements::PageElement;
 use crate::models::page_properties::PageProperties; // Renamed from 'properties' for clarity if desired, ensure import matches file name
 use crate::models::properties::{
-    LayoutProperties,
-    MasterProperties,
-    NotesProperties,
-    SlideProperties,
+    LayoutProperties, MasterProperties, NotesProperties, SlideProperties,
 };
 
 /// The type of the page.
@@ -46,6 +44,7 @@ pub struct Page {
     pub page_type: Option<PageType>,
 
     /// The page elements rendered on the page. Use `pageElements.get` to retrieve elements.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub page_elements: Option<Vec<PageElement>>,
 
     /// Output only. The revision ID of the presentation containing the page. Can be used in
@@ -77,4 +76,7 @@ pub struct Page {
     /// Note: Masters inherit properties from PageProperties. Specific MasterProperties might be minimal.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub master_properties: Option<MasterProperties>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
@@ -1,3 +1,4 @@
+use crate::models::common::ExtraFields;
 use serde::{Deserialize, Serialize};
 
 // Import necessary types
@@ -27,6 +28,9 @@ pub struct PageBackgroundFill {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stretched_picture_fill: Option<StretchedPictureFill>,
     // Note: The API represents this as optional fields rather than a strict union key.
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// The properties of a Page. Inherited properties are represented as unset fields.
@@ -46,4 +50,7 @@ pub struct PageProperties {
     /// Only the concrete colors of the first 12 ThemeColorTypes are editable.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub color_scheme: Option<ColorScheme>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
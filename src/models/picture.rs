@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 // Import common types
-use crate::models::common::Size;
+use crate::models::common::{ExtraFields, Size};
 // Import color types needed for ColorStop
 use crate::models::colors::OpaqueColor;
 
@@ -22,6 +22,9 @@ pub struct StretchedPictureFill {
     /// The original size of the picture fill. Read-only.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub size: Option<Size>, // Read-only
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// The crop properties of an object enclosed in a container (e.g., an Image).
@@ -35,23 +38,31 @@ pub struct CropProperties {
     /// The offset specifies the left edge of the crop rectangle relative to the
     /// left edge of the object's original bounding box.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub left_offset: Option<f32>,
     /// The offset specifies the right edge of the crop rectangle relative to the
     /// right edge of the object's original bounding box.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub right_offset: Option<f32>,
     /// The offset specifies the top edge of the crop rectangle relative to the
     /// top edge of the object's original bounding box.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub top_offset: Option<f32>,
     /// The offset specifies the bottom edge of the crop rectangle relative to the
     /// bottom edge of the object's original bounding box.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub bottom_offset: Option<f32>,
     /// The rotation angle of the crop window around its center, in radians.
     /// Rotation angle is applied after the offset.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub angle: Option<f32>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// A color and position in a gradient band. Used for Recolor effects.
@@ -64,10 +75,15 @@ pub struct ColorStop {
     pub color: Option<OpaqueColor>,
     /// The alpha value of this color in the gradient band. Defaults to 1.0 (fully opaque).
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub alpha: Option<f32>,
     /// The relative position of the color stop in the gradient band (0.0 to 1.0).
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub position: Option<f32>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// A recolor effect applied on an image. This property is read-only.
@@ -82,5 +98,8 @@ pub struct Recolor {
 
     /// The recolor effect represented by a gradient of color stops. Read-only.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub recolor_stops: Option<Vec<ColorStop>,>, // Read-only
+    pub recolor_stops: Option<Vec<ColorStop>>, // Read-only
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
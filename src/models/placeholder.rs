@@ -1,3 +1,4 @@
+use crate::models::common::ExtraFields;
 use serde::{Deserialize, Serialize};
 
 /// The type of placeholder. Helps identify the relationship between a shape on a slide
@@ -40,6 +41,9 @@ pub enum PlaceholderType {
     Title,
     /// Slide image placeholder (usually on notes master).
     SlideImage,
+    /// A placeholder type this library does not know yet, kept as the API sent it.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// The placeholder information that uniquely identifies a placeholder shape.
@@ -61,4 +65,7 @@ pub struct Placeholder {
     /// from any other shape.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parent_object_id: Option<String>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
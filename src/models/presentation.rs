@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 // Import common types from the common.rs file
-use crate::models::common::Size;
+use crate::models::common::{ExtraFields, Size};
 // Import the Page struct (defined in src/models/page.rs)
 use crate::models::page::Page;
 
@@ -18,7 +18,7 @@ pub struct Presentation {
 
     /// The slides in the presentation. A slide inherits properties from a slide layout.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub slides: Option<Vec<Page>,>,
+    pub slides: Option<Vec<Page>>,
 
     /// The title of the presentation.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -32,13 +32,13 @@ pub struct Presentation {
     /// - Any other shapes on the master slide appear on all slides using that master,
     ///   regardless of their layout.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub masters: Option<Vec<Page>,>,
+    pub masters: Option<Vec<Page>>,
 
     /// The layouts in the presentation. A layout is a base that determines
     /// how content is arranged and styled on the slides that inherit from that
     /// layout.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub layouts: Option<Vec<Page>,>,
+    pub layouts: Option<Vec<Page>>,
 
     /// The locale of the presentation, as an IETF BCP 47 language tag (e.g., "en-US").
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -63,4 +63,46 @@ pub struct Presentation {
     /// The notes master is read-only.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notes_master: Option<Page>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn test_presentation_json_round_trips_unchanged() {
+        for path in ["base_presentation.json", "changed_presentation.json"] {
+            let json = std::fs::read_to_string(path).unwrap();
+            let original: Value = serde_json::from_str(&json).unwrap();
+            let presentation: Presentation = serde_json::from_str(&json).unwrap();
+            assert_eq!(
+                serde_json::to_value(&presentation).unwrap(),
+                original,
+                "{}",
+                path
+            );
+        }
+
+        let json = r#"{
+            "presentationId": "p1",
+            "futureField": {"nested": [1, 2]},
+            "slides": [{
+                "objectId": "s1",
+                "pageElements": [{
+                    "objectId": "e1",
+                    "shape": {"shapeType": "TEXT_BOX", "shapeProperties": {"link": {}}},
+                    "newElementField": true
+                }]
+            }]
+        }"#;
+        let presentation: Presentation = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            serde_json::to_value(&presentation).unwrap(),
+            serde_json::from_str::<Value>(json).unwrap()
+        );
+    }
 }
@@ -3,7 +3,7 @@
 use serde::{Deserialize, Serialize};
 
 // Import common types
-use crate::models::common::Dimension;
+use crate::models::common::{Dimension, ExtraFields};
 
 // Import dependent types
 use crate::models::colors::{ColorScheme, OptionalColor};
@@ -88,6 +88,9 @@ pub struct TextStyle {
     /// from `font_family`. This property is read-only.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub weighted_font_family: Option<WeightedFontFamily>, // Read-only
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// The text alignment for a paragraph.
@@ -163,6 +166,7 @@ pub struct ParagraphStyle {
 
     /// The amount of space between lines, as a percentage of normal (100.0 corresponds to 100%).
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub line_spacing: Option<f32>,
 
     /// The amount of extra space above the paragraph.
@@ -176,6 +180,9 @@ pub struct ParagraphStyle {
     /// The spacing mode for the paragraph (COLLAPSE_LISTS or NEVER_COLLAPSE).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub spacing_mode: Option<SpacingMode>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// The properties of a Page common to all page types.
@@ -194,6 +201,9 @@ pub struct PageProperties {
     /// Slides color scheme. Only the first 12 `ThemeColorType`s are editable.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub color_scheme: Option<ColorScheme>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 // --- Structs for Specific Page Types ---
@@ -222,6 +232,9 @@ pub struct SlideProperties {
     /// Whether the slide is skipped in the presentation mode. Defaults to false.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_skipped: Option<bool>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// The properties specific to a page with type `LAYOUT`.
@@ -238,6 +251,9 @@ pub struct LayoutProperties {
     /// Output only. The human-readable name of the layout (e.g., "Title and body").
     #[serde(skip_serializing_if = "Option::is_none")]
     pub display_name: Option<String>, // Read-only
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// The properties specific to a page with type `NOTES`.
@@ -250,6 +266,9 @@ pub struct NotesProperties {
     /// The `GetPresentation` or `GetPage` action will always return the latest object ID for this shape.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub speaker_notes_object_id: Option<String>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// The properties specific to a page with type `MASTER`.
@@ -260,4 +279,7 @@ pub struct MasterProperties {
     /// Output only. The human-readable name of the master.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub display_name: Option<String>, // Read-only
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
@@ -0,0 +1,855 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// Import necessary types from other modules
+use crate::models::common::{AffineTransform, Size};
+use crate::models::line::LineCategory;
+use crate::models::page_properties::PageProperties;
+use crate::models::placeholder::Placeholder;
+use crate::models::properties::{ParagraphStyle, TextStyle};
+use crate::models::shape::ShapeType;
+use crate::models::shape_properties::ShapeProperties;
+use crate::models::table::TableCellLocation;
+
+/// A single kind of update to apply to a presentation.
+/// Serialized as a single-key object, e.g. `{"createSlide": {...}}`.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#Request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)]
+#[serde(rename_all = "camelCase")]
+pub enum Request {
+    /// Creates a new slide.
+    CreateSlide(CreateSlideRequest),
+    /// Creates a new shape.
+    CreateShape(CreateShapeRequest),
+    /// Creates a new table.
+    CreateTable(CreateTableRequest),
+    /// Creates an image.
+    CreateImage(CreateImageRequest),
+    /// Creates a line.
+    CreateLine(CreateLineRequest),
+    /// Inserts text into a shape or table cell.
+    InsertText(InsertTextRequest),
+    /// Deletes text from a shape or a table cell.
+    DeleteText(DeleteTextRequest),
+    /// Replaces all instances of specified text.
+    ReplaceAllText(ReplaceAllTextRequest),
+    /// Deletes a page or page element from the presentation.
+    DeleteObject(DeleteObjectRequest),
+    /// Duplicates a slide or page element.
+    DuplicateObject(DuplicateObjectRequest),
+    /// Updates the styling of text within a Shape or Table.
+    UpdateTextStyle(UpdateTextStyleRequest),
+    /// Updates the styling of paragraphs within a Shape or Table.
+    UpdateParagraphStyle(UpdateParagraphStyleRequest),
+    /// Updates the properties of a Shape.
+    UpdateShapeProperties(UpdateShapePropertiesRequest),
+    /// Updates the properties of a Page.
+    UpdatePageProperties(UpdatePagePropertiesRequest),
+    /// Updates the transform of a page element.
+    UpdatePageElementTransform(UpdatePageElementTransformRequest),
+    /// Updates the alt text title and/or description of a page element.
+    UpdatePageElementAltText(UpdatePageElementAltTextRequest),
+    /// Updates the position of slides in the presentation.
+    UpdateSlidesPosition(UpdateSlidesPositionRequest),
+    /// Creates bullets for paragraphs.
+    CreateParagraphBullets(CreateParagraphBulletsRequest),
+    /// Deletes bullets from paragraphs.
+    DeleteParagraphBullets(DeleteParagraphBulletsRequest),
+    /// Inserts rows into a table.
+    InsertTableRows(InsertTableRowsRequest),
+    /// Inserts columns into a table.
+    InsertTableColumns(InsertTableColumnsRequest),
+    /// Deletes a row from a table.
+    DeleteTableRow(DeleteTableRowRequest),
+    /// Deletes a column from a table.
+    DeleteTableColumn(DeleteTableColumnRequest),
+    /// Replaces all shapes matching some criteria with an image.
+    ReplaceAllShapesWithImage(ReplaceAllShapesWithImageRequest),
+    /// Replaces an existing image with a new image.
+    ReplaceImage(ReplaceImageRequest),
+    /// Groups objects, such as page elements.
+    GroupObjects(GroupObjectsRequest),
+    /// Ungroups objects, such as groups.
+    UngroupObjects(UngroupObjectsRequest),
+}
+
+/// Provides control over how write requests are executed.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/batchUpdate#WriteControl
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteControl {
+    /// The revision ID of the presentation required for the write request.
+    /// If specified and the required revision ID doesn't match the presentation's
+    /// current revision ID, the request is not processed and returns a 400 bad request error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required_revision_id: Option<String>,
+}
+
+/// The request body of `presentations.batchUpdate`.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/batchUpdate#request-body
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchUpdatePresentationRequest {
+    /// A list of updates to apply to the presentation.
+    pub requests: Vec<Request>,
+
+    /// Provides control over how write requests are executed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub write_control: Option<WriteControl>,
+}
+
+// --- Shared request types ---
+
+/// Common properties for a page element being created.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#PageElementProperties
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageElementProperties {
+    /// The object ID of the page where the element is located.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_object_id: Option<String>,
+
+    /// The size of the element.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<Size>,
+
+    /// The transform for the element.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transform: Option<AffineTransform>,
+}
+
+/// The type of a text range.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#Type_2
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RangeType {
+    /// Unspecified range type. This value must not be used.
+    RangeTypeUnspecified,
+    /// A fixed range. Both the `start_index` and `end_index` must be specified.
+    FixedRange,
+    /// Starts the range at `start_index` and continues until the end of the collection.
+    FromStartIndex,
+    /// Sets the range to be the whole length of the collection.
+    #[default]
+    All,
+}
+
+/// Specifies a contiguous range of an indexed collection, such as characters in text.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#Range
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Range {
+    /// The optional zero-based index of the beginning of the collection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_index: Option<u32>,
+
+    /// The optional zero-based index of the end of the collection (exclusive).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_index: Option<u32>,
+
+    /// The type of range.
+    #[serde(rename = "type")]
+    pub range_type: RangeType,
+}
+
+/// A criteria that matches a specific string of text in a shape or table.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#SubstringMatchCriteria
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubstringMatchCriteria {
+    /// The text to search for in the shape or table.
+    pub text: String,
+
+    /// Indicates whether the search should respect case.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub match_case: Option<bool>,
+
+    /// True if the find value should be treated as a regular expression.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_by_regex: Option<bool>,
+}
+
+// --- Slide requests ---
+
+/// Predefined layouts. These are commonly found layouts in presentations.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#PredefinedLayout
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PredefinedLayout {
+    PredefinedLayoutUnspecified,
+    Blank,
+    CaptionOnly,
+    Title,
+    TitleAndBody,
+    TitleAndTwoColumns,
+    TitleOnly,
+    SectionHeader,
+    SectionTitleAndDescription,
+    OneColumnText,
+    MainPoint,
+    BigNumber,
+}
+
+/// Slide layout reference. This may reference either a predefined layout or a layout ID.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#LayoutReference
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LayoutReference {
+    /// Predefined layout.
+    PredefinedLayout(PredefinedLayout),
+    /// Layout ID: the object ID of one of the layouts in the presentation.
+    LayoutId(String),
+}
+
+/// The user-specified ID mapping for a placeholder that will be created on a slide from a specified layout.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#LayoutPlaceholderIdMapping
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutPlaceholderIdMapping {
+    /// A user-supplied object ID for the placeholder identified above that to be created onto a slide.
+    pub object_id: String,
+
+    /// The placeholder on a layout that will be applied to a slide.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layout_placeholder: Option<Placeholder>,
+
+    /// The object ID of the placeholder on a layout that will be applied to a slide.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layout_placeholder_object_id: Option<String>,
+}
+
+/// Creates a slide.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#CreateSlideRequest
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSlideRequest {
+    /// A user-supplied object ID. If unspecified, an ID is generated by the API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_id: Option<String>,
+
+    /// The optional zero-based index indicating where to insert the slides.
+    /// If unspecified, the slide is created at the end.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub insertion_index: Option<i32>,
+
+    /// Layout reference of the slide to be inserted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slide_layout_reference: Option<LayoutReference>,
+
+    /// An optional list of object ID mappings from the placeholder(s) on the layout
+    /// to the placeholders that are created on the slide.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placeholder_id_mappings: Option<Vec<LayoutPlaceholderIdMapping>>,
+}
+
+/// Updates the position of slides in the presentation.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#UpdateSlidesPositionRequest
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSlidesPositionRequest {
+    /// The IDs of the slides in the presentation that should be moved.
+    pub slide_object_ids: Vec<String>,
+
+    /// The index where the slides should be inserted, based on the slide arrangement before the move.
+    pub insertion_index: i32,
+}
+
+/// Updates the properties of a Page.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#UpdatePagePropertiesRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePagePropertiesRequest {
+    /// The object ID of the page the update is applied to.
+    pub object_id: String,
+
+    /// The page properties to update.
+    pub page_properties: PageProperties,
+
+    /// The fields that should be updated (field mask, e.g. `"pageBackgroundFill"`).
+    pub fields: String,
+}
+
+// --- Page element creation requests ---
+
+/// Creates a new shape.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#CreateShapeRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateShapeRequest {
+    /// A user-supplied object ID. If unspecified, an ID is generated by the API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_id: Option<String>,
+
+    /// The element properties for the shape.
+    pub element_properties: PageElementProperties,
+
+    /// The shape type.
+    pub shape_type: ShapeType,
+}
+
+/// Creates a new table.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#CreateTableRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTableRequest {
+    /// A user-supplied object ID. If unspecified, an ID is generated by the API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_id: Option<String>,
+
+    /// The element properties for the table.
+    pub element_properties: PageElementProperties,
+
+    /// Number of rows in the table.
+    pub rows: i32,
+
+    /// Number of columns in the table.
+    pub columns: i32,
+}
+
+/// Creates an image.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#CreateImageRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateImageRequest {
+    /// A user-supplied object ID. If unspecified, an ID is generated by the API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_id: Option<String>,
+
+    /// The element properties for the image.
+    pub element_properties: PageElementProperties,
+
+    /// The image URL. The image is fetched once at insertion time.
+    pub url: String,
+}
+
+/// Creates a line.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#CreateLineRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateLineRequest {
+    /// A user-supplied object ID. If unspecified, an ID is generated by the API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_id: Option<String>,
+
+    /// The element properties for the line.
+    pub element_properties: PageElementProperties,
+
+    /// The category of the line to be created.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<LineCategory>,
+}
+
+// --- Text requests ---
+
+/// Inserts text into a shape or a table cell.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#InsertTextRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InsertTextRequest {
+    /// The object ID of the shape or table where the text will be inserted.
+    pub object_id: String,
+
+    /// The optional table cell location if the text is to be inserted into a table cell.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cell_location: Option<TableCellLocation>,
+
+    /// The text to be inserted.
+    pub text: String,
+
+    /// The index where the text will be inserted, in Unicode code units.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub insertion_index: Option<u32>,
+}
+
+/// Deletes text from a shape or a table cell.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#DeleteTextRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteTextRequest {
+    /// The object ID of the shape or table from which the text will be deleted.
+    pub object_id: String,
+
+    /// The optional table cell location if the text is to be deleted from a table cell.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cell_location: Option<TableCellLocation>,
+
+    /// The range of text to delete, based on TextElement indexes.
+    pub text_range: Range,
+}
+
+/// Replaces all instances of text matching a criteria with `replace_text`.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#ReplaceAllTextRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplaceAllTextRequest {
+    /// The text that will replace the matched text.
+    pub replace_text: String,
+
+    /// If non-empty, limits the matches to page elements only on the given pages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_object_ids: Option<Vec<String>>,
+
+    /// Finds text in a shape matching this substring.
+    pub contains_text: SubstringMatchCriteria,
+}
+
+/// Update the styling of text in a Shape or Table.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#UpdateTextStyleRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTextStyleRequest {
+    /// The object ID of the shape or table with the text to be styled.
+    pub object_id: String,
+
+    /// The location of the cell in the table containing the text to style.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cell_location: Option<TableCellLocation>,
+
+    /// The style(s) to set on the text.
+    pub style: TextStyle,
+
+    /// The range of text to style.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_range: Option<Range>,
+
+    /// The fields that should be updated (field mask, e.g. `"bold,fontSize"`).
+    pub fields: String,
+}
+
+/// Updates the styling for all of the paragraphs within a Shape or Table that overlap with the given text index range.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#UpdateParagraphStyleRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateParagraphStyleRequest {
+    /// The object ID of the shape or table with the text to be styled.
+    pub object_id: String,
+
+    /// The location of the cell in the table containing the paragraph(s) to style.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cell_location: Option<TableCellLocation>,
+
+    /// The paragraph's style.
+    pub style: ParagraphStyle,
+
+    /// The range of text containing the paragraph(s) to style.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_range: Option<Range>,
+
+    /// The fields that should be updated (field mask, e.g. `"alignment"`).
+    pub fields: String,
+}
+
+/// Preset patterns of bullet glyphs for lists in text.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#BulletGlyphPreset
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BulletGlyphPreset {
+    BulletGlyphPresetUnspecified,
+    BulletDiscCircleSquare,
+    BulletDiamondxArrow3dSquare,
+    BulletCheckbox,
+    BulletArrowDiamondDisc,
+    BulletStarCircleSquare,
+    BulletArrow3dCircleSquare,
+    BulletLefttriangleDiamondDisc,
+    BulletDiamondxHollowdiamondSquare,
+    BulletDiamondCircleSquare,
+    NumberedDigitAlphaRoman,
+    NumberedDigitAlphaRomanParens,
+    NumberedDigitNested,
+    NumberedUpperalphaAlphaRoman,
+    NumberedUpperromanUpperalphaDigit,
+    NumberedZerodigitAlphaRoman,
+}
+
+/// Creates bullets for all of the paragraphs that overlap with the given text index range.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#CreateParagraphBulletsRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateParagraphBulletsRequest {
+    /// The object ID of the shape or table containing the text to add bullets to.
+    pub object_id: String,
+
+    /// The optional table cell location if the text to be modified is in a table cell.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cell_location: Option<TableCellLocation>,
+
+    /// The range of text to apply the bullet presets to, based on TextElement indexes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_range: Option<Range>,
+
+    /// The kinds of bullet glyphs to be used. Defaults to `BULLET_DISC_CIRCLE_SQUARE`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bullet_preset: Option<BulletGlyphPreset>,
+}
+
+/// Deletes bullets from all of the paragraphs that overlap with the given text index range.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#DeleteParagraphBulletsRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteParagraphBulletsRequest {
+    /// The object ID of the shape or table containing the text to delete bullets from.
+    pub object_id: String,
+
+    /// The optional table cell location if the text to be modified is in a table cell.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cell_location: Option<TableCellLocation>,
+
+    /// The range of text to delete bullets from, based on TextElement indexes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_range: Option<Range>,
+}
+
+// --- Page element update requests ---
+
+/// Deletes an object, either pages or page elements, from the presentation.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#DeleteObjectRequest
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteObjectRequest {
+    /// The object ID of the page or page element to delete.
+    pub object_id: String,
+}
+
+/// Duplicates a slide or page element.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#DuplicateObjectRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateObjectRequest {
+    /// The ID of the object to duplicate.
+    pub object_id: String,
+
+    /// Optional map from the IDs of the source objects to the IDs to use for the duplicates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_ids: Option<HashMap<String, String>>,
+}
+
+/// Update the properties of a Shape.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#UpdateShapePropertiesRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateShapePropertiesRequest {
+    /// The object ID of the shape the updates are applied to.
+    pub object_id: String,
+
+    /// The shape properties to update.
+    pub shape_properties: ShapeProperties,
+
+    /// The fields that should be updated (field mask, e.g. `"shapeBackgroundFill.solidFill.color"`).
+    pub fields: String,
+}
+
+/// The apply mode of the transform update.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#ApplyMode
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ApplyMode {
+    /// Unspecified mode.
+    ApplyModeUnspecified,
+    /// Applies the new AffineTransform matrix to the existing one, and replaces the existing one with the resulting concatenation.
+    Relative,
+    /// Replaces the existing AffineTransform matrix with the new one.
+    Absolute,
+}
+
+/// Updates the transform of a page element.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#UpdatePageElementTransformRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePageElementTransformRequest {
+    /// The object ID of the page element to update.
+    pub object_id: String,
+
+    /// The input transform matrix used to update the page element.
+    pub transform: AffineTransform,
+
+    /// The apply mode of the transform update.
+    pub apply_mode: ApplyMode,
+}
+
+/// Updates the alt text title and/or description of a page element.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#UpdatePageElementAltTextRequest
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePageElementAltTextRequest {
+    /// The object ID of the page element the updates are applied to.
+    pub object_id: String,
+
+    /// The updated alt text title of the page element. If unset the existing value will be maintained.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// The updated alt text description of the page element. If unset the existing value will be maintained.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+// --- Table requests ---
+
+/// Inserts rows into a table.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#InsertTableRowsRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InsertTableRowsRequest {
+    /// The table to insert rows into.
+    pub table_object_id: String,
+
+    /// The reference table cell location from which rows will be inserted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cell_location: Option<TableCellLocation>,
+
+    /// Whether to insert new rows below the reference cell location.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub insert_below: Option<bool>,
+
+    /// The number of rows to be inserted. Maximum 20 per request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number: Option<i32>,
+}
+
+/// Inserts columns into a table.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#InsertTableColumnsRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InsertTableColumnsRequest {
+    /// The table to insert columns into.
+    pub table_object_id: String,
+
+    /// The reference table cell location from which columns will be inserted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cell_location: Option<TableCellLocation>,
+
+    /// Whether to insert new columns to the right of the reference cell location.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub insert_right: Option<bool>,
+
+    /// The number of columns to be inserted. Maximum 20 per request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number: Option<i32>,
+}
+
+/// Deletes a row from a table.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#DeleteTableRowRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteTableRowRequest {
+    /// The table to delete rows from.
+    pub table_object_id: String,
+
+    /// The reference table cell location from which a row will be deleted.
+    pub cell_location: TableCellLocation,
+}
+
+/// Deletes a column from a table.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#DeleteTableColumnRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteTableColumnRequest {
+    /// The table to delete columns from.
+    pub table_object_id: String,
+
+    /// The reference table cell location from which a column will be deleted.
+    pub cell_location: TableCellLocation,
+}
+
+// --- Image requests ---
+
+/// The image replace method.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#ImageReplaceMethod
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ImageReplaceMethod {
+    /// Unspecified image replace method. This value must not be used.
+    ImageReplaceMethodUnspecified,
+    /// Scales and centers the image to fit within the bounds of the original shape and maintains the image's aspect ratio.
+    CenterInside,
+    /// Scales and centers the image to fill the bounds of the original shape.
+    CenterCrop,
+}
+
+/// Replaces all shapes that match the given criteria with the provided image.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#ReplaceAllShapesWithImageRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplaceAllShapesWithImageRequest {
+    /// The image URL.
+    pub image_url: String,
+
+    /// The image replace method.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_replace_method: Option<ImageReplaceMethod>,
+
+    /// If non-empty, limits the matches to page elements only on the given pages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_object_ids: Option<Vec<String>>,
+
+    /// If set, this request will replace all of the shapes that contain the given text.
+    pub contains_text: SubstringMatchCriteria,
+}
+
+/// Replaces an existing image with a new image.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#ReplaceImageRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplaceImageRequest {
+    /// The ID of the existing image that will be replaced.
+    pub image_object_id: String,
+
+    /// The replacement method.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_replace_method: Option<ImageReplaceMethod>,
+
+    /// The image URL.
+    pub url: String,
+}
+
+// --- Group requests ---
+
+/// Groups objects to create an object group.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#GroupObjectsRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupObjectsRequest {
+    /// A user-supplied object ID for the group to be created.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_object_id: Option<String>,
+
+    /// The object IDs of the objects to group. At least two objects are required.
+    pub children_object_ids: Vec<String>,
+}
+
+/// Ungroups objects, such as groups.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/request#UngroupObjectsRequest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UngroupObjectsRequest {
+    /// The object IDs of the objects to ungroup.
+    pub object_ids: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::common::Unit;
+    use crate::models::placeholder::PlaceholderType;
+    use serde_json::json;
+
+    /// Checks that `request` serializes to `expected`, a request body from the API
+    /// reference, and that the body parses back to the same request.
+    fn assert_api_body(request: Request, expected: serde_json::Value) {
+        assert_eq!(serde_json::to_value(&request).unwrap(), expected);
+        assert_eq!(
+            serde_json::from_value::<Request>(expected).unwrap(),
+            request
+        );
+    }
+
+    #[test]
+    fn test_text_requests_match_api_bodies() {
+        assert_api_body(
+            Request::InsertText(InsertTextRequest {
+                object_id: "table_1".to_string(),
+                cell_location: Some(TableCellLocation {
+                    row_index: Some(1),
+                    column_index: Some(2),
+                    extra: Default::default(),
+                }),
+                text: "Kangaroo".to_string(),
+                insertion_index: Some(0),
+            }),
+            json!({
+                "insertText": {
+                    "objectId": "table_1",
+                    "cellLocation": {"rowIndex": 1, "columnIndex": 2},
+                    "text": "Kangaroo",
+                    "insertionIndex": 0
+                }
+            }),
+        );
+        assert_api_body(
+            Request::DeleteText(DeleteTextRequest {
+                object_id: "shape_1".to_string(),
+                cell_location: None,
+                text_range: Range {
+                    start_index: Some(1),
+                    end_index: None,
+                    range_type: RangeType::FromStartIndex,
+                },
+            }),
+            json!({
+                "deleteText": {
+                    "objectId": "shape_1",
+                    "textRange": {"type": "FROM_START_INDEX", "startIndex": 1}
+                }
+            }),
+        );
+    }
+
+    #[test]
+    fn test_create_slide_matches_api_body() {
+        assert_api_body(
+            Request::CreateSlide(CreateSlideRequest {
+                object_id: Some("slide_1".to_string()),
+                insertion_index: Some(1),
+                slide_layout_reference: Some(LayoutReference::PredefinedLayout(
+                    PredefinedLayout::TitleAndTwoColumns,
+                )),
+                placeholder_id_mappings: Some(vec![LayoutPlaceholderIdMapping {
+                    object_id: "slide_1_title".to_string(),
+                    layout_placeholder: Some(Placeholder {
+                        placeholder_type: Some(PlaceholderType::Title),
+                        index: Some(0),
+                        parent_object_id: None,
+                        extra: Default::default(),
+                    }),
+                    layout_placeholder_object_id: None,
+                }]),
+            }),
+            json!({
+                "createSlide": {
+                    "objectId": "slide_1",
+                    "insertionIndex": 1,
+                    "slideLayoutReference": {"predefinedLayout": "TITLE_AND_TWO_COLUMNS"},
+                    "placeholderIdMappings": [{
+                        "layoutPlaceholder": {"type": "TITLE", "index": 0},
+                        "objectId": "slide_1_title"
+                    }]
+                }
+            }),
+        );
+    }
+
+    #[test]
+    fn test_update_page_element_transform_matches_api_body() {
+        assert_api_body(
+            Request::UpdatePageElementTransform(UpdatePageElementTransformRequest {
+                object_id: "shape_1".to_string(),
+                transform: AffineTransform {
+                    scale_x: Some(1.0),
+                    scale_y: Some(1.0),
+                    shear_x: None,
+                    shear_y: None,
+                    translate_x: Some(100000.0),
+                    translate_y: Some(-50000.5),
+                    unit: Some(Unit::Emu),
+                    extra: Default::default(),
+                },
+                apply_mode: ApplyMode::Relative,
+            }),
+            json!({
+                "updatePageElementTransform": {
+                    "objectId": "shape_1",
+                    "applyMode": "RELATIVE",
+                    "transform": {
+                        "scaleX": 1,
+                        "scaleY": 1,
+                        "translateX": 100000,
+                        "translateY": -50000.5,
+                        "unit": "EMU"
+                    }
+                }
+            }),
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use serde::{Deserialize, Serialize};
+
+// Import necessary types from other modules
+use crate::models::requests::WriteControl;
+
+/// A single response from an update. Requests that produce no reply
+/// (e.g. `insertText`) are represented by `Response::Empty`.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/response#Response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Response {
+    /// The result of creating a slide.
+    CreateSlide(CreateSlideResponse),
+    /// The result of creating a shape.
+    CreateShape(CreateShapeResponse),
+    /// The result of creating a table.
+    CreateTable(CreateTableResponse),
+    /// The result of creating an image.
+    CreateImage(CreateImageResponse),
+    /// The result of creating a line.
+    CreateLine(CreateLineResponse),
+    /// The result of replacing text.
+    ReplaceAllText(ReplaceAllTextResponse),
+    /// The result of duplicating an object.
+    DuplicateObject(DuplicateObjectResponse),
+    /// The result of replacing all shapes matching some criteria with an image.
+    ReplaceAllShapesWithImage(ReplaceAllShapesWithImageResponse),
+    /// The result of grouping objects.
+    GroupObjects(GroupObjectsResponse),
+    /// An empty reply (`{}`), returned for requests without a dedicated response.
+    #[serde(untagged)]
+    Empty(EmptyResponse),
+    /// A reply of a kind not modeled above, kept as the raw JSON object.
+    #[serde(untagged)]
+    Unknown(serde_json::Map<String, serde_json::Value>),
+}
+
+/// An empty reply object. Objects with any key are not empty replies.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EmptyResponse {}
+
+/// The result of creating a slide.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/response#CreateSlideResponse
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSlideResponse {
+    /// The object ID of the created slide.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_id: Option<String>,
+}
+
+/// The result of creating a shape.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/response#CreateShapeResponse
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateShapeResponse {
+    /// The object ID of the created shape.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_id: Option<String>,
+}
+
+/// The result of creating a table.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/response#CreateTableResponse
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTableResponse {
+    /// The object ID of the created table.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_id: Option<String>,
+}
+
+/// The result of creating an image.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/response#CreateImageResponse
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateImageResponse {
+    /// The object ID of the created image.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_id: Option<String>,
+}
+
+/// The result of creating a line.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/response#CreateLineResponse
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateLineResponse {
+    /// The object ID of the created line.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_id: Option<String>,
+}
+
+/// The result of replacing text.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/response#ReplaceAllTextResponse
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplaceAllTextResponse {
+    /// The number of occurrences changed by replacing all text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub occurrences_changed: Option<i32>,
+}
+
+/// The response of duplicating an object.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/response#DuplicateObjectResponse
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateObjectResponse {
+    /// The ID of the new duplicate object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_id: Option<String>,
+}
+
+/// The result of replacing shapes with an image.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/response#ReplaceAllShapesWithImageResponse
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplaceAllShapesWithImageResponse {
+    /// The number of shapes replaced with images.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub occurrences_changed: Option<i32>,
+}
+
+/// The result of grouping objects.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/response#GroupObjectsResponse
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupObjectsResponse {
+    /// The object ID of the created group.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_id: Option<String>,
+}
+
+/// The response body of `presentations.batchUpdate`.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations/batchUpdate#response-body
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchUpdatePresentationResponse {
+    /// The presentation the updates were applied to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presentation_id: Option<String>,
+
+    /// The reply of the updates. This maps 1:1 with the updates, although replies
+    /// to some requests may be empty.
+    #[serde(default)]
+    pub replies: Vec<Response>,
+
+    /// The updated write control after applying the request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub write_control: Option<WriteControl>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_update_response_with_empty_replies() {
+        let json = r#"{
+            "presentationId": "abc",
+            "replies": [{}, {"createSlide": {"objectId": "s1"}}, {"replaceAllText": {"occurrencesChanged": 3}}],
+            "writeControl": {"requiredRevisionId": "rev2"}
+        }"#;
+        let response: BatchUpdatePresentationResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            response.replies,
+            vec![
+                Response::Empty(EmptyResponse {}),
+                Response::CreateSlide(CreateSlideResponse {
+                    object_id: Some("s1".to_string())
+                }),
+                Response::ReplaceAllText(ReplaceAllTextResponse {
+                    occurrences_changed: Some(3)
+                }),
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&response.replies[0]).unwrap(),
+            "{}".to_string()
+        );
+    }
+
+    #[test]
+    fn test_unknown_reply_is_not_empty() {
+        let json = r#"{"createVideo": {"objectId": "v1"}}"#;
+        let response: Response = serde_json::from_str(json).unwrap();
+        let Response::Unknown(raw) = &response else {
+            panic!("expected an unknown reply, got {:?}", response);
+        };
+        assert_eq!(raw["createVideo"]["objectId"], "v1");
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            serde_json::from_str::<serde_json::Value>(json).unwrap()
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::models::common::ExtraFields;
 use serde::{Deserialize, Serialize};
 
 // Import necessary types (assuming defined elsewhere)
@@ -236,6 +237,9 @@ pub enum ShapeType {
     Custom,
     // Note: The API lists many more shapes. This list captures those explicitly named in the enum documentation.
     // You might need to add more if you encounter them.
+    /// A shape type this library does not know yet, kept as the API sent it.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// A PageElement kind representing a generic shape that doesn't have a more
@@ -260,4 +264,7 @@ pub struct Shape {
     /// and inherits properties from the corresponding placeholder shape on the layout or master.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub placeholder: Option<Placeholder>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 // Import necessary types from other modules
 use crate::models::colors::OpaqueColor; // Assuming struct+flatten version is correct
-use crate::models::common::{AffineTransform, Dimension};
+use crate::models::common::{AffineTransform, Dimension, ExtraFields};
 use crate::models::link::Link;
 use crate::models::picture::StretchedPictureFill;
 
@@ -69,9 +69,14 @@ pub struct Autofit {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub autofit_type: Option<AutofitType>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub font_scale: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub line_spacing_reduction: Option<f32>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -79,7 +84,11 @@ pub struct SolidFill {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<OpaqueColor>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub alpha: Option<f32>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -96,6 +105,9 @@ impl Default for OutlineFillContent {
 pub struct OutlineFill {
     #[serde(flatten)]
     pub fill_kind: OutlineFillContent,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -116,6 +128,9 @@ pub struct ShapeBackgroundFill {
     #[serde(flatten)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fill_kind: Option<ShapeBackgroundFillContent>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -132,11 +147,15 @@ pub struct Shadow {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<OpaqueColor>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::models::common::serialize_number")]
     pub alpha: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rotate_with_shape: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub property_state: Option<PropertyState>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 // --- Outline Struct ---
@@ -151,19 +170,36 @@ pub struct Outline {
     pub dash_style: Option<DashStyle>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub property_state: Option<PropertyState>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
-// --- ShapeProperties Struct (Restored) ---
-/// The properties of a Shape element. Uses serde(default) to handle missing fields.
-#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)] // Added Default, PartialEq back
+// --- ShapeProperties Struct ---
+/// The properties of a Shape element.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations.pages/shapes#ShapeProperties
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-#[serde(default)] // Tells serde to use Default for missing fields
 pub struct ShapeProperties {
-    // --- Fields are NOT Option<> anymore ---
-    pub shape_background_fill: ShapeBackgroundFill,
-    pub outline: Outline,
-    pub shadow: Shadow,
-    pub link: Link,                          // Make sure Link derives/impls Default
-    pub content_alignment: ContentAlignment, // Make sure ContentAlignment has #[default] variant
-    pub autofit: Autofit,
+    /// The background fill of the shape.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shape_background_fill: Option<ShapeBackgroundFill>,
+    /// The outline of the shape.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outline: Option<Outline>,
+    /// The shadow properties of the shape.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shadow: Option<Shadow>,
+    /// The hyperlink destination of the shape.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<Link>,
+    /// The alignment of the content in the shape.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_alignment: Option<ContentAlignment>,
+    /// The autofit properties of the shape.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub autofit: Option<Autofit>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
@@ -1,3 +1,4 @@
+use crate::models::common::ExtraFields;
 use serde::{Deserialize, Serialize};
 
 // Import necessary types
@@ -11,6 +12,9 @@ pub struct SheetsChartProperties {
     /// The properties of the embedded chart image. Read-only.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub chart_image_properties: Option<ImageProperties>, // Read-only
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// A PageElement kind representing a linked chart embedded from Google Sheets.
@@ -35,4 +39,7 @@ pub struct SheetsChart {
     /// This URL is tagged with the account of the requester.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content_url: Option<String>, // Read-only
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
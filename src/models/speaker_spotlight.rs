@@ -1,3 +1,4 @@
+use crate::models::common::ExtraFields;
 use serde::{Deserialize, Serialize};
 
 // Import necessary types for properties
@@ -16,6 +17,9 @@ pub struct SpeakerSpotlightProperties {
     pub shadow: Option<Shadow>,
     // Note: Fill properties are not specified for SpeakerSpotlight in the current API docs.
     // It likely uses a default video feed fill.
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// A PageElement kind representing a Speaker Spotlight shape.
@@ -27,4 +31,7 @@ pub struct SpeakerSpotlight {
     /// The properties of the Speaker Spotlight.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub speaker_spotlight_properties: Option<SpeakerSpotlightProperties>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 // Import necessary types from other modules
-use crate::models::common::Dimension;
+use crate::models::common::{Dimension, ExtraFields};
 use crate::models::table_properties::{
     TableBorderRow, TableCellProperties, TableColumnProperties, TableRowProperties,
 };
@@ -18,6 +18,9 @@ pub struct TableCellLocation {
     /// The 0-based column index.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub column_index: Option<i32>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// Properties and contents of each cell.
@@ -44,6 +47,9 @@ pub struct TableCell {
     /// The properties of the table cell.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub table_cell_properties: Option<TableCellProperties>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// Properties and contents of each row in a table.
@@ -58,10 +64,13 @@ pub struct TableRow {
     /// represented only once with a column_span > 1. Cells spanning multiple
     /// rows are contained in only the topmost row and have a row_span > 1.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub table_cells: Option<Vec<TableCell>,>,
+    pub table_cells: Option<Vec<TableCell>>,
     /// Properties of the row.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub table_row_properties: Option<TableRowProperties>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// A PageElement kind representing a table.
@@ -76,17 +85,20 @@ pub struct Table {
 
     /// Properties of each column.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub table_columns: Option<Vec<TableColumnProperties>,>,
+    pub table_columns: Option<Vec<TableColumnProperties>>,
 
     /// Properties and contents of each row.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub table_rows: Option<Vec<TableRow>,>,
+    pub table_rows: Option<Vec<TableRow>>,
 
     /// Properties of horizontal cell borders. A grid with `rows + 1` rows and `columns` columns.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub horizontal_border_rows: Option<Vec<TableBorderRow>,>,
+    pub horizontal_border_rows: Option<Vec<TableBorderRow>>,
 
     /// Properties of vertical cell borders. A grid with `rows` rows and `columns + 1` columns.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub vertical_border_rows: Option<Vec<TableBorderRow>,>,
+    pub vertical_border_rows: Option<Vec<TableBorderRow>>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
@@ -1,8 +1,7 @@
 use serde::{Deserialize, Serialize};
-use serde_json::Value; // Added for the custom deserializer
 
 // Import necessary types from other modules
-use crate::models::common::Dimension;
+use crate::models::common::{Dimension, ExtraFields};
 use crate::models::shape_properties::{ContentAlignment, DashStyle, PropertyState, SolidFill}; // Reusing enums and SolidFill
 use crate::models::table::TableCellLocation; // Defined in table.rs
 
@@ -15,40 +14,19 @@ pub enum TableBorderFillContent {
     SolidFill(SolidFill),
 }
 
-// Helper function to deserialize Option<TableBorderFillContent>
-// where an empty JSON object {} is treated as None.
-fn deserialize_table_border_fill_option<'de, D>(
-    deserializer: D,
-) -> Result<Option<TableBorderFillContent>, D::Error>
-where
-    D: ::serde::Deserializer<'de>,
-{
-    let value = match Value::deserialize(deserializer) {
-        Ok(v) => v,
-        Err(e) => return Err(::serde::de::Error::custom(format!("Failed to deserialize to serde_json::Value: {}", e))),
-    };
-
-    if value.is_null() {
-        Ok(None)
-    } else if value.is_object() && value.as_object().map_or(false, |obj| obj.is_empty()) {
-        Ok(None)
-    } else {
-        match TableBorderFillContent::deserialize(value) {
-            Ok(content) => Ok(Some(content)),
-            Err(e) => Err(::serde::de::Error::custom(format!("Failed to deserialize TableBorderFillContent: {}", e))),
-        }
-    }
-}
-
-/*
+/// The fill of the border. An empty object (`{}`) has no fill kind.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations.pages/tables#TableBorderFill
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TableBorderFill {
     /// The specific fill type.
     #[serde(flatten)]
-    pub fill_kind: TableBorderFillContent,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fill_kind: Option<TableBorderFillContent>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
-*/
 
 /// The border styling properties of a TableBorderCell.
 /// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations.pages/tables#TableBorderProperties
@@ -56,17 +34,17 @@ pub struct TableBorderFill {
 #[serde(rename_all = "camelCase")]
 pub struct TableBorderProperties {
     /// The fill of the table border.
-    #[serde(
-        skip_serializing_if = "Option::is_none",
-        deserialize_with = "deserialize_table_border_fill_option" // Added custom deserializer
-    )]
-    pub table_border_fill: Option<TableBorderFillContent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table_border_fill: Option<TableBorderFill>,
     /// The thickness of the border.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub weight: Option<Dimension>,
     /// The dash style of the border.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dash_style: Option<DashStyle>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// The properties of each border cell.
@@ -80,6 +58,9 @@ pub struct TableBorderCell {
     /// The border properties.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub table_border_properties: Option<TableBorderProperties>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// Contents of each border row in a table. A TableBorderRow corresponds to a
@@ -93,6 +74,9 @@ pub struct TableBorderRow {
     /// merged, it is not included in the response.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub table_border_cells: Option<Vec<TableBorderCell>>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// The background fill of a table cell.
@@ -107,6 +91,9 @@ pub struct TableCellBackgroundFill {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub solid_fill: Option<SolidFill>,
     // Note: Similar to PageBackgroundFill, represented as optional fields.
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// Properties of a TableCell.
@@ -120,6 +107,9 @@ pub struct TableCellProperties {
     /// The alignment of the content in the table cell. Default matches editor defaults.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content_alignment: Option<ContentAlignment>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// Properties of each column in a table.
@@ -130,6 +120,9 @@ pub struct TableColumnProperties {
     /// Width of a column.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub column_width: Option<Dimension>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// Properties of each row in a table.
@@ -141,6 +134,9 @@ pub struct TableRowProperties {
     /// taller if content requires it.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub min_row_height: Option<Dimension>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// A rectangular range of table cells.
@@ -157,4 +153,7 @@ pub struct TableRange {
     /// The column span of the table range.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub column_span: Option<i32>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
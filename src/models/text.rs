@@ -1,7 +1,8 @@
 // src/models/text.rs
 
+use crate::models::common::ExtraFields;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 // Import necessary types
 use crate::models::list::List;
@@ -9,17 +10,20 @@ use crate::models::text_element::TextElement;
 
 /// Represents the textual content of a Shape or TableCell.
 /// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations.pages/text#TextContent
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)] // PartialEq depends on HashMap and Vec<TextElement>
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TextContent {
     /// Output only. The text contents broken down into its component parts (TextElements),
     /// including styling information. This property is read-only. To update text content,
     /// use specific requests like InsertTextRequest, DeleteTextRequest, etc.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub text_elements: Option<Vec<TextElement>,>, // Read-only
+    pub text_elements: Option<Vec<TextElement>>, // Read-only
 
     /// The bulleted lists used in this text, keyed by list ID. A `List` defines
     /// the properties applying to bullets at various nesting levels.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub lists: Option<HashMap<String, List>,>,
+    pub lists: Option<IndexMap<String, List>>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
@@ -1,3 +1,4 @@
+use crate::models::common::ExtraFields;
 use serde::{Deserialize, Serialize};
 
 // Import necessary style and list types
@@ -14,6 +15,9 @@ pub struct TextRun {
     /// The styling applied to this run.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub style: Option<TextStyle>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// Represents the beginning of a new paragraph marker in the text element stream.
@@ -26,6 +30,9 @@ pub struct ParagraphMarker {
     /// The bullet for this paragraph.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bullet: Option<Bullet>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// The type of AutoText.
@@ -52,11 +59,15 @@ pub struct AutoText {
     /// The styling applied to this auto text.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub style: Option<TextStyle>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// Represents the specific kind of content within a TextElement.
 /// The JSON object containing this will have a key like "textRun", "paragraphMarker", etc.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)] // PartialEq should be okay here
+#[allow(clippy::large_enum_variant)]
 #[serde(rename_all = "camelCase")]
 pub enum TextElementKind {
     /// A run of text with consistent styling.
@@ -89,4 +100,7 @@ pub struct TextElement {
     #[serde(flatten)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub kind: Option<TextElementKind>, // Made Option<> in case element is empty/invalid? Check API examples. Usually present. Let's keep it Option for robustness.
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
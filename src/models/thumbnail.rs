@@ -0,0 +1,64 @@
+use crate::models::common::ExtraFields;
+use serde::{Deserialize, Serialize};
+
+/// The predefined thumbnail image sizes.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations.pages/getThumbnail#ThumbnailSize
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ThumbnailSize {
+    /// The default thumbnail image size (currently `LARGE`).
+    ThumbnailSizeUnspecified,
+    /// The thumbnail image width of 1600px.
+    Large,
+    /// The thumbnail image width of 800px.
+    Medium,
+    /// The thumbnail image width of 200px.
+    Small,
+}
+
+/// The optional MIME type of the thumbnail image.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations.pages/getThumbnail#MimeType
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ThumbnailMimeType {
+    /// The default mime type (currently PNG).
+    Png,
+}
+
+/// Options of a `presentations.pages.getThumbnail` call. Unset options use the API defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThumbnailProperties {
+    /// The optional MIME type of the thumbnail image.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<ThumbnailMimeType>,
+
+    /// The optional thumbnail image size.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail_size: Option<ThumbnailSize>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// The thumbnail of a page.
+/// Derived from: https://developers.google.com/slides/api/reference/rest/v1/presentations.pages/getThumbnail#response-body
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Thumbnail {
+    /// The positive width in pixels of the thumbnail image.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<i32>,
+
+    /// The positive height in pixels of the thumbnail image.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
+
+    /// The content URL of the thumbnail image. The URL stays valid for 30 minutes
+    /// and can be fetched without authentication.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_url: Option<String>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
@@ -1,3 +1,4 @@
+use crate::models::common::ExtraFields;
 use serde::{Deserialize, Serialize};
 
 // Import necessary types
@@ -43,6 +44,9 @@ pub struct VideoProperties {
     /// Whether to mute the audio during video playback. Defaults to false.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mute: Option<bool>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 /// A PageElement kind representing a video.
@@ -63,4 +67,7 @@ pub struct Video {
     /// The properties of the video.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub video_properties: Option<VideoProperties>,
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
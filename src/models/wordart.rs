@@ -1,3 +1,4 @@
+use crate::models::common::ExtraFields;
 use serde::{Deserialize, Serialize};
 
 /// A PageElement kind representing word art.
@@ -11,4 +12,7 @@ pub struct WordArt {
     pub rendered_text: Option<String>,
     // Note: WordArt styling is complex and not directly exposed via simple properties.
     // Modifications usually involve replacing the WordArt element.
+    /// Fields of the JSON object that are not modeled above.
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
@@ -0,0 +1,193 @@
+//! Reads the speaker notes of slides.
+//!
+//! Each slide's notes page holds a shape with the speaker notes, named by
+//! `NotesProperties::speaker_notes_object_id` (or, failing that, the page's `BODY`
+//! placeholder). The notes are returned as plain text and as runs with styles resolved by
+//! `style::StyleResolver`, so inherited notes-master styles are included.
+//!
+//! ```
+//! use gslides_tools::notes::speaker_notes;
+//! use gslides_tools::Presentation;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let presentation: Presentation =
+//!     serde_json::from_str(&std::fs::read_to_string("base_presentation.json")?)?;
+//! for notes in speaker_notes(&presentation) {
+//!     println!("Slide {}: {}", notes.slide_index + 1, notes.text);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::models::elements::{PageElement, PageElementKind};
+use crate::models::page::Page;
+use crate::models::placeholder::PlaceholderType;
+use crate::models::presentation::Presentation;
+use crate::models::text_element::TextElementKind;
+use crate::style::{ResolvedRun, StyleResolver};
+
+/// The speaker notes of one slide.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeakerNotes {
+    pub slide_object_id: String,
+    /// The 0-based position of the slide.
+    pub slide_index: usize,
+    pub notes_page_object_id: String,
+    /// The shape holding the notes, if the notes page has one.
+    pub shape_object_id: Option<String>,
+    /// The notes as plain text, without surrounding whitespace.
+    pub text: String,
+    /// The runs of the notes with their resolved styles.
+    pub runs: Vec<ResolvedRun>,
+}
+
+/// Returns the speaker notes of every slide that has a notes page, in slide order.
+pub fn speaker_notes(presentation: &Presentation) -> Vec<SpeakerNotes> {
+    let resolver = StyleResolver::new(presentation);
+    presentation
+        .slides
+        .iter()
+        .flatten()
+        .enumerate()
+        .filter_map(|(slide_index, slide)| {
+            let notes_page = notes_page(slide)?;
+            let shape = find_speaker_notes_shape(notes_page);
+            let runs = shape
+                .and_then(|shape| resolver.resolve_runs(&shape.object_id, None).ok())
+                .unwrap_or_default();
+            Some(SpeakerNotes {
+                slide_object_id: slide.object_id.clone(),
+                slide_index,
+                notes_page_object_id: notes_page.object_id.clone(),
+                shape_object_id: shape.map(|shape| shape.object_id.clone()),
+                text: shape.map(shape_text).unwrap_or_default(),
+                runs,
+            })
+        })
+        .collect()
+}
+
+/// Returns the speaker notes of a slide as plain text, or `None` if it has none.
+pub fn speaker_notes_text(slide: &Page) -> Option<String> {
+    let text = shape_text(speaker_notes_shape(slide)?);
+    (!text.is_empty()).then_some(text)
+}
+
+/// Returns the notes page of a slide.
+pub fn notes_page(slide: &Page) -> Option<&Page> {
+    slide.slide_properties.as_ref()?.notes_page.as_deref()
+}
+
+/// Returns the shape holding a slide's speaker notes.
+pub fn speaker_notes_shape(slide: &Page) -> Option<&PageElement> {
+    find_speaker_notes_shape(notes_page(slide)?)
+}
+
+fn find_speaker_notes_shape(notes_page: &Page) -> Option<&PageElement> {
+    let elements = || notes_page.page_elements.iter().flatten();
+    let by_id = notes_page
+        .notes_properties
+        .as_ref()
+        .and_then(|p| p.speaker_notes_object_id.as_deref())
+        .and_then(|id| elements().find(|element| element.object_id == id));
+    by_id.or_else(|| {
+        elements().find(|element| match &element.element_kind {
+            PageElementKind::Shape(shape) => shape
+                .placeholder
+                .as_ref()
+                .is_some_and(|p| p.placeholder_type == Some(PlaceholderType::Body)),
+            _ => false,
+        })
+    })
+}
+
+fn shape_text(element: &PageElement) -> String {
+    let PageElementKind::Shape(shape) = &element.element_kind else {
+        return String::new();
+    };
+    let mut text = String::new();
+    for element in shape
+        .text
+        .iter()
+        .flat_map(|t| t.text_elements.iter().flatten())
+    {
+        let content = match &element.kind {
+            Some(TextElementKind::TextRun(run)) => run.content.as_deref(),
+            Some(TextElementKind::AutoText(auto_text)) => auto_text.content.as_deref(),
+            _ => None,
+        };
+        text.push_str(content.unwrap_or_default());
+    }
+    text.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::{PageBuilder, PresentationBuilder, ShapeBuilder, TextBuilder};
+    use crate::converters::markdown::{
+        extract_text_from_presentation, extract_text_from_presentation_with_options,
+        TextExtractionOptions,
+    };
+    use crate::converters::svg::{convert_presentation_to_svg_with_mode, SvgRenderMode};
+    use crate::models::properties::TextStyle;
+
+    #[test]
+    fn test_notes_are_extracted_and_rendered() {
+        let notes_text = TextBuilder::new()
+            .add_paragraph("Mention the ")
+            .add_styled_run(
+                "demo",
+                TextStyle {
+                    bold: Some(true),
+                    ..Default::default()
+                },
+            )
+            .build();
+        let notes_page = PageBuilder::notes("notes_1")
+            .set_speaker_notes_object_id("notes_body")
+            .add_element(
+                ShapeBuilder::text_box("notes_body")
+                    .set_placeholder(PlaceholderType::Body, None)
+                    .set_text(notes_text)
+                    .build(),
+            )
+            .build();
+        let presentation = PresentationBuilder::new("deck")
+            .add_slide(
+                PageBuilder::slide("slide_1")
+                    .set_notes_page(notes_page)
+                    .add_element(
+                        ShapeBuilder::text_box("box_1")
+                            .set_text(TextBuilder::new().add_paragraph("Agenda").build())
+                            .build(),
+                    )
+                    .build(),
+            )
+            .add_slide(PageBuilder::slide("slide_2").build())
+            .build();
+
+        let notes = speaker_notes(&presentation);
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].text, "Mention the demo");
+        assert_eq!(notes[0].shape_object_id.as_deref(), Some("notes_body"));
+        assert_eq!(notes[0].runs[1].content, "demo\n");
+        assert_eq!(notes[0].runs[1].text_style.bold, Some(true));
+        let slides = presentation.slides.as_ref().unwrap();
+        assert_eq!(speaker_notes_text(&slides[1]), None);
+
+        let with_notes = extract_text_from_presentation_with_options(
+            &presentation,
+            &TextExtractionOptions {
+                include_speaker_notes: true,
+            },
+        );
+        assert!(with_notes.contains("## Slide 1\n\nAgenda\n\n### Notes\n\nMention the demo\n"));
+        assert!(!extract_text_from_presentation(&presentation).contains("Notes"));
+
+        let svgs = convert_presentation_to_svg_with_mode(&presentation, SvgRenderMode::NotesPages)
+            .unwrap();
+        assert_eq!(svgs.len(), 1);
+        assert!(svgs[0].contains("demo") && !svgs[0].contains("Agenda"));
+    }
+}
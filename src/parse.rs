@@ -0,0 +1,384 @@
+//! Parsing of presentation JSON with precise error reports.
+//!
+//! `parse_presentation` reports the JSON path of the first value that does not match the
+//! models (e.g. `slides[3].pageElements[7].shape.shapeProperties.outline.dashStyle`), the
+//! type that was expected and a snippet of the offending value.
+//! `parse_presentation_lenient` drops every such value instead, and returns the rest of the
+//! presentation together with all the problems it found.
+
+use crate::errors::{Result, SlidesApiError};
+use crate::models::presentation::Presentation;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::fmt;
+
+/// Longest snippet of an offending value kept in a `ParseProblem`, in characters.
+const SNIPPET_LENGTH: usize = 80;
+
+/// Most problems `parse_presentation_lenient` skips before it gives up.
+const MAX_PROBLEMS: usize = 1000;
+
+/// Error messages of `deserialize_nested` look like ``at `shape.outline`: <message>``.
+const NESTED_PREFIX: &str = "at `";
+const NESTED_SEPARATOR: &str = "`: ";
+
+/// One step of a JSON path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A field of an object.
+    Key(String),
+    /// An item of an array.
+    Index(usize),
+}
+
+/// A value of a presentation JSON document that does not match the models.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseProblem {
+    /// The path of the offending value from the document root. Empty for the root itself
+    /// and for JSON syntax errors.
+    pub path: Vec<PathSegment>,
+    /// The error reported for the value, without its location.
+    pub message: String,
+    /// The type that was expected, when the error names one (e.g. `i32`).
+    pub expected: Option<String>,
+    /// The start of the offending value as compact JSON, when it could be located.
+    pub snippet: Option<String>,
+    /// The line where reading stopped, for errors found while reading the JSON text.
+    pub line: Option<usize>,
+    /// The column where reading stopped, for errors found while reading the JSON text.
+    pub column: Option<usize>,
+}
+
+impl ParseProblem {
+    /// The path formatted like `slides[3].pageElements[7].shape`. Keys that are not plain
+    /// identifiers are quoted, e.g. `lists["kix.abc"]`.
+    pub fn path_string(&self) -> String {
+        format_path(&self.path)
+    }
+}
+
+impl fmt::Display for ParseProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = self.path_string();
+        write!(
+            f,
+            "at `{}`: {}",
+            if path.is_empty() { "." } else { &path },
+            self.message
+        )?;
+        if let Some(snippet) = &self.snippet {
+            write!(f, " (found {})", snippet)?;
+        }
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            write!(f, " at line {} column {}", line, column)?;
+        }
+        Ok(())
+    }
+}
+
+/// A presentation parsed in lenient mode, and the values that were dropped from it.
+#[derive(Debug, Clone)]
+pub struct LenientParse {
+    /// The presentation without the offending values.
+    pub presentation: Presentation,
+    /// Every dropped value, in the order found. Paths refer to the original document.
+    pub problems: Vec<ParseProblem>,
+}
+
+/// Parses a presentation JSON document, reporting where the first mismatch is.
+///
+/// # Arguments
+/// * `json` - The JSON text, e.g. a `presentations.get` response body.
+///
+/// # Returns
+/// The presentation, or `SlidesApiError::PresentationParse` with the JSON path, the
+/// expected type and a snippet of the offending value.
+pub fn parse_presentation(json: impl AsRef<[u8]>) -> Result<Presentation> {
+    let json = json.as_ref();
+    let mut deserializer = serde_json::Deserializer::from_slice(json);
+    let problem = match serde_path_to_error::deserialize(&mut deserializer) {
+        Ok(presentation) => match deserializer.end() {
+            Ok(()) => return Ok(presentation),
+            Err(error) => problem_from_error(Vec::new(), &error, None),
+        },
+        Err(error) => {
+            let document = serde_json::from_slice::<Value>(json).ok();
+            problem_from_error(segments(error.path()), error.inner(), document.as_ref())
+        }
+    };
+    Err(SlidesApiError::PresentationParse(Box::new(problem)))
+}
+
+/// Parses a presentation JSON document, dropping every value that does not match the
+/// models instead of stopping at the first one.
+///
+/// A dropped field becomes unset. An object that cannot be read at all (e.g. a page
+/// element without `objectId`) is removed from its parent.
+///
+/// # Arguments
+/// * `json` - The JSON text, e.g. a `presentations.get` response body.
+///
+/// # Returns
+/// The presentation and all problems found, or `SlidesApiError::PresentationParse` when
+/// the text is not JSON or the document root itself does not match.
+pub fn parse_presentation_lenient(json: impl AsRef<[u8]>) -> Result<LenientParse> {
+    let mut document: Value = serde_json::from_slice(json.as_ref()).map_err(|error| {
+        SlidesApiError::PresentationParse(Box::new(problem_from_error(Vec::new(), &error, None)))
+    })?;
+    let mut problems = Vec::new();
+    // Array items removed so far, to map later paths back to the original document.
+    let mut removed_items: Vec<Vec<PathSegment>> = Vec::new();
+    loop {
+        let error = match serde_path_to_error::deserialize::<_, Presentation>(&document) {
+            Ok(presentation) => {
+                return Ok(LenientParse {
+                    presentation,
+                    problems,
+                })
+            }
+            Err(error) => error,
+        };
+        let mut problem =
+            problem_from_error(segments(error.path()), error.inner(), Some(&document));
+        let path = std::mem::take(&mut problem.path);
+        problem.path = original_path(&path, &removed_items);
+        if problems.len() >= MAX_PROBLEMS || !remove(&mut document, &path) {
+            return Err(SlidesApiError::PresentationParse(Box::new(problem)));
+        }
+        if matches!(path.last(), Some(PathSegment::Index(_))) {
+            removed_items.push(path);
+        }
+        problems.push(problem);
+    }
+}
+
+/// Deserializes a value taken out of a larger document, e.g. the kind of a page element.
+///
+/// The error message names the path of the failing field below `key`, so
+/// `parse_presentation` can still report the full path.
+pub(crate) fn deserialize_nested<T: DeserializeOwned>(
+    key: &str,
+    value: Value,
+) -> std::result::Result<T, String> {
+    serde_path_to_error::deserialize(value).map_err(|error| {
+        let mut path = vec![PathSegment::Key(key.to_string())];
+        path.extend(segments(error.path()));
+        format!(
+            "{}{}{}{}",
+            NESTED_PREFIX,
+            format_path(&path),
+            NESTED_SEPARATOR,
+            error.inner()
+        )
+    })
+}
+
+fn problem_from_error(
+    mut path: Vec<PathSegment>,
+    error: &serde_json::Error,
+    document: Option<&Value>,
+) -> ParseProblem {
+    let mut message = error.to_string();
+    let location = (error.line() > 0).then(|| (error.line(), error.column()));
+    if let Some((line, column)) = location {
+        let suffix = format!(" at line {} column {}", line, column);
+        if let Some(stripped) = message.strip_suffix(&suffix) {
+            message = stripped.to_string();
+        }
+    }
+    while let Some((nested_path, rest)) = split_nested(&message) {
+        path.extend(nested_path);
+        message = rest;
+    }
+    let expected = message
+        .rsplit_once(", expected ")
+        .map(|(_, expected)| expected.to_string());
+    let snippet = document.and_then(|d| lookup(d, &path)).map(snippet);
+    ParseProblem {
+        path,
+        message,
+        expected,
+        snippet,
+        line: location.map(|(line, _)| line),
+        column: location.map(|(_, column)| column),
+    }
+}
+
+/// Splits a `deserialize_nested` message into its path and the original message.
+fn split_nested(message: &str) -> Option<(Vec<PathSegment>, String)> {
+    let (path, rest) = message
+        .strip_prefix(NESTED_PREFIX)?
+        .split_once(NESTED_SEPARATOR)?;
+    Some((parse_path(path)?, rest.to_string()))
+}
+
+fn segments(path: &serde_path_to_error::Path) -> Vec<PathSegment> {
+    path.iter()
+        .map_while(|segment| match segment {
+            serde_path_to_error::Segment::Seq { index } => Some(PathSegment::Index(*index)),
+            serde_path_to_error::Segment::Map { key } => Some(PathSegment::Key(key.clone())),
+            serde_path_to_error::Segment::Enum { variant } => {
+                Some(PathSegment::Key(variant.clone()))
+            }
+            serde_path_to_error::Segment::Unknown => None,
+        })
+        .collect()
+}
+
+fn is_identifier(key: &str) -> bool {
+    !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn format_path(path: &[PathSegment]) -> String {
+    let mut formatted = String::new();
+    for segment in path {
+        match segment {
+            PathSegment::Key(key) if is_identifier(key) => {
+                if !formatted.is_empty() {
+                    formatted.push('.');
+                }
+                formatted.push_str(key);
+            }
+            PathSegment::Key(key) => {
+                formatted.push('[');
+                formatted.push_str(&Value::String(key.clone()).to_string());
+                formatted.push(']');
+            }
+            PathSegment::Index(index) => formatted.push_str(&format!("[{}]", index)),
+        }
+    }
+    formatted
+}
+
+/// The inverse of `format_path`.
+fn parse_path(mut formatted: &str) -> Option<Vec<PathSegment>> {
+    let mut path = Vec::new();
+    while !formatted.is_empty() {
+        if formatted.starts_with("[\"") {
+            let mut strings =
+                serde_json::Deserializer::from_str(&formatted[1..]).into_iter::<String>();
+            let key = strings.next()?.ok()?;
+            let end = 1 + strings.byte_offset();
+            formatted = formatted[end..].strip_prefix(']')?;
+            path.push(PathSegment::Key(key));
+        } else if let Some(rest) = formatted.strip_prefix('[') {
+            let (index, rest) = rest.split_once(']')?;
+            path.push(PathSegment::Index(index.parse().ok()?));
+            formatted = rest;
+        } else {
+            let rest = formatted.strip_prefix('.').unwrap_or(formatted);
+            let end = rest.find(['.', '[']).unwrap_or(rest.len());
+            path.push(PathSegment::Key(rest[..end].to_string()));
+            formatted = &rest[end..];
+        }
+    }
+    Some(path)
+}
+
+fn lookup<'a>(document: &'a Value, path: &[PathSegment]) -> Option<&'a Value> {
+    path.iter()
+        .try_fold(document, |value, segment| match segment {
+            PathSegment::Key(key) => value.get(key),
+            PathSegment::Index(index) => value.get(index),
+        })
+}
+
+/// Removes the value at `path`. Returns `false` if there is no such value.
+fn remove(document: &mut Value, path: &[PathSegment]) -> bool {
+    let Some((last, parents)) = path.split_last() else {
+        return false;
+    };
+    let parent = parents
+        .iter()
+        .try_fold(document, |value, segment| match segment {
+            PathSegment::Key(key) => value.get_mut(key),
+            PathSegment::Index(index) => value.get_mut(index),
+        });
+    match (parent, last) {
+        (Some(Value::Object(object)), PathSegment::Key(key)) => object.shift_remove(key).is_some(),
+        (Some(Value::Array(items)), PathSegment::Index(index)) if *index < items.len() => {
+            items.remove(*index);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Maps a path in the document left after removing `removed_items` (in order) back to
+/// the original document.
+fn original_path(path: &[PathSegment], removed_items: &[Vec<PathSegment>]) -> Vec<PathSegment> {
+    let mut path = path.to_vec();
+    for removed in removed_items.iter().rev() {
+        let Some((PathSegment::Index(removed_index), parent)) = removed.split_last() else {
+            continue;
+        };
+        if path.len() > parent.len() && path[..parent.len()] == *parent {
+            if let PathSegment::Index(index) = &mut path[parent.len()] {
+                if *index >= *removed_index {
+                    *index += 1;
+                }
+            }
+        }
+    }
+    path
+}
+
+fn snippet(value: &Value) -> String {
+    let json = value.to_string();
+    if json.chars().count() <= SNIPPET_LENGTH {
+        json
+    } else {
+        let mut truncated: String = json.chars().take(SNIPPET_LENGTH).collect();
+        truncated.push('…');
+        truncated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOCUMENT: &str = r#"{
+        "presentationId": "p1",
+        "slides": [{
+            "objectId": "s1",
+            "pageElements": [
+                {"objectId": "e1", "shape": {"shapeType": "TEXT_BOX"}},
+                {"shape": {"shapeType": "TEXT_BOX"}},
+                {"objectId": "e3", "shape": {"shapeProperties": {"outline": {"dashStyle": "WAVY"}}}}
+            ]
+        }]
+    }"#;
+
+    #[test]
+    fn test_parse_reports_paths_and_lenient_mode_collects_all_problems() {
+        let Err(SlidesApiError::PresentationParse(problem)) = parse_presentation(DOCUMENT) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(problem.path_string(), "slides[0].pageElements[1]");
+        assert_eq!(problem.message, "missing field `objectId`");
+
+        let parsed = parse_presentation_lenient(DOCUMENT).unwrap();
+        let paths: Vec<String> = parsed.problems.iter().map(|p| p.path_string()).collect();
+        assert_eq!(
+            paths,
+            [
+                "slides[0].pageElements[1]",
+                "slides[0].pageElements[2].shape.shapeProperties.outline.dashStyle"
+            ]
+        );
+        let dash_style = &parsed.problems[1];
+        assert_eq!(dash_style.snippet.as_deref(), Some("\"WAVY\""));
+        assert!(dash_style
+            .expected
+            .as_deref()
+            .unwrap()
+            .starts_with("one of"));
+        let elements = parsed.presentation.slides.unwrap()[0]
+            .page_elements
+            .clone()
+            .unwrap();
+        let ids: Vec<&str> = elements.iter().map(|e| e.object_id.as_str()).collect();
+        assert_eq!(ids, ["e1", "e3"]);
+    }
+}
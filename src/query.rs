@@ -0,0 +1,902 @@
+//! Finds page elements with composable selectors.
+//!
+//! A `Selector` matches elements by object ID, kind, shape type, placeholder type, text,
+//! alt text, the page they sit on, their groups, and their position or size. Selectors are
+//! combined with `and`, `or` and `not`, or parsed from a small CSS-like syntax:
+//!
+//! ```text
+//! slide[2] shape[placeholder=TITLE]       the title placeholder of the second slide
+//! layout group shape[text*="Confidential"] shapes in groups on layouts containing the text
+//! table[x<4572000][title~="^Q[1-4]"]      tables in the left half whose title matches a regex
+//! ```
+//!
+//! The last part selects elements, by kind (`shape`, `image`, `video`, `line`, `table`,
+//! `wordArt`, `sheetsChart`, `speakerSpotlight`, `group`, or `*` for any) and attributes:
+//! `id`, `type`, `placeholder`, `text`, `title` and `description` compare with `=`, `*=`
+//! (contains) or `~=` (regex); `=` ignores the newline that ends the last paragraph.
+//! `x`, `y`, `width` and `height` (EMU, on the page) compare with `=`, `!=`, `<`, `<=`,
+//! `>` or `>=`. Earlier parts select the page (`slide`, `layout`, `master`, `notes`,
+//! `notesMaster` or `page`, with a 1-based number as in `slide[2]`, or `[id=...]`) or a
+//! group the element must be inside.
+//!
+//! ```
+//! use gslides_tools::query::{select, Selector};
+//! use gslides_tools::Presentation;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let presentation: Presentation =
+//!     serde_json::from_str(&std::fs::read_to_string("base_presentation.json")?)?;
+//! let selector: Selector = "slide[1] shape[placeholder=CENTERED_TITLE]".parse()?;
+//! for found in select(&presentation, &selector) {
+//!     println!("{} on {}: {}", found.element.object_id, found.page.object_id, found.text());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::geometry::{size_in_emu, Matrix, Rect};
+use crate::models::common::AffineTransform;
+use crate::models::elements::{PageElement, PageElementKind};
+use crate::models::page::{Page, PageType};
+use crate::models::placeholder::PlaceholderType;
+use crate::models::presentation::Presentation;
+use crate::models::shape::ShapeType;
+use crate::models::text::TextContent;
+use crate::models::text_element::TextElementKind;
+use crate::visit::{walk, VisitContext, Visitor};
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Errors raised while parsing a selector string.
+#[derive(Error, Debug)]
+pub enum SelectorError {
+    /// The selector string is malformed. `position` is a byte offset.
+    #[error("Invalid selector at position {position}: {message}")]
+    Syntax { position: usize, message: String },
+
+    /// A `~=` value is not a valid regular expression.
+    #[error("Invalid regex in selector: {0}")]
+    Regex(#[from] regex::Error),
+}
+
+/// An element found by `select`, with the page and groups it sits in.
+#[derive(Debug, Clone)]
+pub struct ElementMatch<'a> {
+    pub element: &'a PageElement,
+    /// The page the element sits on.
+    pub page: &'a Page,
+    /// The role of the page. Notes pages are reached through their slides.
+    pub page_type: PageType,
+    /// The 0-based position of the page among the pages of its role. A notes page has
+    /// the position of its slide.
+    pub page_index: usize,
+    /// The groups around the element, outermost first.
+    pub groups: Vec<&'a PageElement>,
+    /// The element's absolute transform, with the translation in EMU.
+    pub transform: AffineTransform,
+}
+
+impl ElementMatch<'_> {
+    /// The axis-aligned box the element covers on the page, if it has a size.
+    pub fn bounds(&self) -> Option<Rect> {
+        let (width, height) = size_in_emu(self.element.size.as_ref()?);
+        Some(Matrix::from_transform(&self.transform).bounds(width, height))
+    }
+
+    /// The text of a shape, or of all cells of a table (one line per cell).
+    pub fn text(&self) -> String {
+        element_text(self.element)
+    }
+}
+
+/// Which pages a `Selector::OnPage` accepts.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PageSelector {
+    /// The role of the page, e.g. `PageType::Slide`.
+    pub page_type: Option<PageType>,
+    /// The 1-based position among the pages of that role.
+    pub number: Option<usize>,
+    pub object_id: Option<String>,
+}
+
+/// A string test: exact, substring or regex.
+#[derive(Debug, Clone)]
+pub enum TextMatch {
+    /// The whole text, ignoring the newline that ends its last paragraph.
+    Equals(String),
+    Contains(String),
+    Regex(Regex),
+}
+
+impl TextMatch {
+    fn is_match(&self, text: &str) -> bool {
+        match self {
+            TextMatch::Equals(expected) => {
+                text.strip_suffix('\n').unwrap_or(text)
+                    == expected.strip_suffix('\n').unwrap_or(expected)
+            }
+            TextMatch::Contains(part) => text.contains(part.as_str()),
+            TextMatch::Regex(regex) => regex.is_match(text),
+        }
+    }
+}
+
+/// A measurement of an element's bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    X,
+    Y,
+    Width,
+    Height,
+}
+
+/// A numeric comparison for `Selector::Compare`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Comparison {
+    fn holds(self, left: f64, right: f64) -> bool {
+        match self {
+            Comparison::Equal => left == right,
+            Comparison::NotEqual => left != right,
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+            Comparison::Greater => left > right,
+            Comparison::GreaterOrEqual => left >= right,
+        }
+    }
+}
+
+/// A custom test on an element's bounds.
+pub type BoundsPredicate = Arc<dyn Fn(&Rect) -> bool + Send + Sync>;
+
+/// A test on page elements. Build one with the constructors and combinators below, or
+/// parse one from a string (see the module documentation).
+#[derive(Clone)]
+pub enum Selector {
+    /// Matches every element.
+    Any,
+    ObjectId(String),
+    /// The JSON key of the element kind, e.g. `shape` or `elementGroup`
+    /// (see `PageElementKind::kind_name`).
+    Kind(String),
+    ShapeType(ShapeType),
+    Placeholder(PlaceholderType),
+    Text(TextMatch),
+    Title(TextMatch),
+    Description(TextMatch),
+    OnPage(PageSelector),
+    /// Matches elements inside a group that matches the inner selector.
+    InGroup(Box<Selector>),
+    Compare(Metric, Comparison, f64),
+    Bounds(BoundsPredicate),
+    And(Vec<Selector>),
+    Or(Vec<Selector>),
+    Not(Box<Selector>),
+}
+
+impl fmt::Debug for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Selector::Any => write!(f, "Any"),
+            Selector::ObjectId(id) => f.debug_tuple("ObjectId").field(id).finish(),
+            Selector::Kind(kind) => f.debug_tuple("Kind").field(kind).finish(),
+            Selector::ShapeType(t) => f.debug_tuple("ShapeType").field(t).finish(),
+            Selector::Placeholder(t) => f.debug_tuple("Placeholder").field(t).finish(),
+            Selector::Text(m) => f.debug_tuple("Text").field(m).finish(),
+            Selector::Title(m) => f.debug_tuple("Title").field(m).finish(),
+            Selector::Description(m) => f.debug_tuple("Description").field(m).finish(),
+            Selector::OnPage(page) => f.debug_tuple("OnPage").field(page).finish(),
+            Selector::InGroup(group) => f.debug_tuple("InGroup").field(group).finish(),
+            Selector::Compare(metric, comparison, value) => f
+                .debug_tuple("Compare")
+                .field(metric)
+                .field(comparison)
+                .field(value)
+                .finish(),
+            Selector::Bounds(_) => write!(f, "Bounds(..)"),
+            Selector::And(all) => f.debug_tuple("And").field(all).finish(),
+            Selector::Or(any) => f.debug_tuple("Or").field(any).finish(),
+            Selector::Not(inner) => f.debug_tuple("Not").field(inner).finish(),
+        }
+    }
+}
+
+impl Selector {
+    pub fn object_id(object_id: impl Into<String>) -> Self {
+        Selector::ObjectId(object_id.into())
+    }
+
+    pub fn kind(kind: impl Into<String>) -> Self {
+        Selector::Kind(kind.into())
+    }
+
+    /// Matches elements whose text contains `part`.
+    pub fn text_contains(part: impl Into<String>) -> Self {
+        Selector::Text(TextMatch::Contains(part.into()))
+    }
+
+    /// Matches elements whose text matches `pattern`.
+    pub fn text_matches(pattern: &str) -> Result<Self, SelectorError> {
+        Ok(Selector::Text(TextMatch::Regex(Regex::new(pattern)?)))
+    }
+
+    /// Matches elements on the slide at the 1-based `number`.
+    pub fn on_slide(number: usize) -> Self {
+        Selector::OnPage(PageSelector {
+            page_type: Some(PageType::Slide),
+            number: Some(number),
+            object_id: None,
+        })
+    }
+
+    /// Matches elements on the page with the given object ID.
+    pub fn on_page(object_id: impl Into<String>) -> Self {
+        Selector::OnPage(PageSelector {
+            object_id: Some(object_id.into()),
+            ..Default::default()
+        })
+    }
+
+    /// Matches elements whose bounds pass `predicate`.
+    pub fn bounds(predicate: impl Fn(&Rect) -> bool + Send + Sync + 'static) -> Self {
+        Selector::Bounds(Arc::new(predicate))
+    }
+
+    /// Matches elements that match both selectors.
+    pub fn and(self, other: Selector) -> Self {
+        match self {
+            Selector::And(mut all) => {
+                all.push(other);
+                Selector::And(all)
+            }
+            first => Selector::And(vec![first, other]),
+        }
+    }
+
+    /// Matches elements that match either selector.
+    pub fn or(self, other: Selector) -> Self {
+        match self {
+            Selector::Or(mut any) => {
+                any.push(other);
+                Selector::Or(any)
+            }
+            first => Selector::Or(vec![first, other]),
+        }
+    }
+
+    /// Matches elements that do not match this selector.
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        Selector::Not(Box::new(self))
+    }
+
+    /// Returns true if the element matches.
+    pub fn matches(&self, found: &ElementMatch<'_>) -> bool {
+        let element = found.element;
+        match self {
+            Selector::Any => true,
+            Selector::ObjectId(id) => element.object_id == *id,
+            Selector::Kind(kind) => element.element_kind.kind_name() == kind,
+            Selector::ShapeType(shape_type) => matches!(&element.element_kind,
+                PageElementKind::Shape(shape) if shape.shape_type.as_ref() == Some(shape_type)),
+            Selector::Placeholder(placeholder_type) => matches!(&element.element_kind,
+                PageElementKind::Shape(shape) if shape.placeholder.as_ref()
+                    .and_then(|p| p.placeholder_type.as_ref()) == Some(placeholder_type)),
+            Selector::Text(text) => text.is_match(&found.text()),
+            Selector::Title(title) => element.title.as_deref().is_some_and(|t| title.is_match(t)),
+            Selector::Description(description) => element
+                .description
+                .as_deref()
+                .is_some_and(|d| description.is_match(d)),
+            Selector::OnPage(page) => {
+                page.page_type
+                    .as_ref()
+                    .is_none_or(|t| *t == found.page_type)
+                    && page.number.is_none_or(|n| n == found.page_index + 1)
+                    && page
+                        .object_id
+                        .as_ref()
+                        .is_none_or(|id| *id == found.page.object_id)
+            }
+            Selector::InGroup(group) => (0..found.groups.len()).any(|depth| {
+                // The group is matched in its own context: inside its outer groups.
+                let outer = ElementMatch {
+                    element: found.groups[depth],
+                    groups: found.groups[..depth].to_vec(),
+                    ..found.clone()
+                };
+                group.matches(&outer)
+            }),
+            Selector::Compare(metric, comparison, value) => found.bounds().is_some_and(|b| {
+                let measured = match metric {
+                    Metric::X => b.x.0,
+                    Metric::Y => b.y.0,
+                    Metric::Width => b.width.0,
+                    Metric::Height => b.height.0,
+                };
+                comparison.holds(measured, *value)
+            }),
+            Selector::Bounds(predicate) => found.bounds().is_some_and(|b| predicate(&b)),
+            Selector::And(all) => all.iter().all(|s| s.matches(found)),
+            Selector::Or(any) => any.iter().any(|s| s.matches(found)),
+            Selector::Not(inner) => !inner.matches(found),
+        }
+    }
+
+    /// Parses the selector syntax described in the module documentation.
+    pub fn parse(selector: &str) -> Result<Self, SelectorError> {
+        Parser::new(selector).parse()
+    }
+}
+
+impl FromStr for Selector {
+    type Err = SelectorError;
+
+    fn from_str(selector: &str) -> Result<Self, Self::Err> {
+        Selector::parse(selector)
+    }
+}
+
+/// Returns every element of the presentation that matches, in `visit::walk` order.
+pub fn select<'a>(presentation: &'a Presentation, selector: &Selector) -> Vec<ElementMatch<'a>> {
+    let mut collector = Collector::default();
+    walk(presentation, &mut collector);
+    collector
+        .found
+        .into_iter()
+        .filter(|found| selector.matches(found))
+        .collect()
+}
+
+/// Returns the first element that matches, in `visit::walk` order.
+pub fn select_first<'a>(
+    presentation: &'a Presentation,
+    selector: &Selector,
+) -> Option<ElementMatch<'a>> {
+    select(presentation, selector).into_iter().next()
+}
+
+/// Parses `selector` and returns the matching elements.
+pub fn query<'a>(
+    presentation: &'a Presentation,
+    selector: &str,
+) -> Result<Vec<ElementMatch<'a>>, SelectorError> {
+    Ok(select(presentation, &Selector::parse(selector)?))
+}
+
+/// Collects every element with its page context.
+#[derive(Default)]
+struct Collector<'a> {
+    found: Vec<ElementMatch<'a>>,
+    page: Option<(&'a Page, PageType, usize)>,
+    /// Pages seen so far, by role.
+    page_counts: Vec<(PageType, usize)>,
+    groups: HashMap<String, &'a PageElement>,
+}
+
+impl<'a> Visitor<'a> for Collector<'a> {
+    fn visit_page(&mut self, page: &'a Page, page_type: &PageType) {
+        // Notes pages share the position of the slide they follow.
+        let counted = match page_type {
+            PageType::Notes => &PageType::Slide,
+            other => other,
+        };
+        let position = match self.page_counts.iter().position(|(t, _)| t == counted) {
+            Some(position) => position,
+            None => {
+                self.page_counts.push((counted.clone(), 0));
+                self.page_counts.len() - 1
+            }
+        };
+        let count = &mut self.page_counts[position].1;
+        if *page_type != PageType::Notes {
+            *count += 1;
+        }
+        self.page = Some((page, page_type.clone(), count.saturating_sub(1)));
+    }
+
+    fn visit_element(&mut self, element: &'a PageElement, context: &VisitContext<'_>) {
+        let Some((page, page_type, page_index)) = self.page.clone() else {
+            return;
+        };
+        if matches!(element.element_kind, PageElementKind::ElementGroup(_)) {
+            self.groups.insert(element.object_id.clone(), element);
+        }
+        let groups = context
+            .groups
+            .iter()
+            .filter_map(|id| self.groups.get(id).copied())
+            .collect();
+        self.found.push(ElementMatch {
+            element,
+            page,
+            page_type,
+            page_index,
+            groups,
+            transform: context.transform.clone(),
+        });
+    }
+}
+
+fn element_text(element: &PageElement) -> String {
+    match &element.element_kind {
+        PageElementKind::Shape(shape) => shape.text.as_ref().map(text_of).unwrap_or_default(),
+        PageElementKind::Table(table) => table
+            .table_rows
+            .iter()
+            .flatten()
+            .flat_map(|row| row.table_cells.iter().flatten())
+            .filter_map(|cell| cell.text.as_ref().map(text_of))
+            .collect(),
+        _ => String::new(),
+    }
+}
+
+fn text_of(text: &TextContent) -> String {
+    text.text_elements
+        .iter()
+        .flatten()
+        .filter_map(|element| match &element.kind {
+            Some(TextElementKind::TextRun(run)) => run.content.as_deref(),
+            Some(TextElementKind::AutoText(auto_text)) => auto_text.content.as_deref(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// A recursive-descent parser for the selector syntax.
+struct Parser<'s> {
+    input: &'s str,
+    position: usize,
+}
+
+impl<'s> Parser<'s> {
+    fn new(input: &'s str) -> Self {
+        Self { input, position: 0 }
+    }
+
+    fn parse(mut self) -> Result<Selector, SelectorError> {
+        let mut parts = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.rest().is_empty() {
+                break;
+            }
+            parts.push(self.part()?);
+        }
+        let Some((element, scopes)) = parts.split_last() else {
+            return Err(self.error("expected an element selector"));
+        };
+        let mut all = Vec::new();
+        for scope in scopes {
+            match scope {
+                Part::Page(page) => all.push(Selector::OnPage(page.clone())),
+                Part::Element(group) => all.push(Selector::InGroup(Box::new(group.clone()))),
+            }
+        }
+        match element {
+            Part::Element(element) => all.push(element.clone()),
+            Part::Page(page) => {
+                all.push(Selector::OnPage(page.clone()));
+            }
+        }
+        Ok(if all.len() == 1 {
+            all.remove(0)
+        } else {
+            Selector::And(all)
+        })
+    }
+
+    fn part(&mut self) -> Result<Part, SelectorError> {
+        let start = self.position;
+        let name = self.take_while(|c| c.is_ascii_alphanumeric() || c == '*');
+        let page_type = match name {
+            "slide" => Some(Some(PageType::Slide)),
+            "layout" => Some(Some(PageType::Layout)),
+            "master" => Some(Some(PageType::Master)),
+            "notes" => Some(Some(PageType::Notes)),
+            "notesMaster" => Some(Some(PageType::NotesMaster)),
+            "page" => Some(None),
+            _ => None,
+        };
+        if let Some(page_type) = page_type {
+            let mut page = PageSelector {
+                page_type,
+                ..Default::default()
+            };
+            while self.rest().starts_with('[') {
+                self.position += 1;
+                self.skip_whitespace();
+                if self.rest().starts_with(|c: char| c.is_ascii_digit()) {
+                    let number_position = self.position;
+                    let digits = self.take_while(|c| c.is_ascii_digit());
+                    let number = digits.parse().map_err(|_| self.error("bad number"))?;
+                    if number == 0 {
+                        return Err(error(number_position, "page numbers start at 1"));
+                    }
+                    page.number = Some(number);
+                } else {
+                    let (key, op, value) = self.attribute()?;
+                    if key != "id" || op != "=" {
+                        return Err(self.error("pages support only [N] and [id=...]"));
+                    }
+                    page.object_id = Some(value);
+                }
+                self.expect(']')?;
+            }
+            return Ok(Part::Page(page));
+        }
+
+        let mut all = match name {
+            "*" | "element" => vec![],
+            "group" | "elementGroup" => vec![Selector::kind("elementGroup")],
+            "shape" | "image" | "video" | "line" | "table" | "wordArt" | "sheetsChart"
+            | "speakerSpotlight" => vec![Selector::kind(name)],
+            "" => {
+                return Err(self.error("expected a page or element name"));
+            }
+            other => {
+                self.position = start;
+                return Err(self.error(&format!("unknown selector name `{}`", other)));
+            }
+        };
+        while self.rest().starts_with('[') {
+            self.position += 1;
+            self.skip_whitespace();
+            let key_position = self.position;
+            let (key, op, value) = self.attribute()?;
+            all.push(attribute_selector(key_position, &key, &op, value)?);
+            self.expect(']')?;
+        }
+        Ok(Part::Element(match all.len() {
+            0 => Selector::Any,
+            1 => all.remove(0),
+            _ => Selector::And(all),
+        }))
+    }
+
+    /// Parses `key op value` up to (not including) the closing `]`.
+    fn attribute(&mut self) -> Result<(String, String, String), SelectorError> {
+        let key = self
+            .take_while(|c| c.is_ascii_alphanumeric() || c == '_')
+            .to_string();
+        if key.is_empty() {
+            return Err(self.error("expected an attribute name"));
+        }
+        self.skip_whitespace();
+        let op = ["*=", "~=", "!=", "<=", ">=", "=", "<", ">"]
+            .into_iter()
+            .find(|op| self.rest().starts_with(op))
+            .ok_or_else(|| self.error("expected an operator"))?;
+        self.position += op.len();
+        self.skip_whitespace();
+        let value = if self.rest().starts_with('"') {
+            self.quoted()?
+        } else {
+            self.take_while(|c| c != ']').trim_end().to_string()
+        };
+        self.skip_whitespace();
+        Ok((key, op.to_string(), value))
+    }
+
+    fn quoted(&mut self) -> Result<String, SelectorError> {
+        let start = self.position;
+        self.position += 1;
+        let mut value = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((offset, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.position += offset + 1;
+                    return Ok(value);
+                }
+                // Only `\"` and `\\` are escapes, so regexes keep their backslashes.
+                '\\' => match chars.next() {
+                    Some((_, escaped @ ('"' | '\\'))) => value.push(escaped),
+                    Some((_, other)) => {
+                        value.push('\\');
+                        value.push(other);
+                    }
+                    None => value.push('\\'),
+                },
+                c => value.push(c),
+            }
+        }
+        self.position = start;
+        Err(self.error("unterminated string"))
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), SelectorError> {
+        self.skip_whitespace();
+        if self.rest().starts_with(expected) {
+            self.position += expected.len_utf8();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", expected)))
+        }
+    }
+
+    fn take_while(&mut self, accept: impl Fn(char) -> bool) -> &'s str {
+        let rest = &self.input[self.position..];
+        let len = rest.find(|c| !accept(c)).unwrap_or(rest.len());
+        self.position += len;
+        &rest[..len]
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    fn rest(&self) -> &'s str {
+        &self.input[self.position..]
+    }
+
+    fn error(&self, message: &str) -> SelectorError {
+        error(self.position, message)
+    }
+}
+
+/// Builds the selector for one `[key op value]` attribute of an element part.
+fn attribute_selector(
+    position: usize,
+    key: &str,
+    op: &str,
+    value: String,
+) -> Result<Selector, SelectorError> {
+    let text_match = |value: String| -> Result<TextMatch, SelectorError> {
+        Ok(match op {
+            "=" => TextMatch::Equals(value),
+            "*=" => TextMatch::Contains(value),
+            "~=" => TextMatch::Regex(Regex::new(&value)?),
+            _ => {
+                return Err(error(
+                    position,
+                    &format!("`{}` does not support `{}`", key, op),
+                ))
+            }
+        })
+    };
+    let equals_only = || {
+        if op == "=" {
+            Ok(())
+        } else {
+            Err(error(position, &format!("`{}` supports only `=`", key)))
+        }
+    };
+    Ok(match key {
+        "id" => {
+            equals_only()?;
+            Selector::ObjectId(value)
+        }
+        "type" => {
+            equals_only()?;
+            Selector::ShapeType(enum_value(position, key, &value, |shape_type| {
+                !matches!(shape_type, ShapeType::Unknown(_))
+            })?)
+        }
+        "placeholder" => {
+            equals_only()?;
+            Selector::Placeholder(enum_value(position, key, &value, |placeholder_type| {
+                !matches!(placeholder_type, PlaceholderType::Unknown(_))
+            })?)
+        }
+        "text" => Selector::Text(text_match(value)?),
+        "title" => Selector::Title(text_match(value)?),
+        "description" => Selector::Description(text_match(value)?),
+        "x" | "y" | "width" | "height" => {
+            let metric = match key {
+                "x" => Metric::X,
+                "y" => Metric::Y,
+                "width" => Metric::Width,
+                _ => Metric::Height,
+            };
+            let comparison = match op {
+                "=" => Comparison::Equal,
+                "!=" => Comparison::NotEqual,
+                "<" => Comparison::Less,
+                "<=" => Comparison::LessOrEqual,
+                ">" => Comparison::Greater,
+                ">=" => Comparison::GreaterOrEqual,
+                _ => {
+                    return Err(error(
+                        position,
+                        &format!("`{}` does not support `{}`", key, op),
+                    ))
+                }
+            };
+            let number = value
+                .parse()
+                .map_err(|_| error(position, &format!("`{}` is not a number", value)))?;
+            Selector::Compare(metric, comparison, number)
+        }
+        other => return Err(error(position, &format!("unknown attribute `{}`", other))),
+    })
+}
+
+/// One whitespace-separated part of a selector string.
+enum Part {
+    Page(PageSelector),
+    Element(Selector),
+}
+
+fn error(position: usize, message: &str) -> SelectorError {
+    SelectorError::Syntax {
+        position,
+        message: message.to_string(),
+    }
+}
+
+/// Parses an API enum value such as `TEXT_BOX` of attribute `key`. Values the models don't
+/// know are rejected, so a typo doesn't silently match nothing.
+fn enum_value<T: DeserializeOwned>(
+    position: usize,
+    key: &str,
+    value: &str,
+    is_known: impl Fn(&T) -> bool,
+) -> Result<T, SelectorError> {
+    serde_json::from_value(serde_json::Value::String(value.to_string()))
+        .ok()
+        .filter(is_known)
+        .ok_or_else(|| error(position, &format!("unknown `{}` value `{}`", key, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::{
+        PageBuilder, PresentationBuilder, ShapeBuilder, TableBuilder, TextBuilder,
+    };
+    use crate::geometry::Emu;
+    use crate::models::group::Group;
+
+    fn deck() -> Presentation {
+        let mut group = ShapeBuilder::text_box("group_1")
+            .set_position(4_000_000.0, 0.0)
+            .build();
+        group.element_kind = PageElementKind::ElementGroup(Group {
+            children: vec![ShapeBuilder::new("star_1", ShapeType::Rectangle)
+                .set_size(1000.0, 1000.0)
+                .set_position(500.0, 0.0)
+                .set_title("Q3 highlight")
+                .build()],
+            extra: Default::default(),
+        });
+        PresentationBuilder::new("deck_1")
+            .add_slide(
+                PageBuilder::slide("slide_1")
+                    .add_element(
+                        ShapeBuilder::text_box("title_1")
+                            .set_placeholder(PlaceholderType::Title, None)
+                            .set_text(TextBuilder::new().add_paragraph("Welcome").build())
+                            .build(),
+                    )
+                    .build(),
+            )
+            .add_slide(
+                PageBuilder::slide("slide_2")
+                    .add_element(
+                        ShapeBuilder::text_box("title_2")
+                            .set_placeholder(PlaceholderType::Title, None)
+                            .set_text(TextBuilder::new().add_paragraph("Results").build())
+                            .build(),
+                    )
+                    .add_element(group)
+                    .add_element(
+                        TableBuilder::new("table_1", 1, 1)
+                            .set_cell_text(0, 0, TextBuilder::new().add_paragraph("42%").build())
+                            .build(),
+                    )
+                    .build(),
+            )
+            .build()
+    }
+
+    fn ids(presentation: &Presentation, selector: &str) -> Vec<String> {
+        query(presentation, selector)
+            .unwrap()
+            .into_iter()
+            .map(|found| found.element.object_id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_selectors_match_by_page_kind_text_and_geometry() {
+        let deck = deck();
+        assert_eq!(ids(&deck, "slide[2] shape[placeholder=TITLE]"), ["title_2"]);
+        assert_eq!(ids(&deck, "shape[text*=elc]"), ["title_1"]);
+        assert_eq!(ids(&deck, "*[text~=\"^\\d+%\"]"), ["table_1"]);
+        assert_eq!(
+            ids(&deck, "group[id=group_1] shape[type=RECTANGLE]"),
+            ["star_1"]
+        );
+        assert_eq!(
+            ids(&deck, "shape[title*=Q3][x>=4000000][width<2000]"),
+            ["star_1"]
+        );
+        assert_eq!(ids(&deck, "page[id=slide_1] *"), ["title_1"]);
+
+        let found = select_first(&deck, &Selector::object_id("star_1")).unwrap();
+        assert_eq!(found.page.object_id, "slide_2");
+        assert_eq!(found.page_index, 1);
+        assert_eq!(found.groups[0].object_id, "group_1");
+        assert_eq!(
+            found.bounds(),
+            Some(Rect {
+                x: Emu(4_000_500.0),
+                y: Emu(0.0),
+                width: Emu(1000.0),
+                height: Emu(1000.0)
+            })
+        );
+
+        let composed = Selector::on_slide(2)
+            .and(Selector::kind("shape"))
+            .and(Selector::ShapeType(ShapeType::TextBox).not())
+            .or(Selector::text_contains("Welcome"));
+        let found: Vec<_> = select(&deck, &composed)
+            .into_iter()
+            .map(|m| m.element.object_id.clone())
+            .collect();
+        assert_eq!(found, ["title_1", "star_1"]);
+
+        let error = Selector::parse("slide[2] shape[colour=red]").unwrap_err();
+        assert!(matches!(error, SelectorError::Syntax { position: 15, .. }));
+        assert!(Selector::parse("shape[text~=\"(\"]").is_err());
+    }
+
+    #[test]
+    fn test_text_equals_ignores_paragraph_terminator() {
+        let deck = PresentationBuilder::new("deck_1")
+            .add_slide(
+                PageBuilder::slide("slide_1")
+                    .add_element(
+                        ShapeBuilder::text_box("hello_1")
+                            .set_text(TextBuilder::new().add_paragraph("Hello").build())
+                            .build(),
+                    )
+                    .add_element(
+                        ShapeBuilder::text_box("hello_2")
+                            .set_text(TextBuilder::new().add_paragraph("Hello there").build())
+                            .build(),
+                    )
+                    .build(),
+            )
+            .build();
+        let found = select_first(&deck, &Selector::object_id("hello_1")).unwrap();
+        assert_eq!(found.text(), "Hello\n");
+        assert_eq!(ids(&deck, "shape[text=Hello]"), ["hello_1"]);
+        assert_eq!(ids(&deck, "shape[text=\"Hello there\"]"), ["hello_2"]);
+    }
+
+    #[test]
+    fn test_unknown_enum_values_are_rejected() {
+        let error = Selector::parse("slide[1] shape[placeholder=TITEL]").unwrap_err();
+        assert!(matches!(error, SelectorError::Syntax { position: 15, .. }));
+        assert!(error
+            .to_string()
+            .contains("unknown `placeholder` value `TITEL`"));
+        assert!(Selector::parse("shape[type=TEXTBOX]").is_err());
+        assert!(Selector::parse("shape[type=TEXT_BOX]").is_ok());
+        assert!(Selector::parse("shape[placeholder=TITLE]").is_ok());
+    }
+
+    #[test]
+    fn test_page_numbers_are_one_based() {
+        let error = Selector::parse("slide[0] shape").unwrap_err();
+        assert!(matches!(error, SelectorError::Syntax { position: 6, .. }));
+        assert!(Selector::parse("slide[1] shape").is_ok());
+    }
+}
@@ -0,0 +1,269 @@
+//! Retry and rate-limiting policies used by `SlidesClient`.
+//!
+//! Transient failures (HTTP 429, 5xx and connection errors) are retried with exponential
+//! backoff and jitter. A `Retry-After` header sent by the API takes precedence over the
+//! computed backoff, up to `RetryPolicy::max_backoff`.
+//!
+//! Only idempotent requests are retried on every transient failure. A 5xx or a timeout
+//! does not tell whether a write was applied, so non-idempotent requests are only retried
+//! on `429 Too Many Requests` (see `Idempotency`).
+
+use crate::errors::SlidesApiError;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Whether a request can safely be sent again when the outcome of an attempt is unknown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Idempotency {
+    /// Repeating the request has no further effect (reads, and writes guarded by
+    /// `WriteControl::required_revision_id`). Every transient failure is retried.
+    Idempotent,
+    /// The request may already have been applied when a 5xx or timeout is seen. Only
+    /// `429 Too Many Requests`, which is answered before any work is done, is retried.
+    NonIdempotent,
+    /// The request is never retried, e.g. Drive `files.copy`, where a duplicate creates
+    /// another file.
+    Never,
+}
+
+impl Idempotency {
+    /// The idempotency of a request that has no other guarantees than its HTTP method.
+    pub fn of_method(method: &reqwest::Method) -> Self {
+        if method.is_idempotent() {
+            Idempotency::Idempotent
+        } else {
+            Idempotency::NonIdempotent
+        }
+    }
+}
+
+/// Controls how failed requests are retried.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt. `0` disables retrying.
+    pub max_retries: u32,
+    /// Backoff before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper bound for the computed backoff and for delays requested by `Retry-After`.
+    pub max_backoff: Duration,
+    /// Factor the backoff grows by after each retry.
+    pub multiplier: f64,
+    /// HTTP statuses that are considered transient.
+    pub retryable_statuses: Vec<StatusCode>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(32),
+            multiplier: 2.0,
+            retryable_statuses: vec![
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Returns `true` if `error` is worth retrying under this policy.
+    pub fn is_retryable(&self, error: &SlidesApiError) -> bool {
+        match error {
+            SlidesApiError::ApiError { status, .. } => self.retryable_statuses.contains(status),
+            SlidesApiError::Network(err) => err.is_timeout() || err.is_connect(),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if `error` is worth retrying for a request of the given idempotency.
+    pub fn should_retry(&self, error: &SlidesApiError, idempotency: Idempotency) -> bool {
+        match idempotency {
+            Idempotency::Idempotent => self.is_retryable(error),
+            Idempotency::NonIdempotent => {
+                error.status() == Some(StatusCode::TOO_MANY_REQUESTS) && self.is_retryable(error)
+            }
+            Idempotency::Never => false,
+        }
+    }
+
+    /// Computes the delay before retry number `retry` (1-based), honouring a `Retry-After`
+    /// delay requested by the API but never waiting longer than `max_backoff`.
+    pub fn delay(&self, retry: u32, retry_after: Option<Duration>) -> Duration {
+        match retry_after {
+            Some(retry_after) => retry_after.min(self.max_backoff),
+            None => self.backoff(retry),
+        }
+    }
+
+    /// Computes the delay before retry number `retry` (1-based), with "equal jitter":
+    /// half of the exponential backoff is fixed and the other half is random.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponent = retry.saturating_sub(1).min(32) as i32;
+        let backoff = self
+            .initial_backoff
+            .mul_f64(self.multiplier.max(1.0).powi(exponent))
+            .min(self.max_backoff);
+        let half = backoff / 2;
+        half + half.mul_f64(random_unit())
+    }
+}
+
+/// Parses a `Retry-After` header, which is either a number of seconds or an HTTP date.
+pub(crate) fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// Returns a random number in `[0, 1)`, used for jitter.
+fn random_unit() -> f64 {
+    let mut buf = [0u8; 8];
+    if getrandom::getrandom(&mut buf).is_err() {
+        return 0.5;
+    }
+    (u64::from_le_bytes(buf) >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Spaces requests evenly so that a client stays below a request quota.
+///
+/// The limiter is shared by all clones of a `SlidesClient`, so concurrent calls
+/// queue up for the next free slot instead of bursting.
+#[derive(Debug)]
+pub struct RateLimiter {
+    interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    /// Allows at most one request every `interval`.
+    pub fn with_interval(interval: Duration) -> Self {
+        Self {
+            interval,
+            next_slot: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Allows at most `requests` requests per second.
+    pub fn per_second(requests: u32) -> Self {
+        Self::with_interval(Duration::from_secs(1) / requests.max(1))
+    }
+
+    /// Allows at most `requests` requests per minute (Slides quotas are expressed per minute).
+    pub fn per_minute(requests: u32) -> Self {
+        Self::with_interval(Duration::from_secs(60) / requests.max(1))
+    }
+
+    /// Waits until the next request slot is available and reserves it.
+    pub async fn acquire(&self) {
+        let wait = {
+            let mut next_slot = self.next_slot.lock().unwrap();
+            let now = Instant::now();
+            let slot = (*next_slot).max(now);
+            *next_slot = slot + self.interval;
+            slot - now
+        };
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_backoff_grows_and_is_capped() {
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(4),
+            ..Default::default()
+        };
+        for _ in 0..20 {
+            let first = policy.backoff(1);
+            assert!(first >= Duration::from_millis(500) && first <= Duration::from_secs(1));
+            let third = policy.backoff(3);
+            assert!(third >= Duration::from_secs(2) && third <= Duration::from_secs(4));
+            let tenth = policy.backoff(10);
+            assert!(tenth <= Duration::from_secs(4));
+        }
+    }
+
+    #[test]
+    fn test_retry_after_is_capped_at_max_backoff() {
+        let policy = RetryPolicy {
+            max_backoff: Duration::from_secs(4),
+            ..Default::default()
+        };
+        assert_eq!(
+            policy.delay(1, Some(Duration::from_secs(2))),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            policy.delay(1, Some(Duration::from_secs(3600))),
+            Duration::from_secs(4)
+        );
+        assert!(policy.delay(10, None) <= Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_non_idempotent_requests_only_retry_rate_limits() {
+        let policy = RetryPolicy::default();
+        let error = |status| SlidesApiError::ApiError {
+            status,
+            message: String::new(),
+        };
+        let unavailable = error(StatusCode::SERVICE_UNAVAILABLE);
+        let rate_limited = error(StatusCode::TOO_MANY_REQUESTS);
+
+        assert!(policy.should_retry(&unavailable, Idempotency::Idempotent));
+        assert!(policy.should_retry(&rate_limited, Idempotency::Idempotent));
+        assert!(!policy.should_retry(&unavailable, Idempotency::NonIdempotent));
+        assert!(policy.should_retry(&rate_limited, Idempotency::NonIdempotent));
+        assert!(!policy.should_retry(&rate_limited, Idempotency::Never));
+        assert!(!policy.should_retry(&error(StatusCode::NOT_FOUND), Idempotency::Idempotent));
+        assert_eq!(
+            Idempotency::of_method(&reqwest::Method::GET),
+            Idempotency::Idempotent
+        );
+        assert_eq!(
+            Idempotency::of_method(&reqwest::Method::POST),
+            Idempotency::NonIdempotent
+        );
+    }
+
+    #[test]
+    fn test_parse_retry_after_seconds_and_dates() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers), None);
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(7)));
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(parse_retry_after(&headers), Some(Duration::ZERO));
+    }
+}
@@ -0,0 +1,143 @@
+//! Opt-in hooks that observe raw API responses received by `SlidesClient`.
+//!
+//! Useful for capturing fixtures or debugging deserialization failures without
+//! the client itself touching the filesystem.
+
+use crate::parse::ParseProblem;
+use std::fs;
+use std::path::PathBuf;
+
+/// The result of deserializing a raw response body.
+#[derive(Debug)]
+pub enum ResponseOutcome<'a> {
+    /// The body was deserialized successfully.
+    Parsed,
+    /// The body could not be deserialized into the expected model. Names the JSON path
+    /// of the offending value.
+    DeserializationFailed(&'a ParseProblem),
+}
+
+/// Receives every raw presentation body fetched by a `SlidesClient`.
+pub trait ResponseSink: Send + Sync {
+    /// Called once per fetched presentation, after deserialization was attempted.
+    ///
+    /// # Arguments
+    /// * `presentation_id` - The ID of the presentation that was fetched.
+    /// * `body` - The raw JSON response body.
+    /// * `outcome` - Whether the body deserialized successfully.
+    fn on_response(&self, presentation_id: &str, body: &[u8], outcome: &ResponseOutcome<'_>);
+}
+
+/// Writes raw response bodies to files.
+///
+/// By default successful bodies go to `changed_presentation.json` and bodies that failed
+/// to deserialize go to `deserialization_error.json` in the working directory.
+/// Write failures are logged and never abort the fetch.
+#[derive(Debug, Clone)]
+pub struct FileDumpSink {
+    success_path: Option<PathBuf>,
+    error_path: Option<PathBuf>,
+}
+
+impl Default for FileDumpSink {
+    fn default() -> Self {
+        Self {
+            success_path: Some(PathBuf::from("changed_presentation.json")),
+            error_path: Some(PathBuf::from("deserialization_error.json")),
+        }
+    }
+}
+
+impl FileDumpSink {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the file successfully parsed bodies are written to. `None` skips them.
+    pub fn set_success_path(mut self, path: Option<PathBuf>) -> Self {
+        self.success_path = path;
+        self
+    }
+
+    /// Sets the file bodies that failed to deserialize are written to. `None` skips them.
+    pub fn set_error_path(mut self, path: Option<PathBuf>) -> Self {
+        self.error_path = path;
+        self
+    }
+}
+
+impl ResponseSink for FileDumpSink {
+    fn on_response(&self, presentation_id: &str, body: &[u8], outcome: &ResponseOutcome<'_>) {
+        let path = match outcome {
+            ResponseOutcome::Parsed => self.success_path.as_ref(),
+            ResponseOutcome::DeserializationFailed(e) => {
+                let json_snippet = String::from_utf8_lossy(&body[..body.len().min(500)]);
+                log::error!(
+                    "JSON Deserialization Error for presentation {}: {}\nFailing JSON snippet:\n{}",
+                    presentation_id,
+                    e,
+                    json_snippet
+                );
+                self.error_path.as_ref()
+            }
+        };
+        let Some(path) = path else {
+            return;
+        };
+        match fs::write(path, body) {
+            Ok(_) => log::info!(
+                "Raw JSON response body for presentation {} saved to '{}'.",
+                presentation_id,
+                path.display()
+            ),
+            Err(io_err) => log::warn!(
+                "Failed to write response body to file '{}': {}",
+                path.display(),
+                io_err
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::SlidesApiError;
+    use crate::parse::parse_presentation;
+
+    #[test]
+    fn test_file_dump_sink_writes_bodies_by_outcome() {
+        let dir = std::env::temp_dir().join(format!("gslides-sink-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let success_path = dir.join("ok.json");
+        let error_path = dir.join("error.json");
+        let sink = FileDumpSink::new()
+            .set_success_path(Some(success_path.clone()))
+            .set_error_path(Some(error_path.clone()));
+
+        let good = br#"{"presentationId": "deck"}"#;
+        sink.on_response("deck", good, &ResponseOutcome::Parsed);
+        assert_eq!(fs::read(&success_path).unwrap(), good);
+        assert!(!error_path.exists());
+
+        let bad = br#"{"presentationId": "deck", "slides": 1}"#;
+        let Err(SlidesApiError::PresentationParse(problem)) = parse_presentation(bad) else {
+            panic!("expected a parse error");
+        };
+        sink.on_response(
+            "deck",
+            bad,
+            &ResponseOutcome::DeserializationFailed(&problem),
+        );
+        assert_eq!(fs::read(&error_path).unwrap(), bad);
+        assert_eq!(fs::read(&success_path).unwrap(), good);
+
+        // A `None` path skips that kind of body.
+        fs::remove_file(&success_path).unwrap();
+        let sink = sink.set_success_path(None);
+        sink.on_response("deck", good, &ResponseOutcome::Parsed);
+        assert!(!success_path.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
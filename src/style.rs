@@ -0,0 +1,635 @@
+//! Resolves the effective text and paragraph styles of text.
+//!
+//! Text in a placeholder inherits from its parent placeholder on the layout or master.
+//! Every paragraph starts from the shape's first paragraph style over the parent's, and
+//! every run from the parent's default text style over the paragraph's bullet style.
+//! `StyleResolver` applies the same rules as the SVG renderer, so linters, exporters and
+//! diffs see the styles that are drawn. Theme colors are replaced with the RGB values of
+//! the page's color scheme (slide, then layout, then master). Properties that are unset
+//! everywhere stay `None`.
+//!
+//! ```
+//! use gslides_tools::style::StyleResolver;
+//! use gslides_tools::Presentation;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let presentation: Presentation =
+//!     serde_json::from_str(&std::fs::read_to_string("base_presentation.json")?)?;
+//! let resolver = StyleResolver::new(&presentation);
+//! let slide = &presentation.slides.as_ref().unwrap()[0];
+//! for element in slide.page_elements.iter().flatten() {
+//!     for run in resolver.resolve_runs(&element.object_id, None).unwrap_or_default() {
+//!         println!("{:?}: {:?}", run.content, run.text_style.font_family);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::models::colors::{ColorScheme, OpaqueColor, OpaqueColorContent, OptionalColor};
+use crate::models::elements::{PageElement, PageElementKind};
+use crate::models::page::{Page, PageType};
+use crate::models::presentation::Presentation;
+use crate::models::properties::{ParagraphStyle, TextStyle};
+use crate::models::text::TextContent;
+use crate::models::text_element::TextElementKind;
+use crate::theme::ThemeResolver;
+use crate::visit::{walk, VisitContext, Visitor};
+use log::{debug, warn};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Errors raised while resolving styles.
+#[derive(Error, Debug)]
+pub enum StyleError {
+    /// No page element has the object ID.
+    #[error("Page element not found: {0}")]
+    ObjectNotFound(String),
+
+    /// The element is not a shape or table, or the table has no such cell.
+    #[error("Element {object_id} has no text{}", cell_suffix(.cell))]
+    NoText {
+        object_id: String,
+        cell: Option<(usize, usize)>,
+    },
+}
+
+fn cell_suffix(cell: &Option<(usize, usize)>) -> String {
+    cell.map(|(row, column)| format!(" in cell ({}, {})", row, column))
+        .unwrap_or_default()
+}
+
+/// A text run with its fully resolved styles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedRun {
+    /// The UTF-16 index of the run's first character within its text.
+    pub start_index: u32,
+    /// The UTF-16 index after the run's last character.
+    pub end_index: u32,
+    pub content: String,
+    pub text_style: TextStyle,
+    /// The style of the paragraph the run belongs to.
+    pub paragraph_style: ParagraphStyle,
+    /// The list nesting level of the paragraph (0 outside lists).
+    pub nesting_level: i32,
+    /// The style of the paragraph's bullet glyph, if the paragraph has a bullet. The list's
+    /// style for the nesting level sits between the paragraph's base style and the bullet's
+    /// own style.
+    pub bullet_style: Option<TextStyle>,
+}
+
+/// Resolves effective styles for text anywhere in a presentation.
+#[derive(Debug)]
+pub struct StyleResolver<'a> {
+    theme: ThemeResolver<'a>,
+    /// Every page element, groups included, with the page it sits on.
+    elements: HashMap<&'a str, (&'a PageElement, &'a Page)>,
+}
+
+impl<'a> StyleResolver<'a> {
+    /// Indexes the pages and page elements of `presentation`.
+    pub fn new(presentation: &'a Presentation) -> Self {
+        #[derive(Default)]
+        struct Indexer<'a> {
+            page: Option<&'a Page>,
+            elements: HashMap<&'a str, (&'a PageElement, &'a Page)>,
+        }
+
+        impl<'a> Visitor<'a> for Indexer<'a> {
+            fn visit_page(&mut self, page: &'a Page, _page_type: &PageType) {
+                self.page = Some(page);
+            }
+
+            fn visit_element(&mut self, element: &'a PageElement, _context: &VisitContext<'_>) {
+                if let Some(page) = self.page {
+                    self.elements.insert(&element.object_id, (element, page));
+                }
+            }
+        }
+
+        let mut indexer = Indexer::default();
+        walk(presentation, &mut indexer);
+        Self {
+            theme: ThemeResolver::new(presentation),
+            elements: indexer.elements,
+        }
+    }
+
+    /// Resolves every run of a shape's text, or of a table cell's text.
+    ///
+    /// # Arguments
+    /// * `object_id` - The object ID of a shape or table.
+    /// * `cell` - The `(row, column)` of the cell for a table, `None` for a shape.
+    ///
+    /// # Returns
+    /// The runs (text runs and auto text) in order, or an error if the element does not
+    /// exist or has no such text. Text without runs gives an empty list.
+    pub fn resolve_runs(
+        &self,
+        object_id: &str,
+        cell: Option<(usize, usize)>,
+    ) -> Result<Vec<ResolvedRun>, StyleError> {
+        let (element, page) = self
+            .elements
+            .get(object_id)
+            .copied()
+            .ok_or_else(|| StyleError::ObjectNotFound(object_id.to_string()))?;
+        let no_text = || StyleError::NoText {
+            object_id: object_id.to_string(),
+            cell,
+        };
+        let (text, placeholder) = match (&element.element_kind, cell) {
+            (PageElementKind::Shape(shape), None) => {
+                let placeholder = shape
+                    .placeholder
+                    .as_ref()
+                    .and_then(|p| p.parent_object_id.as_deref())
+                    .and_then(|id| self.elements.get(id))
+                    .map(|(parent, _)| *parent);
+                (shape.text.as_ref(), placeholder)
+            }
+            (PageElementKind::Table(table), Some((row, column))) => {
+                let cell = table
+                    .table_rows
+                    .as_ref()
+                    .and_then(|rows| rows.get(row))
+                    .and_then(|r| r.table_cells.as_ref())
+                    .and_then(|cells| cells.get(column))
+                    .ok_or_else(no_text)?;
+                (cell.text.as_ref(), None)
+            }
+            _ => return Err(no_text()),
+        };
+        let Some(text) = text else {
+            return Ok(Vec::new());
+        };
+        let color_scheme = self.theme.color_scheme(page).map(|(scheme, _)| scheme);
+        Ok(self.resolve_text(text, placeholder, color_scheme))
+    }
+
+    /// Resolves the runs overlapping `[start_index, end_index)`, cut to that range.
+    pub fn resolve_range(
+        &self,
+        object_id: &str,
+        cell: Option<(usize, usize)>,
+        start_index: u32,
+        end_index: u32,
+    ) -> Result<Vec<ResolvedRun>, StyleError> {
+        let runs = self.resolve_runs(object_id, cell)?;
+        Ok(runs
+            .into_iter()
+            .filter(|run| run.start_index < end_index && start_index < run.end_index)
+            .map(|mut run| {
+                let from = start_index.max(run.start_index);
+                let to = end_index.min(run.end_index);
+                run.content = utf16_slice(
+                    &run.content,
+                    (from - run.start_index) as usize,
+                    (to - run.start_index) as usize,
+                );
+                run.start_index = from;
+                run.end_index = to;
+                run
+            })
+            .collect())
+    }
+
+    /// Resolves the styles of the character at UTF-16 `index`, if there is one.
+    pub fn resolve_at(
+        &self,
+        object_id: &str,
+        cell: Option<(usize, usize)>,
+        index: u32,
+    ) -> Result<Option<ResolvedRun>, StyleError> {
+        Ok(self
+            .resolve_range(object_id, cell, index, index + 1)?
+            .into_iter()
+            .next())
+    }
+
+    fn resolve_text(
+        &self,
+        text: &TextContent,
+        placeholder: Option<&PageElement>,
+        color_scheme: Option<&ColorScheme>,
+    ) -> Vec<ResolvedRun> {
+        let placeholder_text = placeholder.and_then(|p| match &p.element_kind {
+            PageElementKind::Shape(shape) => shape.text.as_ref(),
+            _ => None,
+        });
+        let base_text_style = placeholder
+            .and_then(get_placeholder_default_text_style)
+            .unwrap_or_default();
+        let initial_paragraph_style = merge_paragraph_styles(
+            first_paragraph_style(text),
+            placeholder_text.and_then(first_paragraph_style),
+        );
+
+        let mut runs = Vec::new();
+        let mut paragraph_style = initial_paragraph_style.clone();
+        let mut paragraph_text_style = base_text_style.clone();
+        let mut nesting_level = 0;
+        let mut bullet_style = None;
+        for element in text.text_elements.iter().flatten() {
+            let (content, style) = match &element.kind {
+                Some(TextElementKind::ParagraphMarker(marker)) => {
+                    paragraph_style = merge_paragraph_styles(
+                        marker.style.as_ref(),
+                        Some(&initial_paragraph_style),
+                    );
+                    let bullet = marker.bullet.as_ref();
+                    paragraph_text_style = merge_text_styles(
+                        bullet.and_then(|b| b.bullet_style.as_ref()),
+                        Some(&base_text_style),
+                    );
+                    nesting_level = bullet.and_then(|b| b.nesting_level).unwrap_or(0);
+                    bullet_style = bullet.map(|b| {
+                        let level_style = b.list_id.as_deref().and_then(|list_id| {
+                            nesting_level_style(text, list_id, nesting_level).or_else(|| {
+                                placeholder_text.and_then(|placeholder_text| {
+                                    nesting_level_style(placeholder_text, list_id, nesting_level)
+                                })
+                            })
+                        });
+                        let glyph_base = merge_text_styles(level_style, Some(&base_text_style));
+                        resolve_colors(
+                            merge_text_styles(b.bullet_style.as_ref(), Some(&glyph_base)),
+                            color_scheme,
+                        )
+                    });
+                    continue;
+                }
+                Some(TextElementKind::TextRun(run)) => (&run.content, &run.style),
+                Some(TextElementKind::AutoText(auto_text)) => {
+                    (&auto_text.content, &auto_text.style)
+                }
+                None => continue,
+            };
+            let start_index = element.start_index.unwrap_or(0);
+            runs.push(ResolvedRun {
+                start_index,
+                end_index: element.end_index.unwrap_or(start_index),
+                content: content.clone().unwrap_or_default(),
+                text_style: resolve_colors(
+                    merge_text_styles(style.as_ref(), Some(&paragraph_text_style)),
+                    color_scheme,
+                ),
+                paragraph_style: paragraph_style.clone(),
+                nesting_level,
+                bullet_style: bullet_style.clone(),
+            });
+        }
+        runs
+    }
+}
+
+/// Merges two `TextStyle` instances, where `specific_style` overrides `inherited_style`.
+/// Properties set in `specific_style` take precedence. If a property is `None` in
+/// `specific_style`, the value from `inherited_style` is used.
+///
+/// # Arguments
+/// * `specific_style` - The overriding style (e.g., from a TextRun).
+/// * `inherited_style` - The base style (e.g., from a ParagraphMarker bullet or placeholder).
+///
+/// # Returns
+/// A new `TextStyle` instance representing the merged style.
+pub fn merge_text_styles(
+    specific_style: Option<&TextStyle>,
+    inherited_style: Option<&TextStyle>,
+) -> TextStyle {
+    let mut merged = inherited_style.cloned().unwrap_or_default();
+    let Some(specific) = specific_style else {
+        return merged;
+    };
+    macro_rules! overwrite {
+        ($($field:ident),*) => {
+            $(
+                if specific.$field.is_some() {
+                    merged.$field = specific.$field.clone();
+                }
+            )*
+        };
+    }
+    overwrite!(
+        background_color,
+        baseline_offset,
+        bold,
+        font_family,
+        font_size,
+        foreground_color,
+        italic,
+        link,
+        small_caps,
+        strikethrough,
+        underline,
+        weighted_font_family
+    );
+    debug!("[merge_text_styles] Merged result: {:?}", merged);
+    merged
+}
+
+/// Merges two `ParagraphStyle` instances. Properties set in `specific_style` override
+/// those in `inherited_style`. If a property is `None` in `specific_style`,
+/// the value from `inherited_style` is used.
+///
+/// # Arguments
+/// * `specific_style` - The overriding style (e.g., from the shape or a specific paragraph marker).
+/// * `inherited_style` - The base style (e.g., from a placeholder or the initial call).
+///
+/// # Returns
+/// A new `ParagraphStyle` instance representing the merged style. Defaults if both are None.
+pub fn merge_paragraph_styles(
+    specific_style: Option<&ParagraphStyle>,
+    inherited_style: Option<&ParagraphStyle>,
+) -> ParagraphStyle {
+    let mut merged = inherited_style.cloned().unwrap_or_default();
+    let Some(specific) = specific_style else {
+        return merged;
+    };
+    macro_rules! overwrite {
+        ($($field:ident),*) => {
+            $(
+                if specific.$field.is_some() {
+                    merged.$field = specific.$field.clone();
+                }
+            )*
+        };
+    }
+    overwrite!(
+        alignment,
+        direction,
+        indent_end,
+        indent_first_line,
+        indent_start,
+        line_spacing,
+        space_above,
+        space_below,
+        spacing_mode
+    );
+    merged
+}
+
+/// Returns the style of the first paragraph marker that has one.
+pub(crate) fn first_paragraph_style(text: &TextContent) -> Option<&ParagraphStyle> {
+    text.text_elements
+        .iter()
+        .flatten()
+        .find_map(|element| match &element.kind {
+            Some(TextElementKind::ParagraphMarker(marker)) => marker.style.as_ref(),
+            _ => None,
+        })
+}
+
+/// Extracts the *default* text style from a placeholder element (typically a Shape).
+/// This is used as the base style for text within shapes that inherit from this placeholder.
+/// Uses the style of the first styled `TextRun`, which is usually the placeholder's base
+/// text style. Falls back to the bullet style of nesting level 0 of the first list used by
+/// a paragraph.
+///
+/// # Arguments
+/// * `placeholder_element` - The placeholder `PageElement` (likely a Shape) on the layout/master.
+///
+/// # Returns
+/// An `Option<TextStyle>` containing the cloned default style, or `None` if no style could be found.
+pub(crate) fn get_placeholder_default_text_style(
+    placeholder_element: &PageElement,
+) -> Option<TextStyle> {
+    let text = match &placeholder_element.element_kind {
+        PageElementKind::Shape(shape) => shape.text.as_ref()?,
+        _ => return None,
+    };
+    let elements = || text.text_elements.iter().flatten();
+    let first_run_style = elements().find_map(|element| match &element.kind {
+        Some(TextElementKind::TextRun(run)) => run.style.as_ref(),
+        _ => None,
+    });
+    if let Some(style) = first_run_style {
+        return Some(style.clone());
+    }
+    let list_id = elements().find_map(|element| match &element.kind {
+        Some(TextElementKind::ParagraphMarker(marker)) => {
+            marker.bullet.as_ref()?.list_id.as_deref()
+        }
+        _ => None,
+    });
+    let style = list_id.and_then(|list_id| nesting_level_style(text, list_id, 0));
+    if style.is_none() {
+        warn!(
+            "[get_placeholder_default_text_style] No default text style for placeholder '{}'.",
+            placeholder_element.object_id
+        );
+    }
+    style.cloned()
+}
+
+fn nesting_level_style<'t>(
+    text: &'t TextContent,
+    list_id: &str,
+    level: i32,
+) -> Option<&'t TextStyle> {
+    text.lists
+        .as_ref()?
+        .get(list_id)?
+        .nesting_level
+        .as_ref()?
+        .get(&level)?
+        .bullet_style
+        .as_ref()
+}
+
+/// Replaces theme colors found in `scheme` with their RGB values.
+fn resolve_colors(mut style: TextStyle, scheme: Option<&ColorScheme>) -> TextStyle {
+    let Some(scheme) = scheme else {
+        return style;
+    };
+    let resolve = |color: &mut Option<OptionalColor>| {
+        let Some(opaque) = color.as_mut().and_then(|c| c.opaque_color.as_mut()) else {
+            return;
+        };
+        if let OpaqueColorContent::ThemeColor(theme_color_type) = &opaque.color_kind {
+            if let Some(rgb) = scheme.rgb(theme_color_type) {
+                *opaque = OpaqueColor {
+                    color_kind: OpaqueColorContent::RgbColor(rgb.clone()),
+                    extra: Default::default(),
+                };
+            }
+        }
+    };
+    resolve(&mut style.foreground_color);
+    resolve(&mut style.background_color);
+    style
+}
+
+/// Returns the UTF-16 code units `[from, to)` of `text`.
+fn utf16_slice(text: &str, from: usize, to: usize) -> String {
+    let units: Vec<u16> = text.encode_utf16().collect();
+    String::from_utf16_lossy(&units[from.min(units.len())..to.min(units.len())])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::{PageBuilder, PresentationBuilder, ShapeBuilder, TextBuilder};
+    use crate::models::bullet::Bullet;
+    use crate::models::colors::{RgbColor, ThemeColorPair, ThemeColorType};
+    use crate::models::list::{List, NestingLevel};
+    use crate::models::page_properties::PageProperties;
+    use crate::models::placeholder::PlaceholderType;
+    use crate::models::properties::Alignment;
+
+    fn theme(color: ThemeColorType) -> Option<OptionalColor> {
+        Some(OptionalColor {
+            opaque_color: Some(OpaqueColor {
+                color_kind: OpaqueColorContent::ThemeColor(color),
+                extra: Default::default(),
+            }),
+            extra: Default::default(),
+        })
+    }
+
+    #[test]
+    fn test_resolves_placeholder_paragraph_list_and_theme_styles() {
+        let layout_text = TextBuilder::new()
+            .add_styled_run(
+                "Title",
+                TextStyle {
+                    font_family: Some("Lato".to_string()),
+                    foreground_color: theme(ThemeColorType::Accent1),
+                    ..Default::default()
+                },
+            )
+            .set_paragraph_style(ParagraphStyle {
+                alignment: Some(Alignment::Center),
+                ..Default::default()
+            })
+            .build();
+        let layout = PageBuilder::layout("layout_1")
+            .set_master("master_1")
+            .add_element(
+                ShapeBuilder::text_box("layout_title")
+                    .set_placeholder(PlaceholderType::Title, None)
+                    .set_text(layout_text)
+                    .build(),
+            )
+            .build();
+        let mut master = PageBuilder::master("master_1").build();
+        master.page_properties = Some(PageProperties {
+            page_background_fill: None,
+            color_scheme: Some(ColorScheme {
+                colors: vec![ThemeColorPair {
+                    theme_color_type: ThemeColorType::Accent1,
+                    color: RgbColor {
+                        red: Some(1.0),
+                        green: Some(0.0),
+                        blue: Some(0.0),
+                        extra: Default::default(),
+                    },
+                    extra: Default::default(),
+                }],
+                extra: Default::default(),
+            }),
+            extra: Default::default(),
+        });
+
+        let mut slide_text = TextBuilder::new()
+            .add_paragraph("Hello ")
+            .add_styled_run(
+                "world",
+                TextStyle {
+                    bold: Some(true),
+                    ..Default::default()
+                },
+            )
+            .add_paragraph("Item")
+            .set_bullet(Bullet {
+                list_id: Some("list_1".to_string()),
+                nesting_level: Some(1),
+                glyph: Some("○".to_string()),
+                bullet_style: None,
+                extra: Default::default(),
+            })
+            .build();
+        slide_text.lists = Some(
+            [(
+                "list_1".to_string(),
+                List {
+                    list_id: "list_1".to_string(),
+                    nesting_level: Some(
+                        [(
+                            1,
+                            NestingLevel {
+                                bullet_style: Some(TextStyle {
+                                    italic: Some(true),
+                                    ..Default::default()
+                                }),
+                                extra: Default::default(),
+                            },
+                        )]
+                        .into_iter()
+                        .collect(),
+                    ),
+                    extra: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        );
+        let presentation = PresentationBuilder::new("deck")
+            .add_master(master)
+            .add_layout(layout)
+            .add_slide(
+                PageBuilder::slide("slide_1")
+                    .set_layout("layout_1")
+                    .add_element(
+                        ShapeBuilder::text_box("title_1")
+                            .set_placeholder(
+                                PlaceholderType::Title,
+                                Some("layout_title".to_string()),
+                            )
+                            .set_text(slide_text)
+                            .build(),
+                    )
+                    .build(),
+            )
+            .build();
+
+        let resolver = StyleResolver::new(&presentation);
+        let runs = resolver.resolve_runs("title_1", None).unwrap();
+        let contents: Vec<_> = runs.iter().map(|r| r.content.as_str()).collect();
+        assert_eq!(contents, ["Hello ", "world\n", "Item\n"]);
+
+        let world = &runs[1];
+        assert_eq!(world.text_style.bold, Some(true));
+        assert_eq!(world.text_style.font_family.as_deref(), Some("Lato"));
+        assert_eq!(world.paragraph_style.alignment, Some(Alignment::Center));
+        let red = world
+            .text_style
+            .foreground_color
+            .as_ref()
+            .and_then(|c| c.opaque_color.as_ref())
+            .map(|c| &c.color_kind);
+        assert!(matches!(red, Some(OpaqueColorContent::RgbColor(rgb)) if rgb.red == Some(1.0)));
+
+        let item = &runs[2];
+        assert_eq!(item.nesting_level, 1);
+        assert_eq!(item.text_style.italic, None);
+        let bullet = item.bullet_style.as_ref().unwrap();
+        assert_eq!(bullet.italic, Some(true));
+        assert_eq!(bullet.font_family.as_deref(), Some("Lato"));
+
+        let cut = resolver.resolve_range("title_1", None, 8, 14).unwrap();
+        let contents: Vec<_> = cut.iter().map(|r| r.content.as_str()).collect();
+        assert_eq!(contents, ["rld\n", "It"]);
+        let at = resolver.resolve_at("title_1", None, 2).unwrap().unwrap();
+        assert_eq!((at.start_index, at.content.as_str()), (2, "l"));
+
+        assert!(matches!(
+            resolver.resolve_runs("missing", None),
+            Err(StyleError::ObjectNotFound(_))
+        ));
+        assert!(matches!(
+            resolver.resolve_runs("title_1", Some((0, 0))),
+            Err(StyleError::NoText { .. })
+        ));
+    }
+}
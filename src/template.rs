@@ -0,0 +1,492 @@
+//! Fills a template presentation from a data context.
+//!
+//! * `{{path.to.value}}` tokens in shapes and table cells are replaced with values from the
+//!   context, even when a token is split across several differently styled `TextRun`s.
+//! * An image whose `title` or `description` is a single token, e.g. `{{customer.logo}}`,
+//!   has its image replaced by the URL the token resolves to.
+//! * A slide containing `{{#each path}}` is repeated once per item of the list at `path`
+//!   (and deleted if the list is empty). On those slides, tokens are resolved against the
+//!   item first, then against the whole context; `{{this}}` is the item itself.
+//!
+//! The result is the filled model together with the equivalent `batchUpdate` requests, so a
+//! deck copied from the template (e.g. with `DriveClient::copy_file`) can be filled remotely.
+//!
+//! ```
+//! use gslides_tools::template::TemplateFiller;
+//! use gslides_tools::Presentation;
+//! use serde_json::json;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let template: Presentation = serde_json::from_str(r#"{"presentationId": "template"}"#)?;
+//! let filled = TemplateFiller::new().fill(&template, &json!({"customer": "ACME"}))?;
+//! println!("{} requests", filled.requests.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::engine::{collect_page_ids, object_ids, BatchUpdateEngine, EngineError};
+use crate::models::elements::{PageElement, PageElementKind};
+use crate::models::presentation::Presentation;
+use crate::models::requests::{
+    DeleteObjectRequest, DuplicateObjectRequest, ReplaceAllTextRequest, ReplaceImageRequest,
+    Request, SubstringMatchCriteria,
+};
+use crate::models::text::TextContent;
+use crate::models::text_element::TextElementKind;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+/// Errors raised while filling a template.
+#[derive(Error, Debug)]
+pub enum TemplateError {
+    /// The data context could not be serialized.
+    #[error("Failed to serialize the data context: {0}")]
+    Context(#[from] serde_json::Error),
+
+    /// A token refers to a value missing from the context.
+    #[error("No value for token '{token}' on page {page_id}")]
+    MissingValue { token: String, page_id: String },
+
+    /// A token resolves to an object or a list instead of a scalar.
+    #[error("Token '{token}' on page {page_id} does not resolve to a string, number or boolean")]
+    NotAScalar { token: String, page_id: String },
+
+    /// The path of an `{{#each}}` marker does not resolve to a list.
+    #[error("'{{{{#each {path}}}}}' on page {page_id} does not refer to a list")]
+    NotAList { path: String, page_id: String },
+
+    /// A slide has more than one `{{#each}}` marker.
+    #[error("Page {0} has more than one '{{{{#each}}}}' marker")]
+    MultipleRepeatMarkers(String),
+
+    /// The generated requests could not be applied to the template.
+    #[error("Failed to apply the template requests: {0}")]
+    Engine(#[from] EngineError),
+}
+
+/// A filled template.
+#[derive(Debug, Clone)]
+pub struct FilledPresentation {
+    /// The template with all requests applied.
+    pub presentation: Presentation,
+    /// The `batchUpdate` requests that turn the template into `presentation`.
+    pub requests: Vec<Request>,
+}
+
+/// Fills template presentations. See the module documentation for the token syntax.
+#[derive(Debug, Clone)]
+pub struct TemplateFiller {
+    allow_missing: bool,
+    id_prefix: String,
+}
+
+impl Default for TemplateFiller {
+    fn default() -> Self {
+        Self {
+            allow_missing: false,
+            id_prefix: "tpl_".to_string(),
+        }
+    }
+}
+
+impl TemplateFiller {
+    /// Creates a filler that fails on tokens missing from the context.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// If `true`, tokens missing from the context are replaced with an empty string
+    /// instead of failing.
+    pub fn set_allow_missing(mut self, allow_missing: bool) -> Self {
+        self.allow_missing = allow_missing;
+        self
+    }
+
+    /// Sets the prefix of the object IDs given to repeated slides and their elements.
+    pub fn set_id_prefix(mut self, id_prefix: impl Into<String>) -> Self {
+        self.id_prefix = id_prefix.into();
+        self
+    }
+
+    /// Fills `template` with values from `data`.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The template presentation; it is not modified.
+    /// * `data` - Any serde-serializable value, typically a struct or a `serde_json::Value`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the filled presentation and the equivalent requests.
+    pub fn fill<T: Serialize>(
+        &self,
+        template: &Presentation,
+        data: &T,
+    ) -> Result<FilledPresentation, TemplateError> {
+        let context = serde_json::to_value(data)?;
+        let mut used_ids = object_ids(template);
+        let mut next_id = 0u64;
+        let mut requests = Vec::new();
+        let mut instances = Vec::new();
+
+        for slide in template.slides.iter().flatten() {
+            let mut tokens = Vec::new();
+            let mut images = Vec::new();
+            scan_elements(
+                slide.page_elements.as_deref().unwrap_or_default(),
+                &mut tokens,
+                &mut images,
+            );
+            let markers: HashSet<&str> = tokens
+                .iter()
+                .filter_map(|token| repeat_path(token))
+                .collect();
+            if markers.len() > 1 {
+                return Err(TemplateError::MultipleRepeatMarkers(
+                    slide.object_id.clone(),
+                ));
+            }
+
+            let Some(path) = markers.into_iter().next() else {
+                instances.push(SlideInstance {
+                    page_id: slide.object_id.clone(),
+                    item: None,
+                    tokens,
+                    images,
+                });
+                continue;
+            };
+            let items = match lookup(&context, None, path) {
+                Some(Value::Array(items)) => items,
+                _ => {
+                    return Err(TemplateError::NotAList {
+                        path: path.to_string(),
+                        page_id: slide.object_id.clone(),
+                    })
+                }
+            };
+            if items.is_empty() {
+                requests.push(Request::DeleteObject(DeleteObjectRequest {
+                    object_id: slide.object_id.clone(),
+                }));
+                continue;
+            }
+
+            // Each duplicate is inserted right after the original, so duplicating for the
+            // last item first leaves the copies in list order.
+            let mut copies = Vec::new();
+            for item in items.iter().skip(1).rev() {
+                let mut slide_ids = HashSet::new();
+                collect_page_ids(slide, &mut slide_ids);
+                let mut slide_ids: Vec<String> = slide_ids.into_iter().collect();
+                slide_ids.sort();
+                let object_ids: HashMap<String, String> = slide_ids
+                    .into_iter()
+                    .map(|old_id| {
+                        let new_id = loop {
+                            next_id += 1;
+                            let candidate = format!("{}{}", self.id_prefix, next_id);
+                            if used_ids.insert(candidate.clone()) {
+                                break candidate;
+                            }
+                        };
+                        (old_id, new_id)
+                    })
+                    .collect();
+                requests.push(Request::DuplicateObject(DuplicateObjectRequest {
+                    object_id: slide.object_id.clone(),
+                    object_ids: Some(object_ids.clone()),
+                }));
+                copies.push(SlideInstance {
+                    page_id: object_ids[&slide.object_id].clone(),
+                    item: Some(item.clone()),
+                    tokens: tokens.clone(),
+                    images: images
+                        .iter()
+                        .map(|(element_id, token)| (object_ids[element_id].clone(), token.clone()))
+                        .collect(),
+                });
+            }
+            instances.push(SlideInstance {
+                page_id: slide.object_id.clone(),
+                item: items.first().cloned(),
+                tokens,
+                images,
+            });
+            instances.extend(copies.into_iter().rev());
+        }
+
+        for instance in &instances {
+            requests.extend(self.fill_requests(instance, &context)?);
+        }
+
+        let mut presentation = template.clone();
+        BatchUpdateEngine::new().apply(&mut presentation, &requests)?;
+        Ok(FilledPresentation {
+            presentation,
+            requests,
+        })
+    }
+
+    /// The text and image replacements of one (possibly repeated) slide.
+    fn fill_requests(
+        &self,
+        instance: &SlideInstance,
+        context: &Value,
+    ) -> Result<Vec<Request>, TemplateError> {
+        let mut requests = Vec::new();
+        let mut seen = HashSet::new();
+        for token in &instance.tokens {
+            if !seen.insert(token) {
+                continue;
+            }
+            let replace_text = match repeat_path(token) {
+                Some(_) => String::new(),
+                None => self.resolve(token, instance, context)?,
+            };
+            requests.push(Request::ReplaceAllText(ReplaceAllTextRequest {
+                replace_text,
+                page_object_ids: Some(vec![instance.page_id.clone()]),
+                contains_text: SubstringMatchCriteria {
+                    text: token.clone(),
+                    match_case: Some(true),
+                    search_by_regex: None,
+                },
+            }));
+        }
+        for (element_id, token) in &instance.images {
+            let url = self.resolve(token, instance, context)?;
+            if url.is_empty() {
+                continue;
+            }
+            requests.push(Request::ReplaceImage(ReplaceImageRequest {
+                image_object_id: element_id.clone(),
+                image_replace_method: None,
+                url,
+            }));
+        }
+        Ok(requests)
+    }
+
+    /// Resolves a raw token (`{{ path }}`) to its replacement text.
+    fn resolve(
+        &self,
+        token: &str,
+        instance: &SlideInstance,
+        context: &Value,
+    ) -> Result<String, TemplateError> {
+        match lookup(context, instance.item.as_ref(), token_path(token)) {
+            Some(Value::String(s)) => Ok(s.clone()),
+            Some(Value::Number(n)) => Ok(n.to_string()),
+            Some(Value::Bool(b)) => Ok(b.to_string()),
+            Some(Value::Null) => Ok(String::new()),
+            Some(_) => Err(TemplateError::NotAScalar {
+                token: token.to_string(),
+                page_id: instance.page_id.clone(),
+            }),
+            None if self.allow_missing => Ok(String::new()),
+            None => Err(TemplateError::MissingValue {
+                token: token.to_string(),
+                page_id: instance.page_id.clone(),
+            }),
+        }
+    }
+}
+
+/// A slide of the output and the data it is filled with.
+struct SlideInstance {
+    page_id: String,
+    /// The list item of a repeated slide.
+    item: Option<Value>,
+    /// Raw tokens (including braces) found in the slide's text.
+    tokens: Vec<String>,
+    /// Image element IDs and the raw token tagging them.
+    images: Vec<(String, String)>,
+}
+
+/// Collects the tokens in the text of `elements` and the images tagged with a token.
+fn scan_elements(
+    elements: &[PageElement],
+    tokens: &mut Vec<String>,
+    images: &mut Vec<(String, String)>,
+) {
+    for element in elements {
+        match &element.element_kind {
+            PageElementKind::Shape(shape) => scan_text(shape.text.as_ref(), tokens),
+            PageElementKind::Table(table) => table
+                .table_rows
+                .iter()
+                .flatten()
+                .flat_map(|row| row.table_cells.iter().flatten())
+                .for_each(|cell| scan_text(cell.text.as_ref(), tokens)),
+            PageElementKind::Image(_) => {
+                let tag = [&element.title, &element.description]
+                    .into_iter()
+                    .flatten()
+                    .map(|tag| tag.trim())
+                    .find(|tag| find_tokens(tag).first().map(String::as_str) == Some(tag));
+                if let Some(tag) = tag {
+                    images.push((element.object_id.clone(), tag.to_string()));
+                }
+            }
+            PageElementKind::ElementGroup(group) => scan_elements(&group.children, tokens, images),
+            _ => {}
+        }
+    }
+}
+
+/// Collects the tokens of a text. Runs are joined first, so tokens split across differently
+/// styled runs are found too.
+fn scan_text(text: Option<&TextContent>, tokens: &mut Vec<String>) {
+    let plain: String = text
+        .and_then(|t| t.text_elements.as_ref())
+        .into_iter()
+        .flatten()
+        .filter_map(|element| match &element.kind {
+            Some(TextElementKind::TextRun(run)) => run.content.as_deref(),
+            _ => None,
+        })
+        .collect();
+    tokens.extend(find_tokens(&plain));
+}
+
+/// Finds `{{...}}` tokens (with their braces) in `text`.
+fn find_tokens(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + length + 2;
+        let token = &rest[start..end];
+        if !token_path(token).is_empty() && !token[2..].contains("{{") {
+            tokens.push(token.to_string());
+            rest = &rest[end..];
+        } else {
+            rest = &rest[start + 2..];
+        }
+    }
+    tokens
+}
+
+/// The trimmed content of a raw token.
+fn token_path(token: &str) -> &str {
+    token.trim_start_matches("{{").trim_end_matches("}}").trim()
+}
+
+/// The list path of a `{{#each path}}` marker.
+fn repeat_path(token: &str) -> Option<&str> {
+    token_path(token)
+        .strip_prefix("#each")
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+}
+
+/// Resolves a dotted path (`customer.contacts.0.name`), first in the list item of a
+/// repeated slide, then in the whole context. `this` refers to the item.
+fn lookup<'a>(context: &'a Value, item: Option<&'a Value>, path: &str) -> Option<&'a Value> {
+    let segments: Vec<&str> = path.split('.').map(str::trim).collect();
+    let walk = |root: &'a Value, segments: &[&str]| {
+        segments
+            .iter()
+            .try_fold(root, |value, segment| match value {
+                Value::Object(map) => map.get(*segment),
+                Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+                _ => None,
+            })
+    };
+    match (item, segments.split_first()) {
+        (Some(item), Some((&"this", rest))) => walk(item, rest),
+        (Some(item), _) => walk(item, &segments).or_else(|| walk(context, &segments)),
+        (None, _) => walk(context, &segments),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn slide_texts(presentation: &Presentation) -> Vec<String> {
+        presentation
+            .slides
+            .iter()
+            .flatten()
+            .map(|slide| {
+                let mut texts = String::new();
+                for element in slide.page_elements.iter().flatten() {
+                    if let PageElementKind::Shape(shape) = &element.element_kind {
+                        for e in shape
+                            .text
+                            .iter()
+                            .flat_map(|t| t.text_elements.iter().flatten())
+                        {
+                            if let Some(TextElementKind::TextRun(run)) = &e.kind {
+                                texts.push_str(run.content.as_deref().unwrap_or_default());
+                            }
+                        }
+                    }
+                }
+                texts
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fill_split_tokens_and_repeated_slides() {
+        let template: Presentation = serde_json::from_str(
+            r#"{"presentationId": "template", "slides": [
+                {"objectId": "cover", "pageElements": [{"objectId": "title", "shape": {"text": {
+                    "textElements": [
+                        {"endIndex": 20, "paragraphMarker": {}},
+                        {"endIndex": 10, "textRun": {"content": "Hello {{cu"}},
+                        {"startIndex": 10, "endIndex": 20, "textRun": {"content": "stomer}}!\n",
+                            "style": {"bold": true}}}
+                    ]}}}, {"objectId": "logo", "title": "{{ logo }}", "image": {"contentUrl": "https://old"}}]},
+                {"objectId": "product", "pageElements": [{"objectId": "name", "shape": {"text": {
+                    "textElements": [
+                        {"endIndex": 44, "paragraphMarker": {}},
+                        {"endIndex": 44, "textRun": {"content": "{{#each products}}{{name}} for {{customer}}\n"}}
+                    ]}}}]}
+            ]}"#,
+        )
+        .unwrap();
+        let data = json!({
+            "customer": "ACME",
+            "logo": "https://example.com/acme.png",
+            "products": [{"name": "Rockets"}, {"name": "Anvils"}, {"name": "Magnets"}]
+        });
+
+        let filled = TemplateFiller::new().fill(&template, &data).unwrap();
+        assert_eq!(
+            slide_texts(&filled.presentation),
+            vec![
+                "Hello ACME!\n",
+                "Rockets for ACME\n",
+                "Anvils for ACME\n",
+                "Magnets for ACME\n"
+            ]
+        );
+        let cover = &filled.presentation.slides.as_ref().unwrap()[0];
+        let PageElementKind::Image(logo) = &cover.page_elements.as_ref().unwrap()[1].element_kind
+        else {
+            panic!("expected an image");
+        };
+        assert_eq!(
+            logo.content_url.as_deref(),
+            Some("https://example.com/acme.png")
+        );
+        assert!(filled
+            .requests
+            .iter()
+            .any(|r| matches!(r, Request::DuplicateObject(_))));
+
+        let error = TemplateFiller::new()
+            .fill(&template, &json!({"products": []}))
+            .unwrap_err();
+        assert!(matches!(error, TemplateError::MissingValue { .. }));
+    }
+}
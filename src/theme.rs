@@ -0,0 +1,254 @@
+//! Resolves colors, including theme color references, to concrete RGB values.
+//!
+//! A color either is an explicit RGB value or names a `ThemeColorType`. Theme colors come
+//! from the color scheme of the page they are used on: the page's own scheme, else its
+//! layout's, else its master's. `ThemeResolver` returns the RGB value together with the
+//! page whose scheme supplied it, so reports and audits can say where a color came from.
+//!
+//! ```
+//! use gslides_tools::models::colors::{OpaqueColor, OpaqueColorContent, ThemeColorType};
+//! use gslides_tools::theme::ThemeResolver;
+//! use gslides_tools::Presentation;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let presentation: Presentation =
+//!     serde_json::from_str(&std::fs::read_to_string("base_presentation.json")?)?;
+//! let resolver = ThemeResolver::new(&presentation);
+//! let slide = &presentation.slides.as_ref().unwrap()[0];
+//! let accent = OpaqueColor {
+//!     color_kind: OpaqueColorContent::ThemeColor(ThemeColorType::Accent1),
+//!     extra: Default::default(),
+//! };
+//! if let Some(color) = resolver.resolve(slide, &accent) {
+//!     println!("{} from {:?}", color.rgb.to_hex(), color.source);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::models::colors::{
+    ColorScheme, OpaqueColor, OpaqueColorContent, OptionalColor, RgbColor, ThemeColorType,
+};
+use crate::models::page::{Page, PageType};
+use crate::models::presentation::Presentation;
+use std::collections::HashMap;
+
+/// Where a resolved color came from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColorSource {
+    /// The color was given as RGB.
+    Explicit,
+    /// The color is a theme color, taken from the color scheme of a page.
+    Theme {
+        theme_color_type: ThemeColorType,
+        /// The role of the page that defines the scheme: the page itself, its layout or
+        /// its master.
+        page_type: PageType,
+        page_object_id: String,
+    },
+}
+
+/// A color resolved to RGB.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedColor {
+    pub rgb: RgbColor,
+    pub source: ColorSource,
+}
+
+/// Resolves colors used on the pages of a presentation.
+#[derive(Debug)]
+pub struct ThemeResolver<'a> {
+    pages: HashMap<&'a str, &'a Page>,
+}
+
+impl<'a> ThemeResolver<'a> {
+    /// Indexes the layouts and masters of `presentation`.
+    pub fn new(presentation: &'a Presentation) -> Self {
+        let pages = [&presentation.layouts, &presentation.masters]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|page| (page.object_id.as_str(), page))
+            .collect();
+        Self { pages }
+    }
+
+    /// Returns the color scheme that applies to `page` and the page that defines it.
+    pub fn color_scheme<'p>(&self, page: &'p Page) -> Option<(&'p ColorScheme, &'p Page)>
+    where
+        'a: 'p,
+    {
+        let owner = find_color_scheme_page(page, |id| self.pages.get(id).copied())?;
+        Some((page_color_scheme(owner)?, owner))
+    }
+
+    /// Resolves a color used on `page`.
+    ///
+    /// # Arguments
+    /// * `page` - The page the color is used on.
+    /// * `color` - The color to resolve.
+    ///
+    /// # Returns
+    /// The RGB value and its source, or `None` for a theme color that no scheme in the
+    /// page's hierarchy defines.
+    pub fn resolve(&self, page: &Page, color: &OpaqueColor) -> Option<ResolvedColor> {
+        match &color.color_kind {
+            OpaqueColorContent::RgbColor(rgb) => Some(ResolvedColor {
+                rgb: rgb.clone(),
+                source: ColorSource::Explicit,
+            }),
+            OpaqueColorContent::ThemeColor(theme_color_type) => {
+                let (scheme, owner) = self.color_scheme(page)?;
+                Some(ResolvedColor {
+                    rgb: scheme.rgb(theme_color_type)?.clone(),
+                    source: ColorSource::Theme {
+                        theme_color_type: theme_color_type.clone(),
+                        page_type: owner.page_type.clone().unwrap_or_default(),
+                        page_object_id: owner.object_id.clone(),
+                    },
+                })
+            }
+        }
+    }
+
+    /// Resolves an optional color used on `page`. A transparent color (no opaque color)
+    /// resolves to `None`, like an unresolvable theme color.
+    pub fn resolve_optional(&self, page: &Page, color: &OptionalColor) -> Option<ResolvedColor> {
+        self.resolve(page, color.opaque_color.as_ref()?)
+    }
+}
+
+/// Finds the page whose color scheme applies to `page`: the page itself, else its layout,
+/// else its master (named by the page, or by its layout). `lookup` finds layouts and
+/// masters by object ID.
+pub(crate) fn find_color_scheme_page<'p>(
+    page: &'p Page,
+    lookup: impl Fn(&str) -> Option<&'p Page>,
+) -> Option<&'p Page> {
+    if page_color_scheme(page).is_some() {
+        return Some(page);
+    }
+    let layout = page
+        .slide_properties
+        .as_ref()
+        .and_then(|p| p.layout_object_id.as_deref())
+        .and_then(&lookup);
+    if let Some(layout) = layout.filter(|l| page_color_scheme(l).is_some()) {
+        return Some(layout);
+    }
+    let master_id = page
+        .slide_properties
+        .as_ref()
+        .and_then(|p| p.master_object_id.as_deref())
+        .or_else(|| {
+            layout
+                .unwrap_or(page)
+                .layout_properties
+                .as_ref()
+                .and_then(|p| p.master_object_id.as_deref())
+        });
+    master_id
+        .and_then(lookup)
+        .filter(|master| page_color_scheme(master).is_some())
+}
+
+fn page_color_scheme(page: &Page) -> Option<&ColorScheme> {
+    page.page_properties.as_ref()?.color_scheme.as_ref()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::{PageBuilder, PresentationBuilder};
+    use crate::models::colors::ThemeColorPair;
+    use crate::models::page_properties::PageProperties;
+
+    fn with_scheme(mut page: Page, red: f32) -> Page {
+        page.page_properties = Some(PageProperties {
+            page_background_fill: None,
+            color_scheme: Some(ColorScheme {
+                colors: vec![ThemeColorPair {
+                    theme_color_type: ThemeColorType::Accent1,
+                    color: RgbColor {
+                        red: Some(red),
+                        green: None,
+                        blue: None,
+                        extra: Default::default(),
+                    },
+                    extra: Default::default(),
+                }],
+                extra: Default::default(),
+            }),
+            extra: Default::default(),
+        });
+        page
+    }
+
+    #[test]
+    fn test_theme_colors_resolve_through_slide_layout_and_master() {
+        let presentation = PresentationBuilder::new("deck")
+            .add_master(with_scheme(PageBuilder::master("master_1").build(), 1.0))
+            .add_layout(
+                PageBuilder::layout("layout_1")
+                    .set_master("master_1")
+                    .build(),
+            )
+            .add_slide(PageBuilder::slide("slide_1").set_layout("layout_1").build())
+            .add_slide(with_scheme(
+                PageBuilder::slide("slide_2").set_layout("layout_1").build(),
+                0.5,
+            ))
+            .build();
+        let resolver = ThemeResolver::new(&presentation);
+        let slides = presentation.slides.as_ref().unwrap();
+        let theme = |theme_color_type| OpaqueColor {
+            color_kind: OpaqueColorContent::ThemeColor(theme_color_type),
+            extra: Default::default(),
+        };
+
+        let from_master = resolver
+            .resolve(&slides[0], &theme(ThemeColorType::Accent1))
+            .unwrap();
+        assert_eq!(from_master.rgb.to_hex(), "#ff0000");
+        assert_eq!(
+            from_master.source,
+            ColorSource::Theme {
+                theme_color_type: ThemeColorType::Accent1,
+                page_type: PageType::Master,
+                page_object_id: "master_1".to_string(),
+            }
+        );
+        let from_slide = resolver
+            .resolve(&slides[1], &theme(ThemeColorType::Accent1))
+            .unwrap();
+        assert_eq!(from_slide.rgb.to_hex(), "#800000");
+        assert!(
+            matches!(from_slide.source, ColorSource::Theme { page_object_id, .. } if page_object_id == "slide_2")
+        );
+
+        assert!(resolver
+            .resolve(&slides[0], &theme(ThemeColorType::Accent2))
+            .is_none());
+        let transparent = OptionalColor {
+            opaque_color: None,
+            extra: Default::default(),
+        };
+        assert!(resolver
+            .resolve_optional(&slides[0], &transparent)
+            .is_none());
+        let explicit = OpaqueColor {
+            color_kind: OpaqueColorContent::RgbColor(RgbColor {
+                red: None,
+                green: Some(1.0),
+                blue: None,
+                extra: Default::default(),
+            }),
+            extra: Default::default(),
+        };
+        let resolved = resolver.resolve(&slides[0], &explicit).unwrap();
+        assert_eq!(
+            (resolved.rgb.to_hex().as_str(), resolved.source),
+            ("#00ff00", ColorSource::Explicit)
+        );
+    }
+}
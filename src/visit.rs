@@ -0,0 +1,546 @@
+//! Walks the presentation tree: pages, page elements (inside groups too), table cells,
+//! paragraphs and text runs.
+//!
+//! Implement `Visitor` to analyse a presentation or `VisitorMut` to edit it in place, then
+//! pass it to `walk` or `walk_mut`. Every callback has a default that does nothing. The
+//! callbacks below page level receive a `VisitContext` with the owning page, the groups
+//! around the element and its absolute transform.
+//!
+//! ```
+//! use gslides_tools::models::text_element::TextRun;
+//! use gslides_tools::visit::{walk, VisitContext, Visitor};
+//! use gslides_tools::Presentation;
+//!
+//! /// Counts the words on each slide.
+//! #[derive(Default)]
+//! struct WordCount(std::collections::HashMap<String, usize>);
+//!
+//! impl<'a> Visitor<'a> for WordCount {
+//!     fn visit_text_run(&mut self, run: &'a TextRun, context: &VisitContext<'_>) {
+//!         let words = run.content.as_deref().unwrap_or_default().split_whitespace().count();
+//!         *self.0.entry(context.page_object_id.to_string()).or_default() += words;
+//!     }
+//! }
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let presentation: Presentation =
+//!     serde_json::from_str(&std::fs::read_to_string("base_presentation.json")?)?;
+//! let mut count = WordCount::default();
+//! walk(&presentation, &mut count);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::geometry::Matrix;
+use crate::models::common::AffineTransform;
+use crate::models::elements::{PageElement, PageElementKind};
+use crate::models::page::{Page, PageType};
+use crate::models::presentation::Presentation;
+use crate::models::table::TableCell;
+use crate::models::text::TextContent;
+use crate::models::text_element::{ParagraphMarker, TextElementKind, TextRun};
+
+/// Where a visited element, cell, paragraph or text run sits in the presentation.
+#[derive(Debug, Clone)]
+pub struct VisitContext<'c> {
+    /// The object ID of the page that owns the node.
+    pub page_object_id: &'c str,
+    /// The role of the owning page. Notes pages are reached through their slides.
+    pub page_type: &'c PageType,
+    /// The object IDs of the groups around the element, outermost first.
+    pub groups: &'c [String],
+    /// The object ID of the page element the node belongs to.
+    pub element_object_id: &'c str,
+    /// The element's transform preconcatenated with those of its groups, with the
+    /// translation in EMU. This is where the element is drawn on the page.
+    pub transform: &'c AffineTransform,
+    /// The table cell holding the node, as `(row, column)`.
+    pub cell: Option<(usize, usize)>,
+    /// The `[start, end)` range of a paragraph or text run, in UTF-16 code units.
+    pub text_range: Option<(u32, u32)>,
+}
+
+/// Read-only callbacks for `walk`. `'a` is the lifetime of the presentation, so visitors
+/// can keep references to the nodes they see.
+#[allow(unused_variables)]
+pub trait Visitor<'a> {
+    /// Called for each page before its elements.
+    fn visit_page(&mut self, page: &'a Page, page_type: &PageType) {}
+
+    /// Called for each page element, including groups, before their contents.
+    fn visit_element(&mut self, element: &'a PageElement, context: &VisitContext<'_>) {}
+
+    /// Called for each table cell before its text.
+    fn visit_table_cell(&mut self, cell: &'a TableCell, context: &VisitContext<'_>) {}
+
+    /// Called for each paragraph marker, before the runs of its paragraph.
+    fn visit_paragraph(&mut self, paragraph: &'a ParagraphMarker, context: &VisitContext<'_>) {}
+
+    /// Called for each text run.
+    fn visit_text_run(&mut self, run: &'a TextRun, context: &VisitContext<'_>) {}
+}
+
+/// Editing callbacks for `walk_mut`.
+///
+/// The walk visits the children an element has after its callback returns, and recomputes
+/// the text indices of a shape or cell after its runs are visited, so run contents may
+/// change length.
+#[allow(unused_variables)]
+pub trait VisitorMut {
+    /// Called for each page before its elements.
+    fn visit_page_mut(&mut self, page: &mut Page, page_type: &PageType) {}
+
+    /// Called for each page element, including groups, before their contents.
+    fn visit_element_mut(&mut self, element: &mut PageElement, context: &VisitContext<'_>) {}
+
+    /// Called for each table cell before its text.
+    fn visit_table_cell_mut(&mut self, cell: &mut TableCell, context: &VisitContext<'_>) {}
+
+    /// Called for each paragraph marker, before the runs of its paragraph.
+    fn visit_paragraph_mut(&mut self, paragraph: &mut ParagraphMarker, context: &VisitContext<'_>) {
+    }
+
+    /// Called for each text run.
+    fn visit_text_run_mut(&mut self, run: &mut TextRun, context: &VisitContext<'_>) {}
+}
+
+/// Walks every slide (followed by its notes page), layout, master and the notes master.
+pub fn walk<'a, V: Visitor<'a> + ?Sized>(presentation: &'a Presentation, visitor: &mut V) {
+    for slide in presentation.slides.iter().flatten() {
+        walk_page(slide, &PageType::Slide, visitor);
+        let notes_page = slide
+            .slide_properties
+            .as_ref()
+            .and_then(|p| p.notes_page.as_deref());
+        if let Some(notes_page) = notes_page {
+            walk_page(notes_page, &PageType::Notes, visitor);
+        }
+    }
+    for layout in presentation.layouts.iter().flatten() {
+        walk_page(layout, &PageType::Layout, visitor);
+    }
+    for master in presentation.masters.iter().flatten() {
+        walk_page(master, &PageType::Master, visitor);
+    }
+    if let Some(notes_master) = &presentation.notes_master {
+        walk_page(notes_master, &PageType::NotesMaster, visitor);
+    }
+}
+
+/// Walks one page and its elements. Notes pages of slides are not included.
+pub fn walk_page<'a, V: Visitor<'a> + ?Sized>(
+    page: &'a Page,
+    page_type: &PageType,
+    visitor: &mut V,
+) {
+    visitor.visit_page(page, page_type);
+    let mut scope = Scope::new(&page.object_id, page_type);
+    walk_elements(page.page_elements.iter().flatten(), &mut scope, visitor);
+}
+
+/// Walks every page like `walk`, allowing edits.
+pub fn walk_mut<V: VisitorMut + ?Sized>(presentation: &mut Presentation, visitor: &mut V) {
+    for slide in presentation.slides.iter_mut().flatten() {
+        walk_page_mut(slide, &PageType::Slide, visitor);
+        let notes_page = slide
+            .slide_properties
+            .as_mut()
+            .and_then(|p| p.notes_page.as_deref_mut());
+        if let Some(notes_page) = notes_page {
+            walk_page_mut(notes_page, &PageType::Notes, visitor);
+        }
+    }
+    for layout in presentation.layouts.iter_mut().flatten() {
+        walk_page_mut(layout, &PageType::Layout, visitor);
+    }
+    for master in presentation.masters.iter_mut().flatten() {
+        walk_page_mut(master, &PageType::Master, visitor);
+    }
+    if let Some(notes_master) = &mut presentation.notes_master {
+        walk_page_mut(notes_master, &PageType::NotesMaster, visitor);
+    }
+}
+
+/// Walks one page and its elements like `walk_page`, allowing edits.
+pub fn walk_page_mut<V: VisitorMut + ?Sized>(
+    page: &mut Page,
+    page_type: &PageType,
+    visitor: &mut V,
+) {
+    visitor.visit_page_mut(page, page_type);
+    let Page {
+        object_id,
+        page_elements,
+        ..
+    } = page;
+    let mut scope = Scope::new(object_id, page_type);
+    walk_elements_mut(page_elements.iter_mut().flatten(), &mut scope, visitor);
+}
+
+/// The page and groups around the elements being walked.
+struct Scope<'c> {
+    page_object_id: &'c str,
+    page_type: &'c PageType,
+    groups: Vec<String>,
+    /// The absolute transform of the innermost group.
+    transform: AffineTransform,
+}
+
+impl<'c> Scope<'c> {
+    fn new(page_object_id: &'c str, page_type: &'c PageType) -> Self {
+        Self {
+            page_object_id,
+            page_type,
+            groups: Vec::new(),
+            transform: Matrix::IDENTITY.to_transform(),
+        }
+    }
+
+    fn context<'s>(
+        &'s self,
+        element_object_id: &'s str,
+        transform: &'s AffineTransform,
+    ) -> VisitContext<'s> {
+        VisitContext {
+            page_object_id: self.page_object_id,
+            page_type: self.page_type,
+            groups: &self.groups,
+            element_object_id,
+            transform,
+            cell: None,
+            text_range: None,
+        }
+    }
+
+    /// The absolute transform of an element of the current group.
+    fn absolute(&self, element: &PageElement) -> AffineTransform {
+        Matrix::from_transform(&self.transform)
+            .multiply(&Matrix::of_element(element))
+            .to_transform()
+    }
+
+    /// Walks a group's children with the group as the innermost scope.
+    fn enter<R>(
+        &mut self,
+        group_object_id: String,
+        transform: AffineTransform,
+        walk: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let outer = std::mem::replace(&mut self.transform, transform);
+        self.groups.push(group_object_id);
+        let result = walk(self);
+        self.groups.pop();
+        self.transform = outer;
+        result
+    }
+}
+
+fn walk_elements<'a, V: Visitor<'a> + ?Sized>(
+    elements: impl Iterator<Item = &'a PageElement>,
+    scope: &mut Scope<'_>,
+    visitor: &mut V,
+) {
+    for element in elements {
+        let transform = scope.absolute(element);
+        let context = scope.context(&element.object_id, &transform);
+        visitor.visit_element(element, &context);
+        match &element.element_kind {
+            PageElementKind::ElementGroup(group) => {
+                scope.enter(element.object_id.clone(), transform, |scope| {
+                    walk_elements(group.children.iter(), scope, visitor)
+                });
+            }
+            PageElementKind::Shape(shape) => {
+                if let Some(text) = &shape.text {
+                    walk_text(text, &context, visitor);
+                }
+            }
+            PageElementKind::Table(table) => {
+                for (row_index, row) in table.table_rows.iter().flatten().enumerate() {
+                    for (column_index, cell) in row.table_cells.iter().flatten().enumerate() {
+                        let context = VisitContext {
+                            cell: Some(cell_position(cell, row_index, column_index)),
+                            ..context.clone()
+                        };
+                        visitor.visit_table_cell(cell, &context);
+                        if let Some(text) = &cell.text {
+                            walk_text(text, &context, visitor);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn walk_text<'a, V: Visitor<'a> + ?Sized>(
+    text: &'a TextContent,
+    context: &VisitContext<'_>,
+    visitor: &mut V,
+) {
+    for element in text.text_elements.iter().flatten() {
+        let context = VisitContext {
+            text_range: Some((
+                element.start_index.unwrap_or(0),
+                element.end_index.unwrap_or(0),
+            )),
+            ..context.clone()
+        };
+        match &element.kind {
+            Some(TextElementKind::ParagraphMarker(marker)) => {
+                visitor.visit_paragraph(marker, &context)
+            }
+            Some(TextElementKind::TextRun(run)) => visitor.visit_text_run(run, &context),
+            _ => {}
+        }
+    }
+}
+
+fn walk_elements_mut<'a, V: VisitorMut + ?Sized>(
+    elements: impl Iterator<Item = &'a mut PageElement>,
+    scope: &mut Scope<'_>,
+    visitor: &mut V,
+) {
+    for element in elements {
+        let object_id = element.object_id.clone();
+        let transform = scope.absolute(element);
+        visitor.visit_element_mut(element, &scope.context(&object_id, &transform));
+        // The callback may have moved the element or replaced its contents.
+        let transform = scope.absolute(element);
+        let context = scope.context(&object_id, &transform);
+        match &mut element.element_kind {
+            PageElementKind::ElementGroup(group) => {
+                scope.enter(object_id, transform, |scope| {
+                    walk_elements_mut(group.children.iter_mut(), scope, visitor)
+                });
+            }
+            PageElementKind::Shape(shape) => {
+                if let Some(text) = &mut shape.text {
+                    walk_text_mut(text, &context, visitor);
+                }
+            }
+            PageElementKind::Table(table) => {
+                for (row_index, row) in table.table_rows.iter_mut().flatten().enumerate() {
+                    for (column_index, cell) in row.table_cells.iter_mut().flatten().enumerate() {
+                        let context = VisitContext {
+                            cell: Some(cell_position(cell, row_index, column_index)),
+                            ..context.clone()
+                        };
+                        visitor.visit_table_cell_mut(cell, &context);
+                        if let Some(text) = &mut cell.text {
+                            walk_text_mut(text, &context, visitor);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn walk_text_mut<V: VisitorMut + ?Sized>(
+    text: &mut TextContent,
+    context: &VisitContext<'_>,
+    visitor: &mut V,
+) {
+    for element in text.text_elements.iter_mut().flatten() {
+        let context = VisitContext {
+            text_range: Some((
+                element.start_index.unwrap_or(0),
+                element.end_index.unwrap_or(0),
+            )),
+            ..context.clone()
+        };
+        match &mut element.kind {
+            Some(TextElementKind::ParagraphMarker(marker)) => {
+                visitor.visit_paragraph_mut(marker, &context)
+            }
+            Some(TextElementKind::TextRun(run)) => visitor.visit_text_run_mut(run, &context),
+            _ => {}
+        }
+    }
+    reindex(text);
+}
+
+/// The cell's own location if it has one: cells spanning several columns are listed
+/// once, so the position in the row can differ from the column index.
+fn cell_position(cell: &TableCell, row_index: usize, column_index: usize) -> (usize, usize) {
+    let location = cell.location.as_ref();
+    let index = |value: Option<i32>, fallback| {
+        value
+            .and_then(|v| usize::try_from(v).ok())
+            .unwrap_or(fallback)
+    };
+    (
+        index(location.and_then(|l| l.row_index), row_index),
+        index(location.and_then(|l| l.column_index), column_index),
+    )
+}
+
+/// Recomputes `start_index`/`end_index` from the contents of runs and auto texts. Each
+/// paragraph marker spans the elements up to the next marker.
+fn reindex(text: &mut TextContent) {
+    let Some(elements) = &mut text.text_elements else {
+        return;
+    };
+    let mut index = 0u32;
+    let mut marker: Option<usize> = None;
+    for position in 0..elements.len() {
+        let len = match &elements[position].kind {
+            Some(TextElementKind::TextRun(run)) => utf16_len(run.content.as_deref()),
+            Some(TextElementKind::AutoText(auto_text)) => utf16_len(auto_text.content.as_deref()),
+            Some(TextElementKind::ParagraphMarker(_)) => {
+                if let Some(previous) = marker {
+                    elements[previous].end_index = Some(index);
+                }
+                marker = Some(position);
+                0
+            }
+            None => continue,
+        };
+        let element = &mut elements[position];
+        element.start_index = (index != 0).then_some(index);
+        index += len;
+        element.end_index = Some(index);
+    }
+    if let Some(previous) = marker {
+        elements[previous].end_index = Some(index);
+    }
+}
+
+fn utf16_len(content: Option<&str>) -> u32 {
+    content.unwrap_or_default().encode_utf16().count() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builders::{
+        PageBuilder, PresentationBuilder, ShapeBuilder, TableBuilder, TextBuilder,
+    };
+    use crate::models::group::Group;
+
+    /// A text run and the context it was visited with.
+    #[derive(Debug, PartialEq)]
+    struct RecordedRun {
+        content: String,
+        page_object_id: String,
+        groups: Vec<String>,
+        translate_x: Option<f64>,
+        cell: Option<(usize, usize)>,
+    }
+
+    impl RecordedRun {
+        fn new(
+            content: &str,
+            groups: &[&str],
+            translate_x: f64,
+            cell: Option<(usize, usize)>,
+        ) -> Self {
+            RecordedRun {
+                content: content.to_string(),
+                page_object_id: "slide_1".to_string(),
+                groups: groups.iter().map(|g| g.to_string()).collect(),
+                translate_x: Some(translate_x),
+                cell,
+            }
+        }
+    }
+
+    /// Records every text run with its context.
+    #[derive(Default)]
+    struct Runs(Vec<RecordedRun>);
+
+    impl<'a> Visitor<'a> for Runs {
+        fn visit_text_run(&mut self, run: &'a TextRun, context: &VisitContext<'_>) {
+            self.0.push(RecordedRun {
+                content: run.content.clone().unwrap_or_default(),
+                page_object_id: context.page_object_id.to_string(),
+                groups: context.groups.to_vec(),
+                translate_x: context.transform.translate_x,
+                cell: context.cell,
+            });
+        }
+    }
+
+    struct Shout;
+
+    impl VisitorMut for Shout {
+        fn visit_text_run_mut(&mut self, run: &mut TextRun, _context: &VisitContext<'_>) {
+            run.content = run.content.as_ref().map(|c| c.replace("Hi", "Hello"));
+        }
+    }
+
+    #[test]
+    fn test_walk_passes_context_and_walk_mut_reindexes_text() {
+        let text_box = ShapeBuilder::text_box("box_1")
+            .set_position(100.0, 0.0)
+            .set_text(
+                TextBuilder::new()
+                    .add_paragraph("Hi")
+                    .add_paragraph("you")
+                    .build(),
+            )
+            .build();
+        let mut group = ShapeBuilder::text_box("group_1")
+            .set_position(1000.0, 0.0)
+            .build();
+        group.element_kind = PageElementKind::ElementGroup(Group {
+            children: vec![text_box],
+            extra: Default::default(),
+        });
+        let table = TableBuilder::new("table_1", 1, 2)
+            .set_cell_text(0, 1, TextBuilder::new().add_paragraph("cell").build())
+            .build();
+        let mut presentation = PresentationBuilder::new("deck_1")
+            .add_slide(
+                PageBuilder::slide("slide_1")
+                    .add_element(group)
+                    .add_element(table)
+                    .build(),
+            )
+            .build();
+
+        let mut runs = Runs::default();
+        walk(&presentation, &mut runs);
+        assert_eq!(
+            runs.0,
+            vec![
+                RecordedRun::new("Hi\n", &["group_1"], 1100.0, None),
+                RecordedRun::new("you\n", &["group_1"], 1100.0, None),
+                RecordedRun::new("cell\n", &[], 0.0, Some((0, 1))),
+            ]
+        );
+
+        walk_mut(&mut presentation, &mut Shout);
+        let PageElementKind::ElementGroup(group) = &presentation.slides.as_ref().unwrap()[0]
+            .page_elements
+            .as_ref()
+            .unwrap()[0]
+            .element_kind
+        else {
+            panic!("expected a group");
+        };
+        let PageElementKind::Shape(shape) = &group.children[0].element_kind else {
+            panic!("expected a shape");
+        };
+        let ranges: Vec<_> = shape
+            .text
+            .as_ref()
+            .and_then(|t| t.text_elements.as_ref())
+            .unwrap()
+            .iter()
+            .map(|e| (e.start_index, e.end_index))
+            .collect();
+        assert_eq!(
+            ranges,
+            vec![
+                (None, Some(6)),
+                (None, Some(6)),
+                (Some(6), Some(10)),
+                (Some(6), Some(10))
+            ]
+        );
+    }
+}
@@ -0,0 +1,455 @@
+//! Polls presentations and streams a diff whenever one of them changes.
+//!
+//! Each poll only requests the presentation's `revisionId`. The full deck is fetched and
+//! compared against the previous copy with `Comparer` only when the revision has moved.
+//!
+//! ```no_run
+//! use futures_util::StreamExt;
+//! use gslides_tools::watch::PresentationWatcherBuilder;
+//! use std::time::Duration;
+//!
+//! # async fn run(client: gslides_tools::SlidesClient) -> Result<(), Box<dyn std::error::Error>> {
+//! let events = PresentationWatcherBuilder::new(client)
+//!     .add_presentation_id("YOUR_PRESENTATION_ID")
+//!     .set_interval(Duration::from_secs(60))
+//!     .build()?
+//!     .into_stream();
+//! let mut events = std::pin::pin!(events);
+//! while let Some(event) = events.next().await {
+//!     let event = event?;
+//!     println!("{}", event.comparison.get_readable_diff()?);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::SlidesClient;
+use crate::diff::comparer::{ComparerBuilder, ComparisonResult};
+use crate::diff::error::DiffError;
+use crate::errors::SlidesApiError;
+use crate::field_mask::{PresentationField, PresentationFieldMask};
+use crate::models::presentation::Presentation;
+use futures_util::stream::{self, Stream};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::time::Instant;
+
+/// A detected change of a watched presentation.
+pub struct WatchEvent {
+    /// The ID of the presentation that changed.
+    pub presentation_id: String,
+    /// The presentation as of the previous poll.
+    pub old: Presentation,
+    /// The presentation as of this poll.
+    pub new: Presentation,
+    /// The comparison of `old` against `new`.
+    pub comparison: ComparisonResult,
+}
+
+/// Errors yielded by the watcher stream. The watcher keeps polling after an error.
+#[derive(Error, Debug)]
+pub enum WatchError {
+    /// Fetching the presentation failed. The presentation is polled again after a backoff.
+    #[error("Failed to poll presentation {presentation_id}: {source}")]
+    Poll {
+        presentation_id: String,
+        source: SlidesApiError,
+    },
+
+    /// Comparing the old and new presentation failed.
+    #[error("Failed to diff presentation {presentation_id}: {source}")]
+    Diff {
+        presentation_id: String,
+        source: DiffError,
+    },
+}
+
+/// Builder for creating a `PresentationWatcher`.
+pub struct PresentationWatcherBuilder {
+    client: SlidesClient,
+    presentation_ids: Vec<String>,
+    interval: Duration,
+    max_backoff: Duration,
+    is_simplify: bool,
+}
+
+impl PresentationWatcherBuilder {
+    /// Creates a builder that polls through `client` every 30 seconds.
+    pub fn new(client: SlidesClient) -> Self {
+        Self {
+            client,
+            presentation_ids: Vec::new(),
+            interval: Duration::from_secs(30),
+            max_backoff: Duration::from_secs(600),
+            is_simplify: false,
+        }
+    }
+
+    /// Adds a presentation to watch.
+    pub fn add_presentation_id(mut self, presentation_id: impl Into<String>) -> Self {
+        self.presentation_ids.push(presentation_id.into());
+        self
+    }
+
+    /// Replaces the set of watched presentations.
+    pub fn set_presentation_ids<I, S>(mut self, presentation_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.presentation_ids = presentation_ids.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the time between two polls of the same presentation.
+    pub fn set_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Caps the backoff after failed polls. The delay doubles with every consecutive
+    /// failure of a presentation, starting from the poll interval.
+    pub fn set_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Produces simplified readable diffs (see `ComparerBuilder::set_simplify`).
+    pub fn set_simplify(mut self, is_simplify: bool) -> Self {
+        self.is_simplify = is_simplify;
+        self
+    }
+
+    /// Builds the `PresentationWatcher`.
+    /// Returns an error if no presentation was added or the interval is zero.
+    pub fn build(self) -> Result<PresentationWatcher, SlidesApiError> {
+        if self.presentation_ids.is_empty() {
+            return Err(SlidesApiError::InvalidInput(
+                "No presentation to watch".to_string(),
+            ));
+        }
+        if self.interval.is_zero() {
+            return Err(SlidesApiError::InvalidInput(
+                "Poll interval must not be zero".to_string(),
+            ));
+        }
+        let now = Instant::now();
+        Ok(PresentationWatcher {
+            client: self.client,
+            watched: self
+                .presentation_ids
+                .into_iter()
+                .map(|presentation_id| Watched {
+                    presentation_id,
+                    last: None,
+                    failures: 0,
+                    next_poll: now,
+                })
+                .collect(),
+            interval: self.interval,
+            max_backoff: self.max_backoff.max(self.interval),
+            is_simplify: self.is_simplify,
+        })
+    }
+}
+
+/// Polling state of one presentation.
+struct Watched {
+    presentation_id: String,
+    last: Option<Presentation>,
+    failures: u32,
+    next_poll: Instant,
+}
+
+/// Polls a set of presentations for revision changes.
+pub struct PresentationWatcher {
+    client: SlidesClient,
+    watched: Vec<Watched>,
+    interval: Duration,
+    max_backoff: Duration,
+    is_simplify: bool,
+}
+
+impl PresentationWatcher {
+    /// Turns the watcher into an endless stream of change events.
+    ///
+    /// The first poll of each presentation only records a baseline. Every later revision
+    /// change yields a `WatchEvent`; failed polls yield a `WatchError` and are retried
+    /// with backoff.
+    pub fn into_stream(self) -> impl Stream<Item = Result<WatchEvent, WatchError>> {
+        stream::unfold(self, |mut watcher| async move {
+            loop {
+                let index = watcher.next_due();
+                tokio::time::sleep_until(watcher.watched[index].next_poll).await;
+                if let Some(item) = watcher.poll(index).await {
+                    return Some((item, watcher));
+                }
+            }
+        })
+    }
+
+    /// The index of the presentation that is due next.
+    fn next_due(&self) -> usize {
+        self.watched
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, watched)| watched.next_poll)
+            .map(|(index, _)| index)
+            .unwrap_or_default()
+    }
+
+    /// Polls one presentation and schedules its next poll.
+    async fn poll(&mut self, index: usize) -> Option<Result<WatchEvent, WatchError>> {
+        let result = self.check(index).await;
+        let watched = &mut self.watched[index];
+        let delay = if result.as_ref().is_some_and(|r| r.is_err()) {
+            watched.failures += 1;
+            let backoff = backoff(self.interval, self.max_backoff, watched.failures);
+            log::warn!(
+                "Polling presentation {} failed {} time(s); next poll in {:?}.",
+                watched.presentation_id,
+                watched.failures,
+                backoff
+            );
+            backoff
+        } else {
+            watched.failures = 0;
+            self.interval
+        };
+        watched.next_poll = Instant::now() + delay;
+        result
+    }
+
+    /// Fetches a presentation if its revision moved and compares it with the last copy.
+    async fn check(&mut self, index: usize) -> Option<Result<WatchEvent, WatchError>> {
+        let presentation_id = self.watched[index].presentation_id.clone();
+        let poll_error = |source| WatchError::Poll {
+            presentation_id: presentation_id.clone(),
+            source,
+        };
+
+        // Without a known revision ID (read-only access) every poll fetches the full deck.
+        if let Some(last_revision) = self.watched[index]
+            .last
+            .as_ref()
+            .and_then(|last| last.revision_id.clone())
+        {
+            let mask = PresentationFieldMask::new().field(PresentationField::RevisionId);
+            match self
+                .client
+                .get_presentation_with_fields(&presentation_id, &mask)
+                .await
+            {
+                Ok(current) if current.revision_id.as_deref() == Some(last_revision.as_str()) => {
+                    return None;
+                }
+                Ok(_) => {}
+                Err(e) => return Some(Err(poll_error(e))),
+            }
+        }
+
+        let new = match self.client.get_presentation(&presentation_id).await {
+            Ok(new) => new,
+            Err(e) => return Some(Err(poll_error(e))),
+        };
+        let Some(old) = self.watched[index].last.replace(new.clone()) else {
+            log::info!(
+                "Watching presentation {} from revision {:?}.",
+                presentation_id,
+                new.revision_id
+            );
+            return None;
+        };
+
+        let comparison = ComparerBuilder::new()
+            .set_base(old.clone())
+            .set_simplify(self.is_simplify)
+            .build()
+            .and_then(|comparer| comparer.compare(&new));
+        let revision_changed = old.revision_id != new.revision_id;
+        match comparison {
+            Ok(comparison) if !revision_changed && comparison.get_structured_diff().is_empty() => {
+                None
+            }
+            Ok(comparison) => Some(Ok(WatchEvent {
+                presentation_id,
+                old,
+                new,
+                comparison,
+            })),
+            Err(source) => Some(Err(WatchError::Diff {
+                presentation_id,
+                source,
+            })),
+        }
+    }
+}
+
+/// The delay before the next poll after `failures` consecutive failed polls: the poll
+/// interval, doubled with every further failure and capped at `max_backoff`.
+fn backoff(interval: Duration, max_backoff: Duration, failures: u32) -> Duration {
+    let exponent = failures.saturating_sub(1).min(16);
+    interval
+        .saturating_mul(2u32.saturating_pow(exponent))
+        .min(max_backoff)
+}
+
+#[cfg(test)]
+#[cfg(feature = "emulator")]
+mod tests {
+    use super::*;
+    use crate::auth::StaticTokenProvider;
+    use crate::emulator::SlidesEmulator;
+    use crate::retry::RetryPolicy;
+    use futures_util::StreamExt;
+    use reqwest::StatusCode;
+    use std::time::Instant as StdInstant;
+
+    const INTERVAL: Duration = Duration::from_millis(20);
+
+    async fn start_emulator() -> SlidesEmulator {
+        SlidesEmulator::builder()
+            .add_presentation_json(
+                r#"{"presentationId": "deck", "revisionId": "rev_1", "title": "Draft"}"#,
+            )
+            .unwrap()
+            .start()
+            .await
+            .unwrap()
+    }
+
+    fn watcher_for(emulator: &SlidesEmulator, max_backoff: Duration) -> PresentationWatcher {
+        let client = SlidesClient::builder()
+            .set_token_provider(StaticTokenProvider::new("test-token"))
+            .set_base_url(emulator.base_url())
+            .set_retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap();
+        PresentationWatcherBuilder::new(client)
+            .add_presentation_id("deck")
+            .set_interval(INTERVAL)
+            .set_max_backoff(max_backoff)
+            .build()
+            .unwrap()
+    }
+
+    /// Waits until the emulator has received at least `count` requests.
+    async fn wait_for_requests(emulator: &SlidesEmulator, count: usize) {
+        while emulator.request_count() < count {
+            tokio::time::sleep(Duration::from_millis(2)).await;
+        }
+    }
+
+    #[test]
+    fn test_backoff_starts_at_interval_and_is_capped() {
+        let interval = Duration::from_secs(1);
+        let max_backoff = Duration::from_secs(5);
+        let delays: Vec<u64> = (1..=5)
+            .map(|failures| backoff(interval, max_backoff, failures).as_secs())
+            .collect();
+        assert_eq!(delays, vec![1, 2, 4, 5, 5]);
+        assert_eq!(backoff(interval, max_backoff, u32::MAX), max_backoff);
+    }
+
+    #[tokio::test]
+    async fn test_revision_change_yields_event() {
+        let emulator = start_emulator().await;
+        let events = watcher_for(&emulator, Duration::from_secs(1)).into_stream();
+        let mut events = std::pin::pin!(events);
+
+        let (event, _) = tokio::join!(events.next(), async {
+            // Change the deck once the baseline has been fetched.
+            wait_for_requests(&emulator, 1).await;
+            emulator
+                .insert_presentation_json(
+                    r#"{"presentationId": "deck", "revisionId": "rev_2", "title": "Final"}"#,
+                )
+                .unwrap();
+        });
+        let event = event.unwrap().unwrap();
+        assert_eq!(event.presentation_id, "deck");
+        assert_eq!(event.old.title.as_deref(), Some("Draft"));
+        assert_eq!(event.new.title.as_deref(), Some("Final"));
+        assert!(!event.comparison.get_structured_diff().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_revision_only_polls_revision_id() {
+        let emulator = start_emulator().await;
+        let events = watcher_for(&emulator, Duration::from_secs(1)).into_stream();
+        let mut events = std::pin::pin!(events);
+
+        let next = tokio::time::timeout(INTERVAL * 10, events.next()).await;
+        assert!(next.is_err(), "no event expected for an unchanged deck");
+
+        let requests = emulator.requests();
+        assert!(requests.len() >= 3);
+        assert_eq!(requests[0].query_param("fields"), None);
+        for request in &requests[1..] {
+            assert_eq!(
+                request.query_param("fields").as_deref(),
+                Some("presentationId,revisionId")
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_poll_error_then_recovery() {
+        let emulator = start_emulator().await;
+        emulator.fail_next(1, StatusCode::SERVICE_UNAVAILABLE);
+        let events = watcher_for(&emulator, Duration::from_secs(1)).into_stream();
+        let mut events = std::pin::pin!(events);
+
+        let error = events.next().await.unwrap().err().unwrap();
+        let WatchError::Poll {
+            presentation_id,
+            source,
+        } = error
+        else {
+            panic!("expected a poll error");
+        };
+        assert_eq!(presentation_id, "deck");
+        assert_eq!(source.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+
+        // The next poll records the baseline, so later changes are reported again.
+        let (event, _) = tokio::join!(events.next(), async {
+            wait_for_requests(&emulator, 2).await;
+            emulator
+                .insert_presentation_json(
+                    r#"{"presentationId": "deck", "revisionId": "rev_2", "title": "Final"}"#,
+                )
+                .unwrap();
+        });
+        let event = event.unwrap().unwrap();
+        assert_eq!(event.old.title.as_deref(), Some("Draft"));
+        assert_eq!(event.new.title.as_deref(), Some("Final"));
+    }
+
+    #[tokio::test]
+    async fn test_backoff_between_failed_polls_is_capped() {
+        let emulator = start_emulator().await;
+        let max_backoff = INTERVAL * 3;
+        emulator.fail_next(6, StatusCode::SERVICE_UNAVAILABLE);
+        let events = watcher_for(&emulator, max_backoff).into_stream();
+        let mut events = std::pin::pin!(events);
+
+        let mut failed_at = Vec::new();
+        for _ in 0..6 {
+            assert!(events.next().await.unwrap().is_err());
+            failed_at.push(StdInstant::now());
+        }
+        let gaps: Vec<Duration> = failed_at.windows(2).map(|w| w[1] - w[0]).collect();
+        // Interval, 2x, then capped at 3x (uncapped, the last gap would be 16x).
+        let expected = [
+            INTERVAL,
+            INTERVAL * 2,
+            max_backoff,
+            max_backoff,
+            max_backoff,
+        ];
+        for (gap, expected) in gaps.iter().zip(expected) {
+            assert!(*gap >= expected, "gap {:?} < {:?}", gap, expected);
+        }
+        assert!(gaps[4] < INTERVAL * 10, "gap {:?} is not capped", gaps[4]);
+    }
+}